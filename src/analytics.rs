@@ -0,0 +1,228 @@
+// analytics.rs
+
+use chrono::{DateTime, Duration as Days, NaiveDate, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::config::AnalyticsConfig;
+use crate::ui::handler::Page;
+
+// One line of the analytics file, written when a session ends
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub started: DateTime<Utc>,
+    pub duration_secs: u64,
+    // Salted hash of the client address, never the address itself
+    pub visitor: Option<String>,
+    pub term: Option<String>,
+    pub width: u16,
+    pub height: u16,
+    pub pages: Vec<PageView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageView {
+    pub page: String,
+    pub secs: u64,
+}
+
+// Which pages were opened and how long each stayed on screen
+#[derive(Debug)]
+pub struct Tracker {
+    views: Vec<PageView>,
+    current: (Page, Instant),
+}
+
+impl Tracker {
+    pub fn new(page: Page) -> Self {
+        Self { views: Vec::new(), current: (page, Instant::now()) }
+    }
+
+    pub fn enter(&mut self, page: Page) {
+        self.close_current();
+        self.current = (page, Instant::now());
+    }
+
+    // Every view so far, counting the page still on screen until now
+    pub fn finish(mut self) -> Vec<PageView> {
+        self.close_current();
+        self.views
+    }
+
+    fn close_current(&mut self) {
+        let (page, since) = self.current;
+        self.views.push(PageView { page: page.label().to_string(), secs: since.elapsed().as_secs() });
+    }
+}
+
+// Short, stable per-salt id for an address. Without the salt the hash can
+// not be reversed by trying every IPv4 address.
+pub fn hash_visitor(salt: &[u8], address: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(address.as_bytes());
+    hasher.finalize()[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Reads the salt, creating a random one readable only by the owner on first use
+pub fn load_or_create_salt(path: &Path) -> io::Result<Vec<u8>> {
+    match fs::read(path) {
+        Ok(salt) if !salt.is_empty() => return Ok(salt),
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let salt: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+    // The salt is written in full before it is linked into place, so when two
+    // sessions start at once the loser reads the winner's salt, never half of it
+    let temp = path.with_extension(format!("{}-{}.tmp", std::process::id(), &salt[..8]));
+    let mut file = OpenOptions::new().write(true).create(true).truncate(true).mode(0o600).open(&temp)?;
+    let linked = file.write_all(salt.as_bytes()).and_then(|_| fs::hard_link(&temp, path));
+    let _ = fs::remove_file(&temp);
+    match linked {
+        Ok(()) => Ok(salt.into_bytes()),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => match fs::read(path)? {
+            salt if salt.is_empty() => Err(io::Error::new(io::ErrorKind::InvalidData, format!("{} is empty", path.display()))),
+            salt => Ok(salt),
+        },
+        Err(e) => Err(e),
+    }
+}
+
+pub fn record(config: &AnalyticsConfig, record: &SessionRecord) -> io::Result<()> {
+    let mut line = serde_json::to_string(record).map_err(io::Error::other)?;
+    line.push('\n');
+    let mut file = OpenOptions::new().create(true).append(true).mode(0o600).open(&config.file)?;
+    // One write per line so concurrent sessions do not interleave
+    file.write_all(line.as_bytes())
+}
+
+// Every readable record, skipping lines a crash may have cut short
+pub fn load(path: &Path) -> io::Result<Vec<SessionRecord>> {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut records = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(record) = serde_json::from_str(&line?) {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DayStats {
+    pub visitors: usize,
+    pub sessions: usize,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PageStats {
+    pub views: usize,
+    pub secs: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub days: i64,
+    pub sessions: usize,
+    pub visitors: usize,
+    pub average_secs: u64,
+    pub daily: BTreeMap<NaiveDate, DayStats>,
+    pub pages: Vec<(String, PageStats)>,
+    pub terminals: Vec<(String, usize)>,
+}
+
+// Sums up the sessions started in the last `days` days before `now`
+pub fn report(records: &[SessionRecord], days: i64, now: DateTime<Utc>) -> Report {
+    let since = now - Days::days(days);
+    let records: Vec<&SessionRecord> = records.iter().filter(|r| r.started > since && r.started <= now).collect();
+
+    let mut daily_visitors: BTreeMap<NaiveDate, HashSet<&str>> = BTreeMap::new();
+    let mut daily: BTreeMap<NaiveDate, DayStats> = BTreeMap::new();
+    let mut pages: BTreeMap<&str, PageStats> = BTreeMap::new();
+    let mut terminals: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut visitors = HashSet::new();
+    for record in &records {
+        let day = record.started.date_naive();
+        daily.entry(day).or_default().sessions += 1;
+        if let Some(visitor) = &record.visitor {
+            daily_visitors.entry(day).or_default().insert(visitor);
+            visitors.insert(visitor.as_str());
+        }
+        for view in &record.pages {
+            let stats = pages.entry(&view.page).or_default();
+            stats.views += 1;
+            stats.secs += view.secs;
+        }
+        *terminals.entry(record.term.as_deref().unwrap_or("unknown")).or_default() += 1;
+    }
+    for (day, stats) in daily.iter_mut() {
+        stats.visitors = daily_visitors.get(day).map_or(0, HashSet::len);
+    }
+
+    let total: u64 = records.iter().map(|r| r.duration_secs).sum();
+    let mut pages: Vec<(String, PageStats)> = pages.into_iter().map(|(p, s)| (p.to_string(), s)).collect();
+    pages.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.views));
+    let mut terminals: Vec<(String, usize)> = terminals.into_iter().map(|(t, n)| (t.to_string(), n)).collect();
+    terminals.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+    Report {
+        days,
+        sessions: records.len(),
+        visitors: visitors.len(),
+        average_secs: total.checked_div(records.len() as u64).unwrap_or(0),
+        daily,
+        pages,
+        terminals,
+    }
+}
+
+// e.g. "2m 05s"
+fn duration(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Last {} days", self.days)?;
+        writeln!(f, "  Sessions          {}", self.sessions)?;
+        writeln!(f, "  Unique visitors   {}", self.visitors)?;
+        writeln!(f, "  Average session   {}", duration(self.average_secs))?;
+
+        writeln!(f, "\nDaily visitors")?;
+        for (day, stats) in &self.daily {
+            writeln!(f, "  {}  {:>5} visitors  {:>5} sessions", day, stats.visitors, stats.sessions)?;
+        }
+
+        writeln!(f, "\nPages")?;
+        for (page, stats) in &self.pages {
+            let average = stats.secs.checked_div(stats.views as u64).unwrap_or(0);
+            writeln!(f, "  {:<12} {:>5} views  {:>8} avg", page, stats.views, duration(average))?;
+        }
+
+        writeln!(f, "\nTerminals")?;
+        for (term, count) in &self.terminals {
+            writeln!(f, "  {:<20} {:>5}", term, count)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+// cli.rs
+
+use std::path::PathBuf;
+
+// What the binary was asked to do. Visitors never get here with arguments:
+// sshd runs a login shell as `shell -c <command>` and a forced command with
+// the visitor's command in SSH_ORIGINAL_COMMAND, both of which are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Portfolio,
+    Stats { days: i64 },
+    Guestbook(Moderation),
+    Inbox(Mailbox),
+    // The web terminal, on `listen` or the configured address
+    Serve { listen: Option<String> },
+    Export(Export),
+    SyncGithub(Sync),
+    SyncContributions,
+    Help,
+}
+
+// Where `sync-github` takes the repositories from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sync {
+    // A saved API response instead of fetching one
+    pub from: Option<PathBuf>,
+    // Instead of `[github] user`
+    pub user: Option<String>,
+}
+
+// Where `export` writes each format, at least one is set
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Export {
+    pub html: Option<PathBuf>,
+    pub markdown: Option<PathBuf>,
+    // Files, "-" for stdout
+    pub json: Option<PathBuf>,
+    pub resume: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mailbox {
+    List { unread: bool },
+    Read(u64),
+    Delete(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Moderation {
+    List { pending: bool },
+    Approve(u64),
+    Delete(u64),
+}
+
+pub const USAGE: &str = "\
+Usage: ssh_medaly [COMMAND]
+
+Without a command, shows the portfolio.
+
+Commands (for the owner, not reachable over SSH):
+  stats [--days N]    Visitors, pages and terminals of the last N days (default 30)
+  guestbook list [--pending]
+                      Guestbook entries, or only those waiting for approval
+  guestbook approve ID
+                      Publish a guestbook entry
+  guestbook delete ID Remove a guestbook entry
+  inbox [--unread]    Messages sent with the contact form, newest first
+  inbox read ID       Print a message and mark it as read
+  inbox delete ID     Remove a message
+  serve [--listen ADDR]
+                      Serve the pages over HTTP (ANSI for curl, HTML for
+                      browsers) and the portfolio in a browser terminal
+  export [--html DIR] [--markdown DIR] [--json FILE] [--resume FILE]
+                      Write the pages as a static website, as Markdown files,
+                      as one JSON document, and About and Contact as a JSON
+                      Resume; FILE can be - for stdout
+  sync-github [--user NAME] [--from FILE]
+                      Save NAME's public GitHub repositories (default
+                      `[github] user`) for the Repos page, or those of an
+                      API response saved in FILE
+  sync-contributions  Count the last year of commits in `[contributions] repos`
+                      into `[contributions] file` for the Home page
+  help                Show this message
+";
+
+pub fn parse(args: &[String], forced_command: bool) -> Result<Command, String> {
+    let mut args = args.iter().map(String::as_str);
+    let command = match args.next() {
+        None => return Ok(Command::Portfolio),
+        // Login shell invocation, whatever the visitor typed follows
+        Some("-c") => return Ok(Command::Portfolio),
+        Some(_) if forced_command => return Ok(Command::Portfolio),
+        Some(command) => command,
+    };
+
+    match command {
+        "stats" => {
+            let mut days = 30;
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--days" => {
+                        days = args
+                            .next()
+                            .and_then(|n| n.parse().ok())
+                            .filter(|n| *n > 0)
+                            .ok_or("--days needs a positive number")?;
+                    }
+                    other => return Err(format!("unknown argument {:?}", other)),
+                }
+            }
+            Ok(Command::Stats { days })
+        }
+        "guestbook" => {
+            let id = |arg: Option<&str>| arg.and_then(|id| id.parse().ok()).ok_or("expected an entry id");
+            let moderation = match args.next() {
+                Some("list") => match args.next() {
+                    None => Moderation::List { pending: false },
+                    Some("--pending") => Moderation::List { pending: true },
+                    Some(other) => return Err(format!("unknown argument {:?}", other)),
+                },
+                Some("approve") => Moderation::Approve(id(args.next())?),
+                Some("delete") => Moderation::Delete(id(args.next())?),
+                Some(other) => return Err(format!("unknown guestbook command {:?}", other)),
+                None => return Err("guestbook needs list, approve or delete".to_string()),
+            };
+            if let Some(arg) = args.next() {
+                return Err(format!("unknown argument {:?}", arg));
+            }
+            Ok(Command::Guestbook(moderation))
+        }
+        "inbox" => {
+            let id = |arg: Option<&str>| arg.and_then(|id| id.parse().ok()).ok_or("expected a message id");
+            let mailbox = match args.next() {
+                None => Mailbox::List { unread: false },
+                Some("--unread") => Mailbox::List { unread: true },
+                Some("read") => Mailbox::Read(id(args.next())?),
+                Some("delete") => Mailbox::Delete(id(args.next())?),
+                Some(other) => return Err(format!("unknown inbox command {:?}", other)),
+            };
+            if let Some(arg) = args.next() {
+                return Err(format!("unknown argument {:?}", arg));
+            }
+            Ok(Command::Inbox(mailbox))
+        }
+        "serve" => {
+            let mut listen = None;
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--listen" => listen = Some(args.next().ok_or("--listen needs an address")?.to_string()),
+                    other => return Err(format!("unknown argument {:?}", other)),
+                }
+            }
+            Ok(Command::Serve { listen })
+        }
+        "export" => {
+            let mut export = Export::default();
+            while let Some(arg) = args.next() {
+                let mut path = || args.next().map(PathBuf::from).ok_or(format!("{} needs a path", arg));
+                match arg {
+                    "--html" => export.html = Some(path()?),
+                    "--markdown" | "--md" => export.markdown = Some(path()?),
+                    "--json" => export.json = Some(path()?),
+                    "--resume" => export.resume = Some(path()?),
+                    other => return Err(format!("unknown argument {:?}", other)),
+                }
+            }
+            if export == Export::default() {
+                return Err("export needs at least one of --html, --markdown, --json or --resume".to_string());
+            }
+            Ok(Command::Export(export))
+        }
+        "sync-github" => {
+            let mut sync = Sync::default();
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--user" => sync.user = Some(args.next().ok_or("--user needs a GitHub user name")?.to_string()),
+                    "--from" => sync.from = Some(args.next().map(PathBuf::from).ok_or("--from needs a path")?),
+                    other => return Err(format!("unknown argument {:?}", other)),
+                }
+            }
+            Ok(Command::SyncGithub(sync))
+        }
+        "sync-contributions" => match args.next() {
+            None => Ok(Command::SyncContributions),
+            Some(arg) => Err(format!("unknown argument {:?}", arg)),
+        },
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        other => Err(format!("unknown command {:?}", other)),
+    }
+}
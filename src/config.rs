@@ -0,0 +1,284 @@
+// config.rs
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+
+use crate::logging::{LogFormat, LogLevel};
+use crate::ui::keymap::Preset;
+
+const CONFIG_ENV: &str = "SSH_PORTFOLIO_CONFIG";
+const DEFAULT_CONFIG_PATH: &str = "/etc/ssh-portfolio/config.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub keys: KeysConfig,
+    pub ui: UiConfig,
+    pub log: LogConfig,
+    pub analytics: AnalyticsConfig,
+    pub counter: CounterConfig,
+    pub guestbook: GuestbookConfig,
+    pub contact: ContactConfig,
+    pub web: WebConfig,
+    pub resume: ResumeConfig,
+    pub projects: ProjectsConfig,
+    pub blog: BlogConfig,
+    pub github: GithubConfig,
+    pub contributions: ContributionsConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResumeConfig {
+    // A JSON Resume (jsonresume.org) the About and Projects pages are
+    // generated from, instead of the built-in text
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectsConfig {
+    // One TOML file per project, shown instead of the built-in catalog
+    pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BlogConfig {
+    // One Markdown file per post, shown instead of the built-in posts
+    pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContributionsConfig {
+    // A GitHub contributions JSON dump for the calendar on the Home page
+    pub file: Option<PathBuf>,
+    // `sync-contributions` counts the commits of these repositories into `file`
+    pub repos: Vec<PathBuf>,
+    // Only this author's commits, any `git log --author` pattern
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GithubConfig {
+    // Account whose public repositories `ssh-portfolio sync-github` fetches
+    pub user: Option<String>,
+    // Written by `sync-github`, shown on the Repos page
+    pub snapshot: PathBuf,
+    // Given the API URL, prints the response; sessions never run it
+    pub fetch: String,
+    // Forked repositories are left out unless this is set
+    pub forks: bool,
+    // `fetch` is killed when it runs longer
+    pub fetch_timeout_secs: u64,
+}
+
+impl Default for GithubConfig {
+    fn default() -> Self {
+        Self {
+            user: None,
+            snapshot: PathBuf::from("/var/lib/ssh-portfolio/github.json"),
+            fetch: "curl -fsSL".to_string(),
+            forks: false,
+            fetch_timeout_secs: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebConfig {
+    // Address `ssh-portfolio serve` listens on
+    pub listen: String,
+    // Directory holding xterm.js, xterm.css and addon-fit.js
+    pub assets: PathBuf,
+    // Further visitors get "503 busy" until someone leaves
+    pub max_sessions: usize,
+    // Connections of any kind, terminals included; more are closed right away
+    pub max_connections: usize,
+    // A session without input for this long is closed
+    pub idle_timeout_secs: u64,
+    // Take the visitor's address from X-Forwarded-For, only behind a proxy
+    pub forwarded_for: bool,
+    // Address the pages tell visitors to curl, e.g. "https://www.medaly.engineer";
+    // taken from the request's Host header when unset
+    pub public_url: Option<String>,
+}
+
+impl Default for WebConfig {
+    fn default() -> Self {
+        Self {
+            listen: "127.0.0.1:8080".to_string(),
+            assets: PathBuf::from("/usr/share/ssh-portfolio/web"),
+            max_sessions: 50,
+            max_connections: 200,
+            idle_timeout_secs: 900,
+            forwarded_for: false,
+            public_url: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContactConfig {
+    pub enabled: bool,
+    // JSON spool read with `ssh-portfolio inbox`
+    pub spool: PathBuf,
+    // How long a visitor waits between two messages
+    pub rate_limit_secs: u64,
+    // e.g. "/usr/sbin/sendmail -t", fed every message as an email on stdin
+    pub sendmail: Option<String>,
+    // The `To:` header of those emails
+    pub to: Option<String>,
+}
+
+impl Default for ContactConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            spool: PathBuf::from("/var/lib/ssh-portfolio/inbox.json"),
+            rate_limit_secs: 600,
+            sendmail: None,
+            to: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GuestbookConfig {
+    pub enabled: bool,
+    pub file: PathBuf,
+    // New entries stay hidden until `ssh-portfolio guestbook approve <id>`
+    pub moderation: bool,
+    // How long a visitor waits between two entries
+    pub rate_limit_secs: u64,
+    // Masked like the built-in list of swear words
+    pub blocked_words: Vec<String>,
+}
+
+impl Default for GuestbookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            file: PathBuf::from("/var/lib/ssh-portfolio/guestbook.json"),
+            moderation: true,
+            rate_limit_secs: 600,
+            blocked_words: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CounterConfig {
+    pub enabled: bool,
+    // Shared by every session; the `.lock` file next to it (`visitors.lock`)
+    // serializes the writers
+    pub file: PathBuf,
+}
+
+impl Default for CounterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            file: PathBuf::from("/var/lib/ssh-portfolio/visitors.json"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnalyticsConfig {
+    // Off unless the owner opts in
+    pub enabled: bool,
+    pub file: PathBuf,
+    // Random secret mixed into the visitor hashes, created on first use
+    pub salt_file: PathBuf,
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            file: PathBuf::from("/var/lib/ssh-portfolio/analytics.jsonl"),
+            salt_file: PathBuf::from("/var/lib/ssh-portfolio/salt"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    pub level: LogLevel,
+    pub format: LogFormat,
+    // Appended to; without it logs go to stderr, but only when stderr is not
+    // the visitor's terminal
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    // Mouse capture disables the terminal's own text selection
+    pub mouse: bool,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self { mouse: true }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeysConfig {
+    pub preset: Preset,
+    // action name -> key(s), replacing the preset's keys for that action
+    pub bindings: BTreeMap<String, KeyList>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn keys(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(key) => vec![key.as_str()],
+            KeyList::Many(keys) => keys.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> PathBuf {
+        std::env::var_os(CONFIG_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH))
+    }
+
+    // A missing config file is not an error, the defaults are used instead
+    pub fn load() -> io::Result<Self> {
+        let path = Self::path();
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e))
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+}
@@ -0,0 +1,236 @@
+// contact.rs
+
+use base64::Engine;
+use chrono::{DateTime, Duration as TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+
+use crate::config::ContactConfig;
+use crate::guestbook::clean_line;
+use crate::store;
+
+pub const MAX_NAME: usize = 60;
+pub const MAX_EMAIL: usize = 254;
+pub const MAX_SUBJECT: usize = 100;
+pub const MAX_MESSAGE: usize = 5000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub id: u64,
+    pub name: String,
+    pub email: String,
+    pub subject: String,
+    pub body: String,
+    pub received: DateTime<Utc>,
+    // Salted hash of the client address, used for rate limiting
+    pub visitor: Option<String>,
+    pub read: bool,
+}
+
+// The spool file, newest message last
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Inbox {
+    pub next_id: u64,
+    pub messages: Vec<Message>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draft {
+    pub name: String,
+    pub email: String,
+    pub subject: String,
+    pub body: String,
+}
+
+// Checks and cleans a draft, the name, email and subject end up on one line each
+pub fn validate(draft: &Draft) -> Result<Draft, String> {
+    let name = clean_line(&draft.name);
+    let email = clean_line(&draft.email);
+    let subject = clean_line(&draft.subject);
+    let body = clean_body(&draft.body);
+
+    if name.is_empty() {
+        return Err("Please enter your name".to_string());
+    }
+    if name.chars().count() > MAX_NAME {
+        return Err(format!("The name can be at most {} characters", MAX_NAME));
+    }
+    if !is_email(&email) {
+        return Err("Please enter an email address I can reply to".to_string());
+    }
+    if subject.is_empty() {
+        return Err("Please enter a subject".to_string());
+    }
+    if subject.chars().count() > MAX_SUBJECT {
+        return Err(format!("The subject can be at most {} characters", MAX_SUBJECT));
+    }
+    if body.is_empty() {
+        return Err("Please enter a message".to_string());
+    }
+    if body.chars().count() > MAX_MESSAGE {
+        return Err(format!("The message can be at most {} characters", MAX_MESSAGE));
+    }
+    Ok(Draft { name, email, subject, body })
+}
+
+// Loose on purpose: one @, something before it, a dotted domain after it
+pub fn is_email(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+    email.len() <= MAX_EMAIL
+        && !local.is_empty()
+        && !domain.contains('@')
+        && !email.contains(char::is_whitespace)
+        && domain.split('.').count() > 1
+        && domain.split('.').all(|part| !part.is_empty())
+}
+
+// Like `clean_line` for every line, keeping at most one blank line in a row
+pub fn clean_body(text: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line: String = line
+            .chars()
+            .filter(|c| !c.is_control() || *c == '\t')
+            .filter(|c| !matches!(c, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}' | '\u{200e}' | '\u{200f}'))
+            .map(|c| if c == '\t' { ' ' } else { c })
+            .collect();
+        let line = line.trim_end().to_string();
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+pub fn load(path: &Path) -> io::Result<Inbox> {
+    store::read(path)
+}
+
+// Spools a message unless the same visitor sent one too recently, then hands
+// it to sendmail when configured. Visitors without a hash share one rate limit.
+pub fn send(config: &ContactConfig, draft: &Draft, visitor: Option<&str>) -> Result<(), String> {
+    let draft = validate(draft)?;
+    let now = Utc::now();
+    let window = TimeDelta::seconds(config.rate_limit_secs as i64);
+
+    let (result, _) = store::update(&config.spool, |inbox: &mut Inbox| {
+        let recent = inbox
+            .messages
+            .iter()
+            .any(|message| message.visitor.as_deref() == visitor && now - message.received < window);
+        if recent {
+            return Err("You sent me a message a moment ago, please try again later".to_string());
+        }
+        inbox.next_id += 1;
+        let message = Message {
+            id: inbox.next_id,
+            name: draft.name.clone(),
+            email: draft.email.clone(),
+            subject: draft.subject.clone(),
+            body: draft.body.clone(),
+            received: now,
+            visitor: visitor.map(str::to_string),
+            read: false,
+        };
+        inbox.messages.push(message.clone());
+        Ok(message)
+    })
+    .map_err(|e| {
+        log::warn!(error = e.to_string(); "could not write the contact spool");
+        "Messages can not be sent right now".to_string()
+    })?;
+    let message = result?;
+
+    // The message is already safe in the spool, a failing hook only gets logged
+    if let Some(command) = &config.sendmail {
+        if let Err(e) = sendmail(command, config.to.as_deref(), &message) {
+            log::warn!(error = e.to_string(), id = message.id; "sendmail hook failed");
+        }
+    }
+    Ok(())
+}
+
+// Pipes the message to a `sendmail -t` style command. Only starting it
+// happens on the session's thread: a hung MTA must not freeze the terminal,
+// so its exit status is waited for and logged in the background.
+fn sendmail(command: &str, to: Option<&str>, message: &Message) -> io::Result<()> {
+    let mut words = command.split_whitespace();
+    let program = words.next().ok_or_else(|| io::Error::other("empty sendmail command"))?;
+    let mut child = Command::new(program)
+        .args(words)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // A message is far smaller than a pipe buffer, this write does not block
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(email(to, message).as_bytes())?;
+    }
+    let (program, id) = (program.to_string(), message.id);
+    thread::spawn(move || match child.wait() {
+        Ok(status) if status.success() => {}
+        Ok(status) => log::warn!(id; "sendmail hook failed: {} exited with {}", program, status),
+        Err(e) => log::warn!(error = e.to_string(), id; "sendmail hook failed"),
+    });
+    Ok(())
+}
+
+// The message as an RFC 5322 email, replies going to the visitor
+pub fn email(to: Option<&str>, message: &Message) -> String {
+    let mut out = String::new();
+    if let Some(to) = to {
+        out.push_str(&format!("To: {}\n", to));
+    }
+    out.push_str(&format!("Reply-To: {} <{}>\n", display_name(&message.name), message.email));
+    out.push_str(&format!("Subject: {}\n", header_text(&format!("[portfolio] {}", message.subject))));
+    out.push_str(&format!("Date: {}\n", message.received.to_rfc2822()));
+    out.push_str("MIME-Version: 1.0\n");
+    out.push_str("Content-Type: text/plain; charset=utf-8\n");
+    out.push_str("Content-Transfer-Encoding: 8bit\n\n");
+    out.push_str(&format!("From {} <{}>:\n\n{}\n", message.name, message.email, message.body));
+    out
+}
+
+// Quoted so commas and angle brackets in a name stay part of it
+fn display_name(name: &str) -> String {
+    if !name.is_ascii() {
+        return header_text(name);
+    }
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// Header values outside ASCII need RFC 2047 encoding
+fn header_text(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_string();
+    }
+    format!("=?utf-8?b?{}?=", base64::engine::general_purpose::STANDARD.encode(text))
+}
+
+// Owner commands, finding nothing when there is no such message
+pub fn mark_read(path: &Path, id: u64) -> io::Result<Option<Message>> {
+    let (message, _) = store::update(path, |inbox: &mut Inbox| {
+        let message = inbox.messages.iter_mut().find(|message| message.id == id)?;
+        message.read = true;
+        Some(message.clone())
+    })?;
+    Ok(message)
+}
+
+pub fn delete(path: &Path, id: u64) -> io::Result<bool> {
+    let (found, _) = store::update(path, |inbox: &mut Inbox| {
+        let before = inbox.messages.len();
+        inbox.messages.retain(|message| message.id != id);
+        inbox.messages.len() != before
+    })?;
+    Ok(found)
+}
@@ -0,0 +1,111 @@
+// contributions.rs
+
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::{files, store};
+
+// Contributions per day, for the calendar on the Home page
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Calendar {
+    pub days: BTreeMap<NaiveDate, u32>,
+}
+
+impl Calendar {
+    pub fn count(&self, day: NaiveDate) -> u32 {
+        self.days.get(&day).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, day: NaiveDate, count: u32) {
+        let total = self.days.entry(day).or_insert(0);
+        *total = total.saturating_add(count);
+    }
+}
+
+// Set once at startup, none when `[contributions]` names no source
+static CALENDAR: OnceLock<Calendar> = OnceLock::new();
+
+pub fn install(calendar: Calendar) {
+    let _ = CALENDAR.set(calendar);
+}
+
+pub fn calendar() -> Option<&'static Calendar> {
+    CALENDAR.get()
+}
+
+pub fn load(path: &Path) -> io::Result<Calendar> {
+    files::load_file(path, parse_github)
+}
+
+// A dump of GitHub contributions: the GraphQL API's `contributionCalendar`
+// (`{"date", "contributionCount"}` days inside weeks) or the flat
+// `{"contributions": [{"date", "count"}]}` of the contributions APIs.
+// Every object with a date and a count is a day, wherever it is nested.
+pub fn parse_github(text: &str) -> serde_json::Result<Calendar> {
+    let json: Value = serde_json::from_str(text)?;
+    let mut calendar = Calendar::default();
+    collect_days(&json, &mut calendar);
+    Ok(calendar)
+}
+
+fn collect_days(json: &Value, calendar: &mut Calendar) {
+    match json {
+        Value::Array(items) => items.iter().for_each(|item| collect_days(item, calendar)),
+        Value::Object(object) => {
+            let date = object.get("date").and_then(Value::as_str).and_then(|date| date.get(..10));
+            let count = object.get("contributionCount").or_else(|| object.get("count")).and_then(Value::as_u64);
+            match (date.and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()), count) {
+                (Some(day), Some(count)) => calendar.add(day, u32::try_from(count).unwrap_or(u32::MAX)),
+                _ => object.values().for_each(|value| collect_days(value, calendar)),
+            }
+        }
+        _ => {}
+    }
+}
+
+// Written in the flat format `parse_github` reads, through the store so a
+// session starting meanwhile reads the old or the new calendar
+pub fn save(path: &Path, calendar: &Calendar) -> io::Result<()> {
+    let days: Vec<Value> = calendar.days.iter().map(|(day, count)| json!({"date": day.to_string(), "count": count})).collect();
+    store::replace(path, &json!({ "contributions": days }))
+}
+
+// Commits per day in local repositories since `since`, only `author`'s
+// when set (any `git log --author` pattern, e.g. an email address)
+pub fn scan_git(repos: &[PathBuf], author: Option<&str>, since: NaiveDate) -> io::Result<Calendar> {
+    let mut calendar = Calendar::default();
+    for repo in repos {
+        let mut git = Command::new("git");
+        git.arg("-C").arg(repo).args(["log", "--all", "--no-merges", "--date=short", "--format=%ad"]);
+        git.arg(format!("--since={}", since));
+        if let Some(author) = author {
+            git.arg(format!("--author={}", author));
+        }
+        let output = git
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|e| io::Error::new(e.kind(), format!("git: {}", e)))?;
+        if !output.status.success() {
+            return Err(io::Error::other(format!("{}: git log exited with {}", repo.display(), output.status)));
+        }
+        for (day, count) in parse_git_log(&String::from_utf8_lossy(&output.stdout)).days {
+            calendar.add(day, count);
+        }
+    }
+    Ok(calendar)
+}
+
+// One `YYYY-MM-DD` line per commit
+pub fn parse_git_log(output: &str) -> Calendar {
+    let mut calendar = Calendar::default();
+    for day in output.lines().filter_map(|line| NaiveDate::parse_from_str(line.trim(), "%Y-%m-%d").ok()) {
+        calendar.add(day, 1);
+    }
+    calendar
+}
@@ -0,0 +1,143 @@
+// counter.rs
+
+use chrono::{DateTime, Duration as TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::store;
+
+// How often a running session tells the others it is still there
+const HEARTBEAT: Duration = Duration::from_secs(30);
+// A session that missed this many seconds of heartbeats is gone (killed, crashed)
+const ONLINE_TIMEOUT_SECS: i64 = 90;
+// How often the numbers on screen are re-read
+const REFRESH: Duration = Duration::from_secs(5);
+
+// What is kept on disk, shared by every session process
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Store {
+    total: u64,
+    last_visit: Option<DateTime<Utc>>,
+    // session id -> last heartbeat
+    online: BTreeMap<String, DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub total: u64,
+    pub online: usize,
+    // The latest visit by someone else
+    pub last_visit: Option<DateTime<Utc>>,
+}
+
+// This session's view of the shared visitor counter
+#[derive(Debug)]
+pub struct Counter {
+    path: PathBuf,
+    session: String,
+    joined_at: DateTime<Utc>,
+    // Who came before us, shown until someone new arrives
+    previous_visit: Option<DateTime<Utc>>,
+    last_heartbeat: Instant,
+    last_refresh: Instant,
+    pub stats: Stats,
+}
+
+impl Counter {
+    // Counts this visit and marks the session as online
+    pub fn join(path: &Path, session: &str) -> io::Result<Self> {
+        let now = Utc::now();
+        let (previous_visit, store) = update(path, |store| {
+            store.total += 1;
+            let previous = store.last_visit.replace(now);
+            store.online.insert(session.to_string(), now);
+            previous
+        })?;
+        let mut counter = Self {
+            path: path.to_path_buf(),
+            session: session.to_string(),
+            joined_at: now,
+            previous_visit,
+            last_heartbeat: Instant::now(),
+            last_refresh: Instant::now(),
+            stats: Stats::default(),
+        };
+        counter.stats = counter.stats_from(&store);
+        Ok(counter)
+    }
+
+    // Sends the heartbeat and re-reads the numbers when they are due
+    pub fn tick(&mut self) -> io::Result<()> {
+        if self.last_heartbeat.elapsed() >= HEARTBEAT {
+            self.last_heartbeat = Instant::now();
+            self.last_refresh = Instant::now();
+            let session = self.session.clone();
+            let ((), store) = update(&self.path, |store| {
+                store.online.insert(session, Utc::now());
+            })?;
+            self.stats = self.stats_from(&store);
+        } else if self.last_refresh.elapsed() >= REFRESH {
+            self.last_refresh = Instant::now();
+            let store: Store = store::read(&self.path)?;
+            self.stats = self.stats_from(&store);
+        }
+        Ok(())
+    }
+
+    pub fn leave(&self) -> io::Result<()> {
+        update(&self.path, |store| {
+            store.online.remove(&self.session);
+        })
+        .map(|_| ())
+    }
+
+    fn stats_from(&self, store: &Store) -> Stats {
+        let last_visit = match store.last_visit {
+            Some(visit) if visit != self.joined_at => Some(visit),
+            _ => self.previous_visit,
+        };
+        Stats { total: store.total, online: online(store, Utc::now()), last_visit }
+    }
+}
+
+fn online(store: &Store, now: DateTime<Utc>) -> usize {
+    let cutoff = now - TimeDelta::seconds(ONLINE_TIMEOUT_SECS);
+    store.online.values().filter(|&&seen| seen > cutoff).count()
+}
+
+// Every write also forgets the sessions that stopped sending heartbeats
+fn update<T>(path: &Path, change: impl FnOnce(&mut Store) -> T) -> io::Result<(T, Store)> {
+    store::update(path, |store: &mut Store| {
+        let result = change(store);
+        let cutoff = Utc::now() - TimeDelta::seconds(ONLINE_TIMEOUT_SECS);
+        store.online.retain(|_, seen| *seen > cutoff);
+        result
+    })
+}
+
+// e.g. "just now", "5m ago", "3h ago", "2d ago"
+pub fn time_ago(then: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - then).num_seconds().max(0);
+    match secs {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+// 1234567 -> "1,234,567"
+pub fn thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
@@ -0,0 +1,218 @@
+// export/html.rs
+
+use crossterm::style::Color;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{file_stem, write_pages, GUESTBOOK_NOTE};
+use crate::ui::buffer::{Buffer, Cell, Style};
+use crate::ui::content::full_page;
+use crate::ui::handler::{Page, PageContent};
+use crate::ui::header::{nav_layout, NAV_ROW};
+use crate::ui::markup::{escape_html, links};
+use crate::web::pages::DEFAULT_WIDTH;
+
+// xterm's default palette, what most visitors see over SSH
+const PALETTE: [&str; 16] = [
+    "#000000", "#cd0000", "#00cd00", "#cdcd00", "#0000ee", "#cd00cd", "#00cdcd", "#e5e5e5", "#7f7f7f", "#ff0000",
+    "#00ff00", "#ffff00", "#5c5cff", "#ff00ff", "#00ffff", "#ffffff",
+];
+
+pub fn file_name(page: Page) -> String {
+    format!("{}.html", file_stem(page))
+}
+
+// Writes one HTML file per page into `dir`, returning their paths
+pub fn write(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    write_pages(dir, file_name, page_html)
+}
+
+// A page as the terminal draws it: every cell in place, colors kept, the
+// navigation tabs and URLs turned into links
+pub fn page_html(page: Page) -> String {
+    let mut content = PageContent::get_content(&page);
+    if page == Page::Guestbook {
+        content.push_str(GUESTBOOK_NOTE);
+    }
+    let buf = full_page(page, &content, DEFAULT_WIDTH);
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title} · medaly.engineer</title>
+<style>
+html {{ background: #000; color: {fg}; }}
+body {{ margin: 0; padding: 1rem; overflow-x: auto; }}
+pre {{ margin: 0 auto; width: max-content; font: 15px/1.25 "JetBrains Mono", "Fira Code", Menlo, Consolas, monospace; }}
+a {{ color: inherit; text-decoration: none; }}
+a:hover, a:focus {{ text-decoration: underline; }}
+</style>
+</head>
+<body>
+<pre>{body}</pre>
+</body>
+</html>
+"#,
+        title = escape_html(page.label()),
+        fg = PALETTE[7],
+        body = buffer_html(&buf),
+    )
+}
+
+// Cells `start..end` of row `y` link to `href`
+struct Anchor {
+    y: u16,
+    start: usize,
+    end: usize,
+    href: String,
+}
+
+fn anchors(buf: &Buffer) -> Vec<Anchor> {
+    let mut anchors: Vec<Anchor> = nav_layout(buf.area.x)
+        .into_iter()
+        .map(|item| Anchor {
+            y: buf.area.y + NAV_ROW,
+            start: item.x as usize,
+            end: (item.x + item.width) as usize,
+            href: file_name(item.page),
+        })
+        .collect();
+
+    for y in buf.area.y..buf.area.bottom() {
+        // Cell index of every char, wide chars take two cells
+        let row = buf.row(y);
+        let cells: Vec<usize> = (0..row.len()).filter(|&x| !row[x].symbol.is_empty()).collect();
+        let text: String = cells.iter().map(|&x| row[x].symbol.as_str()).collect();
+        let chars: Vec<char> = text.chars().collect();
+        for (start, end) in links(&text) {
+            let href: String = chars[start..end].iter().collect();
+            anchors.push(Anchor { y, start: cells[start], end: cells[end - 1] + 1, href });
+        }
+    }
+    anchors
+}
+
+fn buffer_html(buf: &Buffer) -> String {
+    let anchors = anchors(buf);
+    let mut out = String::new();
+    for y in buf.area.y..buf.area.bottom() {
+        let row = buf.row(y);
+        let end = row.iter().rposition(|cell| !is_blank(cell)).map_or(0, |i| i + 1);
+        let row_anchors: Vec<&Anchor> = anchors.iter().filter(|a| a.y == y && a.start < end).collect();
+
+        let mut x = 0;
+        while x < end {
+            let anchor = row_anchors.iter().find(|a| a.start == x);
+            let stop = match anchor {
+                Some(anchor) => anchor.end.min(end),
+                None => row_anchors.iter().map(|a| a.start).filter(|&s| s > x).min().unwrap_or(end),
+            };
+            if let Some(anchor) = anchor {
+                out.push_str(&format!("<a href=\"{}\">", escape_html(&anchor.href)));
+            }
+            out.push_str(&spans(&row[x..stop]));
+            if anchor.is_some() {
+                out.push_str("</a>");
+            }
+            x = stop;
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn is_blank(cell: &Cell) -> bool {
+    cell.symbol == " " && cell.style.bg.is_none() && !cell.style.underline
+}
+
+// Runs of cells in the same style, one span each
+fn spans(cells: &[Cell]) -> String {
+    let mut out = String::new();
+    let mut run = String::new();
+    let mut style = Style::default();
+    for cell in cells.iter().filter(|cell| !cell.symbol.is_empty()) {
+        if cell.style != style {
+            push_span(&mut out, &run, style);
+            run.clear();
+            style = cell.style;
+        }
+        run.push_str(&cell.symbol);
+    }
+    push_span(&mut out, &run, style);
+    out
+}
+
+fn push_span(out: &mut String, text: &str, style: Style) {
+    if text.is_empty() {
+        return;
+    }
+    let css = css(style);
+    if css.is_empty() {
+        out.push_str(&escape_html(text));
+    } else {
+        out.push_str(&format!("<span style=\"{}\">{}</span>", css, escape_html(text)));
+    }
+}
+
+fn css(style: Style) -> String {
+    let mut rules = Vec::new();
+    if let Some(fg) = style.fg.and_then(css_color) {
+        rules.push(format!("color:{}", fg));
+    }
+    if let Some(bg) = style.bg.and_then(css_color) {
+        rules.push(format!("background:{}", bg));
+    }
+    if style.bold {
+        rules.push("font-weight:bold".to_string());
+    }
+    if style.dim {
+        rules.push("opacity:.6".to_string());
+    }
+    if style.italic {
+        rules.push("font-style:italic".to_string());
+    }
+    if style.underline {
+        rules.push("text-decoration:underline".to_string());
+    }
+    rules.join(";")
+}
+
+pub fn css_color(color: Color) -> Option<String> {
+    let index = match color {
+        Color::Reset => return None,
+        Color::Black => 0,
+        Color::DarkRed => 1,
+        Color::DarkGreen => 2,
+        Color::DarkYellow => 3,
+        Color::DarkBlue => 4,
+        Color::DarkMagenta => 5,
+        Color::DarkCyan => 6,
+        Color::Grey => 7,
+        Color::DarkGrey => 8,
+        Color::Red => 9,
+        Color::Green => 10,
+        Color::Yellow => 11,
+        Color::Blue => 12,
+        Color::Magenta => 13,
+        Color::Cyan => 14,
+        Color::White => 15,
+        Color::AnsiValue(n) => n,
+        Color::Rgb { r, g, b } => return Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+    };
+    Some(match index {
+        0..=15 => PALETTE[index as usize].to_string(),
+        // 6x6x6 color cube
+        16..=231 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let n = index - 16;
+            format!("#{:02x}{:02x}{:02x}", level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        // Grey ramp
+        _ => {
+            let grey = 8 + (index - 232) * 10;
+            format!("#{:02x}{:02x}{:02x}", grey, grey, grey)
+        }
+    })
+}
@@ -0,0 +1,146 @@
+// export/json.rs
+
+use serde::Serialize;
+use std::io;
+use std::path::Path;
+
+use super::{content, file_stem, write_file};
+use crate::ui::handler::{Page, PageContent};
+use crate::ui::markup::{self, links};
+
+// Every page as data: its sections in order, each with its blocks and the
+// URLs they mention
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Document {
+    pub site: String,
+    pub pages: Vec<PageDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PageDoc {
+    pub slug: String,
+    pub label: String,
+    // The exported Markdown and HTML files share this name
+    pub file: String,
+    pub sections: Vec<Section>,
+}
+
+// A "#", "##" or "###" heading and what follows up to the next one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Section {
+    pub heading: String,
+    pub level: u8,
+    pub blocks: Vec<Block>,
+    pub links: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Block {
+    // Hard wrapped lines joined back into one
+    Paragraph { text: String },
+    List { items: Vec<String> },
+    Code { language: Option<String>, text: String },
+    // Banners and box drawings, only meaningful in a monospace font
+    Art { text: String },
+}
+
+impl Section {
+    pub fn paragraphs(&self) -> impl Iterator<Item = &str> {
+        self.blocks.iter().filter_map(|block| match block {
+            Block::Paragraph { text } => Some(text.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &str> {
+        self.blocks
+            .iter()
+            .flat_map(|block| match block {
+                Block::List { items } => items.as_slice(),
+                _ => &[],
+            })
+            .map(String::as_str)
+    }
+}
+
+impl PageDoc {
+    // The "###" sections under the "##" section called `heading`
+    pub fn subsections<'a>(&'a self, heading: &str) -> impl Iterator<Item = &'a Section> {
+        let start = self.sections.iter().position(|s| s.level == 2 && s.heading == heading);
+        let rest = start.map_or(&[][..], |i| &self.sections[i + 1..]);
+        rest.iter().take_while(|s| s.level > 2)
+    }
+
+    pub fn section(&self, heading: &str) -> Option<&Section> {
+        self.sections.iter().find(|s| s.heading == heading)
+    }
+}
+
+pub fn document() -> Document {
+    Document { site: PageContent::profile().url.to_string(), pages: Page::ALL.into_iter().map(page_doc).collect() }
+}
+
+pub fn write(path: &Path) -> io::Result<()> {
+    // Plain data, serializing it does not fail
+    let json = serde_json::to_string_pretty(&document()).expect("document serializes");
+    write_file(path, &format!("{}\n", json))
+}
+
+pub fn page_doc(page: Page) -> PageDoc {
+    PageDoc {
+        slug: page.slug().to_string(),
+        label: page.label().to_string(),
+        file: file_stem(page).to_string(),
+        sections: sections(&content(page)),
+    }
+}
+
+// Splits page markup into sections, text before the first heading gets an
+// untitled one
+pub fn sections(content: &str) -> Vec<Section> {
+    let mut sections: Vec<Section> = Vec::new();
+    for block in markup::blocks(content) {
+        let block = match block {
+            markup::Block::Heading(level, text) => {
+                sections.push(Section { heading: text.to_string(), level, blocks: Vec::new(), links: Vec::new() });
+                continue;
+            }
+            markup::Block::Paragraph(lines) => Block::Paragraph { text: lines.join(" ") },
+            markup::Block::List(items) => Block::List { items: items.into_iter().map(str::to_string).collect() },
+            markup::Block::Code(language, lines) => {
+                Block::Code { language: language.map(str::to_string), text: lines.join("\n") }
+            }
+            markup::Block::Art(lines) => Block::Art { text: lines.join("\n") },
+        };
+        current(&mut sections).blocks.push(block);
+    }
+
+    for section in &mut sections {
+        section.links = section.blocks.iter().flat_map(block_links).collect();
+    }
+    sections
+}
+
+fn current(sections: &mut Vec<Section>) -> &mut Section {
+    if sections.is_empty() {
+        sections.push(Section { heading: String::new(), level: 0, blocks: Vec::new(), links: Vec::new() });
+    }
+    let last = sections.len() - 1;
+    &mut sections[last]
+}
+
+fn block_links(block: &Block) -> Vec<String> {
+    let texts: Vec<&str> = match block {
+        Block::Paragraph { text } | Block::Code { text, .. } => vec![text],
+        Block::List { items } => items.iter().map(String::as_str).collect(),
+        Block::Art { .. } => Vec::new(),
+    };
+    texts
+        .into_iter()
+        .flat_map(|text| {
+            let chars: Vec<char> = text.chars().collect();
+            links(text).into_iter().map(move |(start, end)| chars[start..end].iter().collect::<String>())
+        })
+        .collect()
+}
@@ -0,0 +1,66 @@
+// export/markdown.rs
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{content, file_stem, write_pages, GUESTBOOK_NOTE};
+use crate::ui::handler::Page;
+use crate::ui::markup::{blocks, escape, links, Block};
+
+pub fn file_name(page: Page) -> String {
+    format!("{}.md", file_stem(page))
+}
+
+// Writes one Markdown file per page into `dir`, returning their paths
+pub fn write(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    write_pages(dir, file_name, page_markdown)
+}
+
+// The page markup is nearly Markdown already: headings, bullets and fences
+// carry over, banners get fenced so they keep their shape, URLs become links.
+// Blocks are one blank line apart.
+pub fn page_markdown(page: Page) -> String {
+    let mut content = content(page);
+    if page == Page::Guestbook {
+        content.push_str(GUESTBOOK_NOTE);
+    }
+
+    let nav: Vec<String> = Page::ALL
+        .iter()
+        .map(|&p| match p == page {
+            true => format!("**{}**", p.label()),
+            false => format!("[{}]({})", p.label(), file_name(p)),
+        })
+        .collect();
+    let mut lines = vec![nav.join(" · "), String::new()];
+
+    for block in blocks(&content) {
+        lines.push(match block {
+            Block::Heading(level, text) => format!("{} {}", "#".repeat(level as usize), text),
+            // Escaped lines stay escaped, Markdown would read them as markup too
+            Block::Paragraph(text) => text.iter().map(|line| autolink(&escape(line))).collect::<Vec<_>>().join("\n"),
+            Block::List(items) => items.iter().map(|item| format!("- {}", autolink(item))).collect::<Vec<_>>().join("\n"),
+            Block::Code(language, code) => format!("```{}\n{}\n```", language.unwrap_or(""), code.join("\n")),
+            Block::Art(art) => format!("```text\n{}\n```", art.join("\n")),
+        });
+        lines.push(String::new());
+    }
+    lines.pop();
+    lines.join("\n") + "\n"
+}
+
+// URLs as autolinks, Markdown renderers differ on bare ones
+fn autolink(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut at = 0;
+    for (start, end) in links(text) {
+        out.extend(&chars[at..start]);
+        out.push('<');
+        out.extend(&chars[start..end]);
+        out.push('>');
+        at = end;
+    }
+    out.extend(&chars[at..]);
+    out
+}
@@ -0,0 +1,53 @@
+// export/mod.rs
+
+pub mod html;
+pub mod json;
+pub mod markdown;
+pub mod resume;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::files::with_path;
+use crate::ui::handler::{Page, PageContent};
+
+// Exports keep the guestbook intro, signing it needs a session
+const GUESTBOOK_NOTE: &str = "\nSign it over SSH or in the web terminal.\n";
+
+// index for the first page, so a static host serves it at "/"
+pub fn file_stem(page: Page) -> &'static str {
+    match page {
+        Page::Home => "index",
+        page => page.slug(),
+    }
+}
+
+// Writes one file per page into `dir`, returning their paths
+fn write_pages(dir: &Path, file_name: fn(Page) -> String, render: fn(Page) -> String) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir).map_err(|e| with_path(dir, e))?;
+    let mut written = Vec::new();
+    for page in Page::ALL {
+        let path = dir.join(file_name(page));
+        fs::write(&path, render(page)).map_err(|e| with_path(&path, e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+// Writes a single document, "-" prints it instead
+fn write_file(path: &Path, text: &str) -> io::Result<()> {
+    if path == Path::new("-") {
+        print!("{}", text);
+        return Ok(());
+    }
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(|e| with_path(dir, e))?;
+    }
+    fs::write(path, text).map_err(|e| with_path(path, e))
+}
+
+// A page's markup with the blank line its raw string starts with dropped
+fn content(page: Page) -> String {
+    PageContent::get_content(&page).trim_start_matches('\n').to_string()
+}
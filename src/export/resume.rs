@@ -0,0 +1,144 @@
+// export/resume.rs
+
+use std::io;
+use std::path::Path;
+
+use super::json::{page_doc, PageDoc, Section};
+use super::write_file;
+use crate::resume::{Basics, Education, Location, Profile, Resume, Skill, Work};
+use crate::ui::handler::{Page, PageContent};
+
+const SCHEMA: &str = "https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json";
+
+// The sections of the About and Contact pages a resume is built from. Entries
+// are "###" headings: work as "Position | Company", education as the field of
+// study, each followed by a "Place | 2024 - 2027" line and bullets.
+const SUMMARY: &str = "My Journey";
+const WORK: &str = "Professional Experience";
+const EDUCATION: &str = "Education";
+const SKILLS: &str = "Technical Skills";
+const PROFILES: &str = "Social Media & Links";
+
+pub fn write(path: &Path) -> io::Result<()> {
+    // Plain data, serializing it does not fail
+    let json = serde_json::to_string_pretty(&resume()).expect("resume serializes");
+    write_file(path, &format!("{}\n", json))
+}
+
+// The imported resume when there is one, otherwise one read off the pages
+pub fn resume() -> Resume {
+    if let Some(resume) = PageContent::resume() {
+        return resume.clone();
+    }
+    let about = page_doc(Page::About);
+    let contact = page_doc(Page::FAQ);
+    let profile = PageContent::profile();
+    Resume {
+        schema: Some(SCHEMA.to_string()),
+        basics: Basics {
+            name: Some(profile.name.to_string()),
+            label: Some(profile.label.to_string()),
+            url: Some(profile.url.to_string()),
+            summary: about.section(SUMMARY).map(|s| s.paragraphs().collect::<Vec<_>>().join("\n\n")),
+            location: Some(Location { country_code: Some(profile.country_code.to_string()), ..Default::default() }),
+            profiles: contact.subsections(PROFILES).filter_map(social_profile).collect(),
+            ..Default::default()
+        },
+        work: about.subsections(WORK).map(work).collect(),
+        education: about.subsections(EDUCATION).map(education).collect(),
+        skills: skills(&about),
+        ..Default::default()
+    }
+}
+
+fn social_profile(section: &Section) -> Option<Profile> {
+    let url = section.links.first()?;
+    let username = url.trim_end_matches('/').rsplit('/').next().unwrap_or_default();
+    Some(Profile {
+        network: Some(section.heading.clone()),
+        username: Some(username.to_string()),
+        url: Some(url.clone()),
+    })
+}
+
+fn work(section: &Section) -> Work {
+    let (position, name) = match section.heading.split_once(" | ") {
+        Some((position, name)) => (position.trim(), Some(name.trim().to_string())),
+        None => (section.heading.as_str(), None),
+    };
+    let mut work = Work { name, position: Some(position.to_string()), ..Default::default() };
+    let mut summary = Vec::new();
+    for paragraph in section.paragraphs() {
+        match when_and_where(paragraph) {
+            Some((start, end, place)) if work.start_date.is_none() => {
+                (work.start_date, work.end_date, work.location) = (Some(start), end, place);
+            }
+            _ => summary.push(paragraph),
+        }
+    }
+    work.summary = (!summary.is_empty()).then(|| summary.join("\n\n"));
+    work.highlights = section.items().map(str::to_string).collect();
+    work
+}
+
+fn education(section: &Section) -> Education {
+    let mut education = Education { area: Some(section.heading.clone()), ..Default::default() };
+    if let Some((start, end, institution)) = section.paragraphs().find_map(when_and_where) {
+        (education.start_date, education.end_date, education.institution) = (Some(start), end, institution);
+    }
+    education
+}
+
+fn skills(about: &PageDoc) -> Vec<Skill> {
+    about
+        .subsections(SKILLS)
+        .map(|section| Skill {
+            name: Some(section.heading.clone()),
+            level: None,
+            keywords: section
+                .paragraphs()
+                .flat_map(|text| text.split(','))
+                .map(str::trim)
+                .filter(|keyword| !keyword.is_empty())
+                .map(str::to_string)
+                .collect(),
+        })
+        .collect()
+}
+
+// "ESPRIT University | 2024 - 2027" or "2024 - Present | Tunisia": the
+// start, the end unless ongoing, and the other part
+fn when_and_where(line: &str) -> Option<(String, Option<String>, Option<String>)> {
+    let parts: Vec<&str> = line.split(" | ").map(str::trim).collect();
+    let (i, (start, end)) = parts.iter().enumerate().find_map(|(i, part)| dates(part).map(|dates| (i, dates)))?;
+    let other = parts.iter().enumerate().find(|(j, _)| *j != i).map(|(_, part)| part.to_string());
+    Some((start, end, other))
+}
+
+// "2024", "2024 - 2027", "2024-09 - Present"; JSON Resume dates are ISO 8601
+// and an ongoing entry has no end
+pub fn dates(text: &str) -> Option<(String, Option<String>)> {
+    let (start, end) = match text.split_once(" - ").or_else(|| text.split_once(" – ")) {
+        Some((start, end)) => (start.trim(), Some(end.trim())),
+        None => (text.trim(), None),
+    };
+    if !is_date(start) {
+        return None;
+    }
+    let end = match end {
+        None => None,
+        Some(end) if is_date(end) => Some(end.to_string()),
+        Some(end) if ["present", "now", "ongoing"].contains(&end.to_ascii_lowercase().as_str()) => None,
+        Some(_) => return None,
+    };
+    Some((start.to_string(), end))
+}
+
+// YYYY, YYYY-MM or YYYY-MM-DD
+fn is_date(text: &str) -> bool {
+    let parts: Vec<&str> = text.split('-').collect();
+    parts.len() <= 3
+        && parts[0].len() == 4
+        && parts[1..].iter().all(|part| part.len() == 2)
+        && parts.iter().all(|part| part.bytes().all(|b| b.is_ascii_digit()))
+}
@@ -0,0 +1,38 @@
+// files.rs
+
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// The error with the file it is about in front, for messages that go to the owner
+pub fn with_path(path: &Path, e: io::Error) -> io::Error {
+    io::Error::new(e.kind(), format!("{}: {}", path.display(), e))
+}
+
+// Reads and parses one file, a parse error is InvalidData
+pub fn load_file<T, E: Display>(path: &Path, parse: impl FnOnce(&str) -> Result<T, E>) -> io::Result<T> {
+    let text = fs::read_to_string(path).map_err(|e| with_path(path, e))?;
+    parse(&text).map_err(|e| with_path(path, io::Error::new(io::ErrorKind::InvalidData, e.to_string())))
+}
+
+// Every `*.<ext>` file in `dir` in file name order, parsed from its name and
+// text. One that does not parse fails them all.
+pub fn load_dir<T, E: Display>(dir: &Path, ext: &str, parse: impl Fn(&str, &str) -> Result<T, E>) -> io::Result<Vec<T>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| with_path(dir, e))? {
+        let path = entry.map_err(|e| with_path(dir, e))?.path();
+        if path.extension().is_some_and(|e| e == ext) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+            load_file(path, |text| parse(name, text))
+        })
+        .collect()
+}
@@ -0,0 +1,131 @@
+// github.rs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use crate::store;
+
+// One repository, in the GitHub REST API's own field names so a snapshot
+// and a saved `GET /users/{user}/repos` response read the same. The API
+// sends many more fields, they are dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Repo {
+    pub name: String,
+    pub description: Option<String>,
+    pub language: Option<String>,
+    pub stargazers_count: u64,
+    pub forks_count: u64,
+    pub html_url: String,
+    pub topics: Vec<String>,
+    pub fork: bool,
+    pub archived: bool,
+    pub pushed_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Repo {
+    // Last push, which is what GitHub calls "Updated" on a profile
+    pub fn updated(&self) -> Option<DateTime<Utc>> {
+        self.pushed_at.or(self.updated_at)
+    }
+}
+
+// What `sync-github` writes and the Repos page shows
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Snapshot {
+    pub user: Option<String>,
+    pub synced: Option<DateTime<Utc>>,
+    pub repos: Vec<Repo>,
+}
+
+impl Snapshot {
+    // https://github.com/<user>, from the repositories when the sync had no user
+    pub fn profile(&self) -> Option<String> {
+        let user = self.user.clone().or_else(|| self.repos.iter().find_map(|repo| owner(&repo.html_url)))?;
+        Some(format!("https://github.com/{}", user))
+    }
+}
+
+// Set once at startup, sessions only ever read it
+static SNAPSHOT: OnceLock<Snapshot> = OnceLock::new();
+
+pub fn install(snapshot: Snapshot) {
+    let _ = SNAPSHOT.set(snapshot);
+}
+
+pub fn snapshot() -> Option<&'static Snapshot> {
+    SNAPSHOT.get()
+}
+
+// A missing file is an empty snapshot: nothing was synced yet
+pub fn load(path: &Path) -> io::Result<Snapshot> {
+    store::read(path)
+}
+
+pub fn save(path: &Path, snapshot: Snapshot) -> io::Result<()> {
+    store::update(path, |stored: &mut Snapshot| *stored = snapshot).map(|_| ())
+}
+
+// The body of `GET /users/{user}/repos`, a JSON array of repositories
+pub fn parse_api(text: &str) -> serde_json::Result<Vec<Repo>> {
+    serde_json::from_str(text)
+}
+
+// Forks are left out unless asked for, the most starred come first
+pub fn snapshot_from(user: Option<&str>, repos: Vec<Repo>, forks: bool, synced: DateTime<Utc>) -> Snapshot {
+    let mut repos: Vec<Repo> = repos.into_iter().filter(|repo| forks || !repo.fork).collect();
+    repos.sort_by_key(|repo| std::cmp::Reverse(repo.stargazers_count));
+    Snapshot { user: user.map(str::to_string), synced: Some(synced), repos }
+}
+
+pub fn api_url(user: &str) -> String {
+    format!("https://api.github.com/users/{}/repos?per_page=100&type=owner&sort=pushed", user)
+}
+
+// Runs the configured fetch command (`curl -fsSL` by default) on the API
+// URL and returns what it printed. The portfolio has no HTTPS client of its
+// own, and sessions never fetch anything: only `sync-github` calls this.
+// A command still running after `timeout` is killed.
+pub fn fetch(command: &str, user: &str, timeout: Duration) -> io::Result<String> {
+    let mut words = command.split_whitespace();
+    let program = words.next().ok_or_else(|| io::Error::other("empty fetch command"))?;
+    let mut child = Command::new(program)
+        .args(words)
+        .arg(api_url(user))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", program, e)))?;
+    let stdout = child.stdout.take();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut body = String::new();
+        let read = stdout.map_or(Ok(0), |mut stdout| stdout.read_to_string(&mut body));
+        let _ = sender.send(read.map(|_| body));
+    });
+    let body = receiver.recv_timeout(timeout).unwrap_or_else(|_| {
+        let _ = child.kill();
+        Err(io::Error::new(io::ErrorKind::TimedOut, format!("{} did not finish in {:?}", program, timeout)))
+    });
+    // Reaped whatever happened, a failed read must not leave a zombie behind
+    let status = child.wait()?;
+    let body = body?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{} exited with {}", program, status)));
+    }
+    Ok(body)
+}
+
+// "mohamedalidridii" of https://github.com/mohamedalidridii/repo
+fn owner(url: &str) -> Option<String> {
+    let path = url.strip_prefix("https://github.com/")?;
+    path.split('/').next().filter(|user| !user.is_empty()).map(str::to_string)
+}
@@ -0,0 +1,185 @@
+// guestbook.rs
+
+use chrono::{DateTime, Duration as TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+use crate::config::GuestbookConfig;
+use crate::store;
+
+pub const MAX_NAME: usize = 40;
+pub const MAX_MESSAGE: usize = 500;
+pub const MAX_LINK: usize = 200;
+
+// Masked unless the owner adds more in `blocked_words`
+const PROFANITY: [&str; 9] = ["fuck", "shit", "bitch", "cunt", "asshole", "dick", "bastard", "slut", "whore"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub id: u64,
+    pub name: String,
+    pub message: String,
+    pub link: Option<String>,
+    pub posted: DateTime<Utc>,
+    // Salted hash of the client address, used for rate limiting
+    pub visitor: Option<String>,
+    pub approved: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Book {
+    pub next_id: u64,
+    pub entries: Vec<Entry>,
+}
+
+impl Book {
+    // Newest first, as shown on the page
+    pub fn approved(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().rev().filter(|entry| entry.approved)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draft {
+    pub name: String,
+    pub message: String,
+    pub link: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Posted {
+    Published,
+    // Waiting for the owner to approve it
+    Pending,
+}
+
+// Checks and cleans a draft, returning the fields to store or what is wrong
+pub fn validate(draft: &Draft, blocked_words: &[String]) -> Result<(String, String, Option<String>), String> {
+    let name = clean_line(&draft.name);
+    let message = clean_line(&draft.message);
+    let link = clean_line(&draft.link);
+
+    if name.is_empty() {
+        return Err("Please enter a name".to_string());
+    }
+    if message.is_empty() {
+        return Err("Please enter a message".to_string());
+    }
+    if name.chars().count() > MAX_NAME {
+        return Err(format!("The name can be at most {} characters", MAX_NAME));
+    }
+    if message.chars().count() > MAX_MESSAGE {
+        return Err(format!("The message can be at most {} characters", MAX_MESSAGE));
+    }
+    if link.chars().count() > MAX_LINK {
+        return Err(format!("The link can be at most {} characters", MAX_LINK));
+    }
+    let web = link.starts_with("https://") || link.starts_with("http://");
+    if !link.is_empty() && !web {
+        return Err("Links must start with https:// or http://".to_string());
+    }
+    if link.contains(char::is_whitespace) {
+        return Err("Links can not contain spaces".to_string());
+    }
+
+    let link = (!link.is_empty()).then_some(link);
+    Ok((mask(&name, blocked_words), mask(&message, blocked_words), link))
+}
+
+// Drops control characters (ANSI escapes start with one) and bidi overrides,
+// turns every run of whitespace into a single space
+pub fn clean_line(text: &str) -> String {
+    let kept: String = text
+        .chars()
+        .filter(|c| !c.is_control() || c.is_whitespace())
+        .filter(|c| !matches!(c, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}' | '\u{200e}' | '\u{200f}'))
+        .collect();
+    kept.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// "shit happens" -> "s*** happens", whole words only, any case
+pub fn mask(text: &str, blocked_words: &[String]) -> String {
+    let blocked = |word: &str| {
+        let word = word.to_lowercase();
+        PROFANITY.contains(&word.as_str()) || blocked_words.iter().any(|b| b.to_lowercase() == word)
+    };
+
+    let mut out = String::with_capacity(text.len());
+    let mut word = String::new();
+    let flush = |word: &mut String, out: &mut String| {
+        if blocked(word) {
+            let mut chars = word.chars();
+            out.extend(chars.next());
+            out.extend(chars.map(|_| '*'));
+        } else {
+            out.push_str(word);
+        }
+        word.clear();
+    };
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut out);
+            out.push(c);
+        }
+    }
+    flush(&mut word, &mut out);
+    out
+}
+
+pub fn load(config: &GuestbookConfig) -> io::Result<Book> {
+    store::read(&config.file)
+}
+
+// Stores a draft unless the same visitor posted too recently. Visitors
+// without a hash (no client address, or no salt) share one rate limit.
+pub fn post(config: &GuestbookConfig, draft: &Draft, visitor: Option<&str>) -> Result<Posted, String> {
+    let (name, message, link) = validate(draft, &config.blocked_words)?;
+    let now = Utc::now();
+    let window = TimeDelta::seconds(config.rate_limit_secs as i64);
+
+    let (result, _) = store::update(&config.file, |book: &mut Book| {
+        let recent = book
+            .entries
+            .iter()
+            .any(|entry| entry.visitor.as_deref() == visitor && now - entry.posted < window);
+        if recent {
+            return Err("You signed the guestbook a moment ago, please try again later".to_string());
+        }
+        book.next_id += 1;
+        book.entries.push(Entry {
+            id: book.next_id,
+            name,
+            message,
+            link,
+            posted: now,
+            visitor: visitor.map(str::to_string),
+            approved: !config.moderation,
+        });
+        Ok(if config.moderation { Posted::Pending } else { Posted::Published })
+    })
+    .map_err(|e| {
+        log::warn!(error = e.to_string(); "could not write the guestbook");
+        "The guestbook is not available right now".to_string()
+    })?;
+    result
+}
+
+// Owner moderation, returns false when there is no such entry
+pub fn approve(path: &Path, id: u64) -> io::Result<bool> {
+    let (found, _) = store::update(path, |book: &mut Book| {
+        book.entries.iter_mut().find(|entry| entry.id == id).map(|entry| entry.approved = true).is_some()
+    })?;
+    Ok(found)
+}
+
+pub fn delete(path: &Path, id: u64) -> io::Result<bool> {
+    let (found, _) = store::update(path, |book: &mut Book| {
+        let before = book.entries.len();
+        book.entries.retain(|entry| entry.id != id);
+        book.entries.len() != before
+    })?;
+    Ok(found)
+}
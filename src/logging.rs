@@ -0,0 +1,188 @@
+// logging.rs
+
+use chrono::{SecondsFormat, Utc};
+use crossterm::tty::IsTty;
+use log::kv::{self, Key, Value, VisitSource};
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::Deserialize;
+use serde_json::{Map, Number};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+use crate::config::LogConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => LevelFilter::Off,
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    // One JSON object per line
+    #[default]
+    Json,
+    Text,
+}
+
+thread_local! {
+    // The visit this thread serves, when one process serves many
+    static SESSION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+struct Logger {
+    level: LevelFilter,
+    format: LogFormat,
+    // Added to every record so the lines of one visit can be grouped
+    session: String,
+    sink: Mutex<Box<dyn Write + Send>>,
+}
+
+// Installs the logger described by `config`. Nothing is ever written to the
+// visitor's screen: without a file, stderr is only used when it has been
+// redirected away from the terminal.
+pub fn init(config: &LogConfig, session: &str) -> io::Result<()> {
+    let sink: Box<dyn Write + Send> = match &config.file {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+            Box::new(file)
+        }
+        None if !io::stderr().is_tty() => Box::new(io::stderr()),
+        None => return Ok(()),
+    };
+
+    let level = LevelFilter::from(config.level);
+    let logger = Logger {
+        level,
+        format: config.format,
+        session: session.to_string(),
+        sink: Mutex::new(sink),
+    };
+    log::set_boxed_logger(Box::new(logger))
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    log::set_max_level(level);
+    Ok(())
+}
+
+// Tags the records logged from this thread with `session` instead of the
+// one given to `init`
+pub fn set_session(session: &str) {
+    SESSION.with(|current| *current.borrow_mut() = Some(session.to_string()));
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let session = SESSION.with(|session| session.borrow().clone());
+        let session = session.as_deref().unwrap_or(&self.session);
+        let line = match self.format {
+            LogFormat::Json => json_line(record, session),
+            LogFormat::Text => text_line(record, session),
+        };
+        if let Ok(mut sink) = self.sink.lock() {
+            // A full disk must not take the session down with it
+            let _ = writeln!(sink, "{}", line);
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut sink) = self.sink.lock() {
+            let _ = sink.flush();
+        }
+    }
+}
+
+fn timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn json_line(record: &Record, session: &str) -> String {
+    let mut fields = Map::new();
+    fields.insert("ts".into(), timestamp().into());
+    fields.insert("level".into(), record.level().as_str().into());
+    fields.insert("target".into(), record.target().into());
+    fields.insert("session".into(), session.into());
+    fields.insert("msg".into(), record.args().to_string().into());
+    let _ = record.key_values().visit(&mut JsonFields(&mut fields));
+    serde_json::Value::Object(fields).to_string()
+}
+
+struct JsonFields<'a>(&'a mut Map<String, serde_json::Value>);
+
+impl<'kvs> VisitSource<'kvs> for JsonFields<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        // Numbers and booleans stay typed so they can be queried as such
+        let value = if let Some(n) = value.to_u64() {
+            n.into()
+        } else if let Some(n) = value.to_i64() {
+            n.into()
+        } else if let Some(n) = value.to_f64().and_then(Number::from_f64) {
+            n.into()
+        } else if let Some(b) = value.to_bool() {
+            b.into()
+        } else {
+            value.to_string().into()
+        };
+        self.0.insert(key.as_str().to_string(), value);
+        Ok(())
+    }
+}
+
+fn text_line(record: &Record, session: &str) -> String {
+    let mut line = format!(
+        "{} {:<5} {} [{}] {}",
+        timestamp(),
+        record.level(),
+        record.target(),
+        session,
+        record.args()
+    );
+    let _ = record.key_values().visit(&mut TextFields(&mut line));
+    line
+}
+
+struct TextFields<'a>(&'a mut String);
+
+impl<'kvs> VisitSource<'kvs> for TextFields<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        let value = value.to_string();
+        if value.is_empty() || value.contains(char::is_whitespace) {
+            self.0.push_str(&format!(" {}={:?}", key, value));
+        } else {
+            self.0.push_str(&format!(" {}={}", key, value));
+        }
+        Ok(())
+    }
+}
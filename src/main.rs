@@ -1,201 +1,712 @@
-use crossterm::event::{DisableMouseCapture};
+use crossterm::event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture};
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, Clear, ClearType},
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     cursor,
-    style::{Color, Print, ResetColor, SetForegroundColor, SetAttribute, Attribute},
+    style::Print,
 };
+use base64::Engine;
 use std::io::{self, Write};
 use std::time::Duration;
 
+use log::{debug, error, info, warn};
+
+mod analytics;
+mod cli;
+mod config;
+mod contact;
+mod contributions;
+mod counter;
+mod export;
+mod files;
+mod github;
+mod guestbook;
+mod logging;
+mod posts;
+mod projects;
+mod resume;
+mod session;
+mod store;
+#[cfg(test)]
+mod tests;
 mod ui;
+mod web;
+use analytics::{SessionRecord, Tracker};
+use cli::{Command, Export, Mailbox, Moderation, Sync};
+use config::Config;
+use counter::Counter;
+use session::Session;
+use ui::buffer::Buffer;
+use ui::contact::Contact;
+use ui::content::{ContentView, Scrollbar};
+use ui::finder::{finder_overlay, Finder};
+use ui::footer::{self, Footer};
+use ui::guestbook::{form_overlay, Guestbook};
 use ui::handler::{Page, PageContent};
-use ui::header::draw_header;
+use ui::history::{History, Location};
+use ui::blog::Reading;
+use ui::header::{self, nav_hit, Breadcrumb, Header};
+use ui::help::Help;
+use ui::keymap::{lookup_prompt, Action, Keymap, PromptAction, SEARCH_KEYS, SEARCH_PROMPT_KEYS};
+use ui::layout::{split_horizontal, split_vertical, Constraint, Rect};
+use ui::markup::{char_at_column, char_slice, display_width, links, Line};
+use ui::heatmap::{self, Heatmap};
+use ui::projects::Catalog;
+use ui::repos::Repos;
+use ui::resume::ResumePages;
+use ui::search::Search;
+use ui::terminal::Terminal;
+use ui::toc::{self, TocSidebar};
+use ui::view::{Context, Handled, View};
+use ui::widget::Widget;
+
+const WHEEL_LINES: u16 = 3;
+const MAX_BURST: usize = 4096;
+const GOODBYE: &str = "✨ Thanks for visiting btw I use Arch! ✨";
+
+// The regions of the screen, see `Portfolio::screen`
+struct Screen {
+    area: Rect,
+    header: Rect,
+    breadcrumb: Rect,
+    content: Rect,
+    scrollbar: Rect,
+    sidebar: Option<Rect>,
+    footer: Rect,
+}
 
 struct Portfolio {
     current_page: Page,
     scroll_offset: u16,
     terminal_height: u16,
     terminal_width: u16,
+    keymap: Keymap,
+    search: Search,
+    finder: Finder,
+    show_toc: bool,
+    history: History,
+    // The help overlay while it is open
+    help: Option<Help>,
+    mouse: bool,
+    dragging_scrollbar: bool,
+    // Shown in place of the footer hints until the next key press
+    status: Option<String>,
+    // Sent to the terminal clipboard (OSC 52) on the next render
+    clipboard: Option<String>,
+    terminal: Terminal,
+    tracker: Tracker,
+    // Shared visitor counter shown in the footer, when enabled
+    counter: Option<Counter>,
+    // None while the owner keeps the guestbook closed
+    guestbook: Option<Guestbook>,
+    // None when the contact form is off
+    contact: Option<Contact>,
+    // Selected card and open project on the Projects page
+    catalog: Catalog,
+    // Order of the Repos page
+    repos: Repos,
+    // Selected and open post on the Blog page
+    reading: Reading,
+    // The About page's pages, when it is generated from a resume
+    about: Option<ResumePages>,
 }
 
 impl Portfolio {
-    fn new() -> Self {
+    fn new(keymap: Keymap, mouse: bool) -> Self {
         let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
         Self {
             current_page: Page::Home,
             scroll_offset: 0,
             terminal_height: height,
             terminal_width: width,
+            keymap,
+            search: Search::default(),
+            finder: Finder::new(),
+            show_toc: true,
+            history: History::default(),
+            help: None,
+            mouse,
+            dragging_scrollbar: false,
+            status: None,
+            clipboard: None,
+            terminal: Terminal::default(),
+            tracker: Tracker::new(Page::Home),
+            counter: None,
+            guestbook: None,
+            contact: None,
+            catalog: Catalog::default(),
+            repos: Repos::default(),
+            reading: Reading::default(),
+            about: PageContent::resume().map(ResumePages::new),
         }
     }
 
-    fn render(&mut self, stdout: &mut io::Stdout) -> crossterm::Result<()> {
-        execute!(stdout, Clear(ClearType::All), cursor::MoveTo(0, 0))?;
-        
-        // Draw header
-        let header_height = draw_header(stdout, 0, 0, &self.current_page)?;
-        
-        // Draw page content
-        let content_start_y = header_height + 1;
-        self.draw_content(stdout, content_start_y)?;
-        
-        // Draw footer with instructions
-        self.draw_footer(stdout)?;
-        
-        stdout.flush()?;
-        Ok(())
+    fn content_height(&self) -> u16 {
+        self.screen().content.height
+    }
+
+    fn toc_visible(&self) -> bool {
+        self.show_toc && self.terminal_width >= toc::MIN_TERMINAL_WIDTH
+    }
+
+    // The current page's state, for the pages that keep one
+    fn view(&self) -> Option<&dyn View> {
+        match self.current_page {
+            Page::Store => Some(&self.catalog),
+            Page::Blog => Some(&self.reading),
+            Page::Repos => Some(&self.repos),
+            Page::Guestbook => self.guestbook.as_ref().map(|guestbook| guestbook as &dyn View),
+            Page::About => self.about.as_ref().map(|about| about as &dyn View),
+            _ => None,
+        }
+    }
+
+    fn context(&self) -> Context<'_> {
+        Context { keymap: &self.keymap, area: self.screen().content, scroll: self.scroll_offset }
     }
 
-    fn draw_content(&self, stdout: &mut io::Stdout, start_y: u16) -> crossterm::Result<()> {
+    // Hands some input to the current page's state and takes back the
+    // scroll position it leaves
+    fn with_view(&mut self, f: impl FnOnce(&mut dyn View, &mut Context) -> Handled) -> Handled {
+        let mut ctx = Context { keymap: &self.keymap, area: self.screen().content, scroll: self.scroll_offset };
+        let view: &mut dyn View = match self.current_page {
+            Page::Store => &mut self.catalog,
+            Page::Blog => &mut self.reading,
+            Page::Repos => &mut self.repos,
+            Page::Guestbook => match &mut self.guestbook {
+                Some(guestbook) => guestbook,
+                None => return Handled::No,
+            },
+            Page::About => match &mut self.about {
+                Some(about) => about,
+                None => return Handled::No,
+            },
+            _ => return Handled::No,
+        };
+        let handled = f(view, &mut ctx);
+        self.scroll_offset = ctx.scroll;
+        if handled == Handled::Replaced {
+            self.search.clear();
+        }
+        handled
+    }
+
+    // The current page's text, with the guestbook entries filled in
+    fn page_content(&self) -> String {
+        if let Some(view) = self.view() {
+            return view.text(&self.context());
+        }
+        if self.current_page == Page::Home {
+            return self.home_page().0;
+        }
         let content = PageContent::get_content(&self.current_page);
-        let max_height = self.terminal_height.saturating_sub(start_y + 3);
-        
-        for (i, line) in content.lines().enumerate().skip(self.scroll_offset as usize) {
-            if i >= (self.scroll_offset as usize + max_height as usize) {
-                break;
-            }
-            
-            let y = start_y + (i - self.scroll_offset as usize) as u16;
-            execute!(stdout, cursor::MoveTo(2, y))?;
-            
-            // Parse and render colored lines
-            if line.starts_with("# ") {
-                execute!(
-                    stdout,
-                    SetForegroundColor(Color::Cyan),
-                    SetAttribute(Attribute::Bold),
-                    Print(&line[2..]),
-                    SetAttribute(Attribute::Reset),
-                    ResetColor
-                )?;
-            } else if line.starts_with("## ") {
-                execute!(
-                    stdout,
-                    SetForegroundColor(Color::Green),
-                    SetAttribute(Attribute::Bold),
-                    Print(&line[3..]),
-                    SetAttribute(Attribute::Reset),
-                    ResetColor
-                )?;
-            } else if line.starts_with("- ") {
-                execute!(
-                    stdout,
-                    SetForegroundColor(Color::Yellow),
-                    Print("● "),
-                    SetForegroundColor(Color::White),
-                    Print(&line[2..]),
-                    ResetColor
-                )?;
-            } else if line.starts_with("```") {
-                execute!(
-                    stdout,
-                    SetForegroundColor(Color::Magenta),
-                    SetAttribute(Attribute::Dim),
-                    Print(line),
-                    SetAttribute(Attribute::Reset),
-                    ResetColor
-                )?;
-            } else {
-                execute!(stdout, SetForegroundColor(Color::White), Print(line), ResetColor)?;
+        match self.current_page {
+            Page::FAQ if self.contact.is_some() => ui::contact::page(&content, &self.keymap),
+            Page::Guestbook => ui::guestbook::page(&content, None, 0, &self.keymap, chrono::Utc::now()),
+            _ => content,
+        }
+    }
+
+    // The home page with the contribution calendar as wide as the screen
+    // allows, and the line the calendar starts on
+    fn home_page(&self) -> (String, Option<usize>) {
+        let keys: Vec<String> = [Page::Store, Page::About, Page::FAQ]
+            .iter()
+            .filter_map(|&page| self.keymap.first_key(Action::GoTo(page)).map(|key| format!("'{}' for {}", key, page.label())))
+            .collect();
+        let hint = (!keys.is_empty()).then(|| format!("Press {}", keys.join(" | ")));
+        let mut home = PageContent::home_content(hint.as_deref());
+        let calendar = contributions::calendar().map(|calendar| {
+            let width = self.screen().content.width as usize;
+            heatmap::append(&mut home, calendar, chrono::Utc::now().date_naive(), width)
+        });
+        (home, calendar)
+    }
+
+    // Where the page is, e.g. "Projects › Rust › newest first"
+    fn breadcrumb(&self) -> Vec<String> {
+        let mut parts = vec![self.current_page.label().to_string()];
+        if let Some(view) = self.view() {
+            parts.extend(view.breadcrumb());
+        }
+        parts
+    }
+
+    fn max_scroll(&self) -> u16 {
+        let lines = self.page_content().lines().count() as u16;
+        lines.saturating_sub(self.content_height())
+    }
+
+    fn scroll_to(&mut self, offset: u16) {
+        self.scroll_offset = offset.min(self.max_scroll());
+    }
+
+    fn location(&self) -> Location {
+        Location { page: self.current_page, scroll: self.scroll_offset }
+    }
+
+    // Opens a page where the reader last left it and records the move
+    fn go_to(&mut self, page: Page) {
+        if page == self.current_page {
+            self.scroll_to(0);
+            return;
+        }
+        self.history.visit(self.location());
+        self.show(Location { page, scroll: self.history.position(page) });
+    }
+
+    // Opens `page`, or one of its projects or posts, scrolled to `line`, e.g. from the finder
+    fn jump_to(&mut self, page: Page, item: Option<usize>, line: usize) {
+        self.history.visit(self.location());
+        if page != self.current_page {
+            self.show(Location { page, scroll: 0 });
+        }
+        self.with_view(|view, ctx| view.open(item, ctx));
+        self.reveal(line);
+    }
+
+    fn show(&mut self, location: Location) {
+        if location.page != self.current_page {
+            info!(page = location.page.label(); "page visited");
+            self.tracker.enter(location.page);
+            if location.page == Page::Guestbook {
+                if let Some(guestbook) = &mut self.guestbook {
+                    guestbook.reload();
+                }
             }
         }
-        
-        Ok(())
+        self.current_page = location.page;
+        self.search.clear();
+        self.scroll_to(location.scroll);
     }
 
-    fn draw_footer(&self, stdout: &mut io::Stdout) -> crossterm::Result<()> {
-        let footer_y = self.terminal_height - 2;
-        
-        execute!(
-            stdout,
-            cursor::MoveTo(0, footer_y),
-            SetForegroundColor(Color::DarkGrey),
-            Print("─".repeat(self.terminal_width as usize)),
-            cursor::MoveTo(2, footer_y + 1),
-            SetForegroundColor(Color::Grey),
-            Print("Navigation: "),
-            SetForegroundColor(Color::Cyan),
-            Print("a"),
-            SetForegroundColor(Color::Grey),
-            Print(" Projects | "),
-            SetForegroundColor(Color::Cyan),
-            Print("s"),
-            SetForegroundColor(Color::Grey),
-            Print(" About | "),
-            SetForegroundColor(Color::Cyan),
-            Print("d"),
-            SetForegroundColor(Color::Grey),
-            Print(" Contact | "),
-            SetForegroundColor(Color::Cyan),
-            Print("h"),
-            SetForegroundColor(Color::Grey),
-            Print(" Home | "),
-            SetForegroundColor(Color::Cyan),
-            Print("q"),
-            SetForegroundColor(Color::Grey),
-            Print(" Quit | "),
-            SetForegroundColor(Color::Cyan),
-            Print("↑↓"),
-            SetForegroundColor(Color::Grey),
-            Print(" Scroll"),
-            ResetColor
-        )?;
+    // Scrolls just enough context around `line` when it is off screen
+    fn reveal(&mut self, line: usize) {
+        let line = line as u16;
+        let height = self.content_height();
+        if line < self.scroll_offset || line >= self.scroll_offset + height {
+            self.scroll_to(line.saturating_sub(height / 3));
+        }
+    }
+
+    // Where everything goes on the current terminal, shared by drawing and mouse hit-testing
+    fn screen(&self) -> Screen {
+        let area = Rect::new(0, 0, self.terminal_width, self.terminal_height);
+        let rows = split_vertical(
+            area,
+            &[
+                Constraint::Length(header::HEIGHT),
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+                Constraint::Length(footer::HEIGHT),
+            ],
+        );
+        let sidebar = if self.toc_visible() { toc::SIDEBAR_WIDTH + 1 } else { 0 };
+        let columns = split_horizontal(
+            rows[2],
+            &[Constraint::Length(2), Constraint::Min(0), Constraint::Length(1), Constraint::Length(sidebar)],
+        );
+        Screen {
+            area,
+            header: rows[0],
+            breadcrumb: rows[1],
+            content: columns[1],
+            scrollbar: columns[2],
+            sidebar: self.toc_visible().then(|| columns[3]),
+            footer: rows[4],
+        }
+    }
+
+    fn render(&mut self, out: &mut impl Write) -> crossterm::Result<()> {
+        let mut frame = Buffer::new(self.terminal_width, self.terminal_height);
+        self.draw(&mut frame);
+        self.terminal.draw(frame, out)?;
+
+        if let Some(text) = self.clipboard.take() {
+            execute!(out, Print(format!("\x1b]52;c;{}\x07", base64::engine::general_purpose::STANDARD.encode(text))))?;
+        }
         
+        out.flush()?;
         Ok(())
     }
 
+    fn draw(&mut self, frame: &mut Buffer) {
+        let screen = self.screen();
+        let content = self.page_content();
+
+        Header { page: self.current_page }.render(screen.header, frame);
+        Breadcrumb { parts: &self.breadcrumb() }.render(screen.breadcrumb, frame);
+        ContentView { content: &content, scroll: self.scroll_offset, search: &self.search }.render(screen.content, frame);
+        if let Some(view) = self.view() {
+            view.draw(&self.context(), &self.search, frame);
+        }
+        if let (Page::Home, Some(calendar)) = (self.current_page, contributions::calendar()) {
+            if let (_, Some(first_line)) = self.home_page() {
+                Heatmap {
+                    calendar,
+                    today: chrono::Utc::now().date_naive(),
+                    width: screen.content.width as usize,
+                    first_line,
+                    scroll: self.scroll_offset,
+                    search: &self.search,
+                }
+                .render(screen.content, frame);
+            }
+        }
+        Scrollbar { offset: self.scroll_offset, max_scroll: self.max_scroll() }.render(screen.scrollbar, frame);
+        if let Some(sidebar) = screen.sidebar {
+            TocSidebar::new(&content, self.scroll_offset).render(sidebar, frame);
+        }
+
+        let right = match &self.counter {
+            Some(counter) => footer::counter(&counter.stats, chrono::Utc::now()),
+            None => Vec::new(),
+        };
+        let right_width: u16 = right.iter().map(|span| span.width() as u16).sum();
+        let line = if self.search.is_active() {
+            footer::search_prompt(&self.search, &self.keymap)
+        } else if let Some(status) = &self.status {
+            footer::status(status)
+        } else {
+            // The counter gives way to the hints on narrow terminals. The
+            // line is padded by 2 columns on both sides.
+            let mut width = screen.footer.width.saturating_sub(4);
+            if right_width > 0 && footer::hints(&self.keymap, width.saturating_sub(right_width + 4)).len() > 1 {
+                width = width.saturating_sub(right_width + 4);
+            }
+            footer::hints(&self.keymap, width)
+        };
+        Footer { line, right }.render(screen.footer, frame);
+
+        if self.finder.open {
+            finder_overlay(&self.finder).render(screen.area, frame);
+        }
+        if let Some(form) = self.guestbook.as_ref().and_then(|g| g.form.as_ref()) {
+            form_overlay(form).render(screen.area, frame);
+        }
+        if let Some(form) = self.contact.as_ref().and_then(|c| c.form.as_ref()) {
+            ui::contact::form_overlay(form).render(screen.area, frame);
+        }
+        if let Some(help) = &mut self.help {
+            help.draw(&self.keymap, self.mouse, screen.area, frame);
+        }
+    }
+
     fn handle_input(&mut self) -> crossterm::Result<bool> {
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(KeyEvent { code, modifiers, .. }) = event::read()? {
-                match code {
-                    KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
-                    KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return Ok(false),
-                    KeyCode::Char('h') | KeyCode::Home => {
-                        self.current_page = Page::Home;
-                        self.scroll_offset = 0;
-                    }
-                    KeyCode::Char('a') => {
-                        self.current_page = Page::Store;
-                        self.scroll_offset = 0;
-                    }
-                    KeyCode::Char('s') => {
-                        self.current_page = Page::About;
-                        self.scroll_offset = 0;
-                    }
-                    KeyCode::Char('d') => {
-                        self.current_page = Page::FAQ;
-                        self.scroll_offset = 0;
-                    }
-                    KeyCode::Up => {
-                        self.scroll_offset = self.scroll_offset.saturating_sub(1);
-                    }
-                    KeyCode::Down => {
-                        self.scroll_offset = self.scroll_offset.saturating_add(1);
-                    }
-                    KeyCode::PageUp => {
-                        self.scroll_offset = self.scroll_offset.saturating_sub(10);
-                    }
-                    KeyCode::PageDown => {
-                        self.scroll_offset = self.scroll_offset.saturating_add(10);
-                    }
-                    _ => {}
+        if !event::poll(Duration::from_millis(100))? {
+            return Ok(true);
+        }
+        let mut events = vec![event::read()?];
+        while events.len() < MAX_BURST && event::poll(Duration::ZERO)? {
+            events.push(event::read()?);
+        }
+        Ok(self.handle_events(events))
+    }
+
+    // Events that arrived together, returns false when the portfolio should exit
+    fn handle_events(&mut self, events: Vec<Event>) -> bool {
+        events.into_iter().all(|event| self.handle_event(event))
+    }
+
+    // Inserts a bracketed paste into the focused text field, if there is one.
+    // Anywhere else it is dropped rather than run as key presses.
+    fn paste(&mut self, text: &str) {
+        self.status = None;
+        if self.help.is_some() {
+            return;
+        }
+        if self.finder.open {
+            self.finder.paste(text);
+        } else if let Some(form) = self.guestbook.as_mut().and_then(|g| g.form.as_mut()) {
+            form.focused().insert_str(text);
+        } else if let Some(form) = self.contact.as_mut().and_then(|c| c.form.as_mut()) {
+            form.focused().insert_str(text);
+        } else if self.search.editing {
+            self.search.input.insert_str(text);
+            self.update_search();
+        }
+    }
+
+    // Returns false when the portfolio should exit
+    fn handle_event(&mut self, event: Event) -> bool {
+        match event {
+            Event::Mouse(mouse) => self.handle_mouse(mouse),
+            Event::Key(key) => return self.handle_key(key),
+            Event::Paste(text) => self.paste(&text),
+            Event::Resize(width, height) => self.resize(width, height),
+            Event::FocusGained | Event::FocusLost => {}
+        }
+        true
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.status = None;
+        // Overlays and the search prompt take every key but Ctrl-C
+        let prompt = self.help.is_some() || self.finder.open || self.form_open() || self.search.editing;
+        if prompt && is_ctrl_c(&key) {
+            return false;
+        }
+        if let Some(help) = &mut self.help {
+            if !help.handle_key(&key) {
+                self.help = None;
+            }
+            return true;
+        }
+        if self.finder.open {
+            if let Some(entry) = self.finder.handle_key(&key) {
+                self.jump_to(entry.page, entry.item, entry.line);
+            }
+            return true;
+        }
+        if let Some(guestbook) = self.guestbook.as_mut().filter(|g| g.form.is_some()) {
+            if let Some(thanks) = guestbook.form_key(&key) {
+                self.status = Some(thanks);
+                self.scroll_to(0);
+            }
+            return true;
+        }
+        if let Some(contact) = self.contact.as_mut().filter(|c| c.form.is_some()) {
+            self.status = contact.form_key(&key);
+            return true;
+        }
+        if self.search.editing {
+            self.search_input(key);
+            return true;
+        }
+        if self.search.is_active() && lookup_prompt(SEARCH_KEYS, &key) == Some(PromptAction::Cancel) {
+            self.search.clear();
+            return true;
+        }
+        if self.with_view(|view, ctx| view.handle_key(&key, ctx)) != Handled::No {
+            return true;
+        }
+        match self.keymap.lookup(&key) {
+            Some(action) => self.perform(action),
+            // Ctrl-C quits even when the config leaves it unbound
+            None => !is_ctrl_c(&key),
+        }
+    }
+
+    fn resize(&mut self, width: u16, height: u16) {
+        if (width, height) != (self.terminal_width, self.terminal_height) {
+            debug!(width, height; "terminal resized");
+        }
+        self.terminal_width = width;
+        self.terminal_height = height;
+        self.scroll_to(self.scroll_offset);
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let (column, row) = (mouse.column, mouse.row);
+        if let Some(help) = &mut self.help {
+            match mouse.kind {
+                MouseEventKind::ScrollDown => help.scroll = help.scroll.saturating_add(WHEEL_LINES),
+                MouseEventKind::ScrollUp => help.scroll = help.scroll.saturating_sub(WHEEL_LINES),
+                MouseEventKind::Down(_) => self.help = None,
+                _ => {}
+            }
+            return;
+        }
+        match mouse.kind {
+            _ if self.form_open() => {}
+            MouseEventKind::ScrollDown if self.finder.open => self.finder.select_next(),
+            MouseEventKind::ScrollUp if self.finder.open => self.finder.select_prev(),
+            MouseEventKind::ScrollDown => self.scroll_to(self.scroll_offset.saturating_add(WHEEL_LINES)),
+            MouseEventKind::ScrollUp => self.scroll_to(self.scroll_offset.saturating_sub(WHEEL_LINES)),
+            MouseEventKind::Down(MouseButton::Left) if !self.finder.open => self.click(column, row),
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging_scrollbar => self.drag_scrollbar(row),
+            MouseEventKind::Up(_) => self.dragging_scrollbar = false,
+            _ => {}
+        }
+    }
+
+    fn click(&mut self, column: u16, row: u16) {
+        self.status = None;
+        let screen = self.screen();
+        if let Some(page) = nav_hit(screen.header.x, screen.header.y, column, row) {
+            self.go_to(page);
+            return;
+        }
+
+        if screen.scrollbar.contains(column, row) && self.max_scroll() > 0 {
+            self.dragging_scrollbar = true;
+            self.drag_scrollbar(row);
+        } else if let Some(sidebar) = screen.sidebar.filter(|s| s.contains(column, row) && column > s.x) {
+            let content = self.page_content();
+            let toc = TocSidebar::new(&content, self.scroll_offset);
+            let target = toc.entries(sidebar).into_iter().find(|(y, _, _)| *y == row).map(|(_, h, _)| h.line);
+            if let Some(line) = target {
+                self.scroll_to(line as u16);
+            }
+        } else if screen.content.contains(column, row) {
+            if let Some(url) = self.link_at(screen.content, column, row) {
+                self.status = Some(format!("Copied {} to your clipboard", url));
+                self.clipboard = Some(url);
+            } else {
+                let column = (column - screen.content.x) as usize;
+                self.with_view(|view, ctx| view.click(ctx.line_at(row), column, ctx));
+            }
+        }
+    }
+
+    fn drag_scrollbar(&mut self, row: u16) {
+        let area = self.screen().scrollbar;
+        let height = area.height.max(2);
+        let row = row.saturating_sub(area.y).min(height - 1);
+        self.scroll_to((row as u32 * self.max_scroll() as u32 / (height - 1) as u32) as u16);
+    }
+
+    fn link_at(&self, area: Rect, column: u16, row: u16) -> Option<String> {
+        let content = self.page_content();
+        let index = (self.scroll_offset + row - area.y) as usize;
+        let line = Line::parse(content.lines().nth(index)?);
+        let column = (column as usize).checked_sub(area.x as usize + display_width(line.marker()))?;
+        let at = char_at_column(line.text, column)?;
+        links(line.text)
+            .into_iter()
+            .find(|(start, end)| *start <= at && at < *end)
+            .map(|(start, end)| char_slice(line.text, start, end).to_string())
+    }
+
+    fn form_open(&self) -> bool {
+        self.guestbook.as_ref().is_some_and(|g| g.form.is_some())
+            || self.contact.as_ref().is_some_and(|c| c.form.is_some())
+    }
+
+    // Keys typed while the search prompt is open
+    fn search_input(&mut self, key: KeyEvent) {
+        match lookup_prompt(SEARCH_PROMPT_KEYS, &key) {
+            Some(PromptAction::Cancel) => {
+                let origin = self.search.origin;
+                self.search.clear();
+                self.scroll_to(origin);
+            }
+            Some(PromptAction::Confirm) => {
+                self.search.editing = false;
+                if self.search.input.is_empty() {
+                    self.search.clear();
                 }
             }
+            _ => {
+                let before = self.search.input.value().to_string();
+                self.search.input.handle_key(&key);
+                if self.search.input.value() != before {
+                    self.update_search();
+                }
+            }
+        }
+    }
+
+    fn update_search(&mut self) {
+        let content = self.page_content();
+        self.search.update(&content, self.search.origin as usize);
+        match self.search.current_match() {
+            Some(m) => self.reveal(m.line),
+            None => self.scroll_to(self.search.origin),
         }
-        Ok(true)
+    }
+
+    // Returns false when the portfolio should exit
+    fn perform(&mut self, action: Action) -> bool {
+        if self.with_view(|view, ctx| view.perform(action, ctx)) != Handled::No {
+            return true;
+        }
+        let page = self.content_height();
+        match action {
+            Action::Quit => return false,
+            Action::GoTo(target) => self.go_to(target),
+            Action::Back => {
+                if let Some(location) = self.history.back(self.location()) {
+                    self.show(location);
+                }
+            }
+            Action::Forward => {
+                if let Some(location) = self.history.forward(self.location()) {
+                    self.show(location);
+                }
+            }
+            Action::NextPage => self.go_to(self.current_page.next()),
+            Action::PrevPage => self.go_to(self.current_page.prev()),
+            Action::ScrollUp => self.scroll_to(self.scroll_offset.saturating_sub(1)),
+            Action::ScrollDown => self.scroll_to(self.scroll_offset.saturating_add(1)),
+            Action::PageUp => self.scroll_to(self.scroll_offset.saturating_sub(page)),
+            Action::PageDown => self.scroll_to(self.scroll_offset.saturating_add(page)),
+            Action::HalfPageUp => self.scroll_to(self.scroll_offset.saturating_sub(page / 2)),
+            Action::HalfPageDown => self.scroll_to(self.scroll_offset.saturating_add(page / 2)),
+            Action::Top => self.scroll_to(0),
+            Action::Bottom => self.scroll_to(u16::MAX),
+            Action::Search => self.search.start(self.scroll_offset),
+            Action::Finder => self.finder.open(),
+            Action::Help => self.help = Some(Help::default()),
+            Action::ToggleToc => self.show_toc = !self.show_toc,
+            Action::NextSection | Action::PrevSection => {
+                let content = self.page_content();
+                let headings = toc::headings(&content);
+                let target = if action == Action::NextSection {
+                    toc::next(&headings, self.scroll_offset)
+                } else {
+                    toc::prev(&headings, self.scroll_offset)
+                };
+                if let Some(heading) = target {
+                    self.scroll_to(heading.line as u16);
+                }
+            }
+            Action::WriteGuestbook => match &mut self.guestbook {
+                Some(guestbook) => {
+                    guestbook.form = Some(ui::guestbook::form());
+                    self.go_to(Page::Guestbook);
+                }
+                None => self.status = Some("The guestbook is closed at the moment".to_string()),
+            },
+            Action::WriteMessage => match &mut self.contact {
+                Some(contact) => {
+                    contact.form = Some(ui::contact::form());
+                    self.go_to(Page::FAQ);
+                }
+                None => self.status = Some("The contact form is closed, the links on the Contact page still work".to_string()),
+            },
+            Action::SearchNext => {
+                if let Some(m) = self.search.next() {
+                    self.reveal(m.line);
+                }
+            }
+            Action::SearchPrev => {
+                if let Some(m) = self.search.prev() {
+                    self.reveal(m.line);
+                }
+            }
+            // Page keys on a page that does not use them
+            Action::NextTag
+            | Action::PrevTag
+            | Action::ToggleTag
+            | Action::ClearTags
+            | Action::Sort
+            | Action::NewerEntries
+            | Action::OlderEntries
+            | Action::NewerPost
+            | Action::OlderPost
+            | Action::NextResumePage
+            | Action::PrevResumePage => {}
+        }
+        true
     }
 
     fn run(&mut self) -> crossterm::Result<()> {
         let mut stdout = io::stdout();
         
         enable_raw_mode()?;
-        execute!(stdout, EnterAlternateScreen, cursor::Hide, DisableMouseCapture)?;
+        execute!(stdout, EnterAlternateScreen, cursor::Hide, EnableBracketedPaste)?;
+        if self.mouse {
+            execute!(stdout, EnableMouseCapture)?;
+        } else {
+            execute!(stdout, DisableMouseCapture)?;
+        }
         
         let result = self.main_loop(&mut stdout);
         
-        execute!(stdout, LeaveAlternateScreen, cursor::Show)?;
+        execute!(stdout, DisableBracketedPaste, DisableMouseCapture, LeaveAlternateScreen, cursor::Show)?;
         disable_raw_mode()?;
         
         result
@@ -207,10 +718,9 @@ impl Portfolio {
         loop {
             // Update terminal size
             let (width, height) = crossterm::terminal::size()?;
-            self.terminal_width = width;
-            self.terminal_height = height;
-            
-            
+            self.resize(width, height);
+            self.tick();
+
             if !self.handle_input()? {
                 break;
             }
@@ -218,13 +728,372 @@ impl Portfolio {
         }
         Ok(())
     }
+
+    // Work that happens between two inputs, about ten times a second
+    fn tick(&mut self) {
+        if let Some(counter) = &mut self.counter {
+            if let Err(e) = counter.tick() {
+                warn!(error = e.to_string(); "visitor counter unavailable");
+                self.counter = None;
+            }
+        }
+    }
+}
+
+fn is_ctrl_c(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL)
 }
 
 fn main() -> crossterm::Result<()> {
-    let mut portfolio = Portfolio::new();
-    portfolio.run()?;
-    
-    println!("\n✨ Thanks for visiting btw I use Arch! ✨\n");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let forced_command = std::env::var_os("SSH_ORIGINAL_COMMAND").is_some();
+    let command = match cli::parse(&args, forced_command) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let config = Config::load()?;
+
+    match command {
+        Command::Portfolio => show_portfolio(&config),
+        Command::Stats { days } => show_stats(&config, days),
+        Command::Guestbook(moderation) => moderate_guestbook(&config, moderation),
+        Command::Inbox(mailbox) => read_inbox(&config, mailbox),
+        Command::Serve { listen } => serve_web(config, listen),
+        Command::Export(export) => export_pages(&config, export),
+        Command::SyncGithub(sync) => sync_github(&config, sync),
+        Command::SyncContributions => sync_contributions(&config),
+        Command::Help => {
+            print!("{}", cli::USAGE);
+            Ok(())
+        }
+    }
+}
+
+fn show_portfolio(config: &Config) -> crossterm::Result<()> {
+    let keymap = Keymap::from_config(&config.keys)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("[keys] {}", e)))?;
+    let session = Session::from_env();
+    logging::init(&config.log, &session.id)?;
+    for e in load_content(config) {
+        warn!(error = e.to_string(); "could not read the content, showing the built-in one");
+    }
+
+    let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+    let mut portfolio = start_session(config, keymap, &session, width, height);
+    let result = portfolio.run();
+    if let Err(e) = &result {
+        error!(error = e.to_string(); "session failed");
+    }
+    end_session(config, &session, portfolio);
+    log::logger().flush();
+    result?;
+
+    println!("\n{}\n", GOODBYE);
     
     Ok(())
 }
+
+// A visit, over SSH or the web terminal, up to the first frame
+fn start_session(config: &Config, keymap: Keymap, session: &Session, width: u16, height: u16) -> Portfolio {
+    let mut portfolio = Portfolio::new(keymap, config.ui.mouse);
+    portfolio.resize(width, height);
+    info!(
+        client = session.client.as_deref().unwrap_or("-"),
+        term = session.term.as_deref().unwrap_or("-"),
+        width = portfolio.terminal_width,
+        height = portfolio.terminal_height;
+        "session started"
+    );
+    info!(page = portfolio.current_page.label(); "page visited");
+    if config.counter.enabled {
+        match Counter::join(&config.counter.file, &session.id) {
+            Ok(counter) => portfolio.counter = Some(counter),
+            Err(e) => warn!(error = e.to_string(); "visitor counter unavailable"),
+        }
+    }
+
+    if config.guestbook.enabled {
+        portfolio.guestbook = open_guestbook(config, session);
+    }
+    if config.contact.enabled {
+        let visitor = visitor_hash(config, session);
+        portfolio.contact = Some(Contact { config: config.contact.clone(), visitor, form: None });
+    }
+    portfolio
+}
+
+fn end_session(config: &Config, session: &Session, portfolio: Portfolio) {
+    if let Some(counter) = &portfolio.counter {
+        if let Err(e) = counter.leave() {
+            warn!(error = e.to_string(); "could not leave the visitor counter");
+        }
+    }
+    let (width, height) = (portfolio.terminal_width, portfolio.terminal_height);
+    let pages = portfolio.tracker.finish();
+    info!(
+        duration_secs = session.started.elapsed().as_secs(),
+        page_views = pages.len();
+        "session ended"
+    );
+    if config.analytics.enabled {
+        if let Err(e) = record_session(config, session, width, height, pages) {
+            warn!(error = e.to_string(); "could not record analytics");
+        }
+    }
+}
+
+fn serve_web(config: Config, listen: Option<String>) -> crossterm::Result<()> {
+    let keymap = Keymap::from_config(&config.keys)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("[keys] {}", e)))?;
+    // Each connection tags its own lines with its session id
+    logging::init(&config.log, "web")?;
+    for e in load_content(&config) {
+        warn!(error = e.to_string(); "could not read the content, showing the built-in one");
+    }
+    let listen = listen.unwrap_or_else(|| config.web.listen.clone());
+    web::serve(config, keymap, &listen)
+}
+
+type Loader = fn(&Config) -> io::Result<()>;
+
+// Everything the pages show beyond the built-in text, in order: the projects
+// may come from the resume
+const CONTENT: [(&str, Loader); 5] = [
+    ("resume", load_resume),
+    ("projects", load_projects),
+    ("posts", load_posts),
+    ("GitHub snapshot", load_github),
+    ("contributions", load_contributions),
+];
+
+// Installs the content, returning what could not be read; the built-in
+// content stands in for it
+fn load_content(config: &Config) -> Vec<io::Error> {
+    CONTENT
+        .into_iter()
+        .filter_map(|(what, load)| load(config).err().map(|e| io::Error::new(e.kind(), format!("{}: {}", what, e))))
+        .collect()
+}
+
+// Generates the About and Projects pages from `[resume] file`, if set
+fn load_resume(config: &Config) -> io::Result<()> {
+    if let Some(path) = &config.resume.file {
+        PageContent::use_resume(resume::load(path)?);
+    }
+    Ok(())
+}
+
+// The Projects catalog from `[projects] dir`, otherwise the resume's projects
+// when it lists any. Call after `load_resume`.
+fn load_projects(config: &Config) -> io::Result<()> {
+    if let Some(dir) = &config.projects.dir {
+        projects::install(projects::load_dir(dir)?);
+    } else if let Some(resume) = PageContent::resume().filter(|resume| !resume.projects.is_empty()) {
+        projects::install(resume.projects.iter().map(projects::from_resume).collect());
+    }
+    Ok(())
+}
+
+// The Blog page's posts from `[blog] dir`
+fn load_posts(config: &Config) -> io::Result<()> {
+    if let Some(dir) = &config.blog.dir {
+        posts::install(posts::load_dir(dir)?);
+    }
+    Ok(())
+}
+
+// The Repos page's repositories, as the last `sync-github` saved them
+fn load_github(config: &Config) -> io::Result<()> {
+    github::install(github::load(&config.github.snapshot)?);
+    Ok(())
+}
+
+// The Home page's calendar from `[contributions] file`, as given or as the
+// last `sync-contributions` saved it
+fn load_contributions(config: &Config) -> io::Result<()> {
+    if let Some(path) = &config.contributions.file {
+        contributions::install(contributions::load(path)?);
+    }
+    Ok(())
+}
+
+fn sync_github(config: &Config, sync: Sync) -> crossterm::Result<()> {
+    let user = sync.user.as_deref().or(config.github.user.as_deref());
+    let body = match (&sync.from, user) {
+        (Some(path), _) => std::fs::read_to_string(path).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?,
+        (None, Some(user)) => github::fetch(&config.github.fetch, user, Duration::from_secs(config.github.fetch_timeout_secs))?,
+        (None, None) => {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "sync-github needs --user, --from or [github] user"));
+        }
+    };
+    let repos = github::parse_api(&body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("GitHub response: {}", e)))?;
+    let snapshot = github::snapshot_from(user, repos, config.github.forks, chrono::Utc::now());
+    let count = snapshot.repos.len();
+    github::save(&config.github.snapshot, snapshot)?;
+    println!("{} repositories saved to {}", count, config.github.snapshot.display());
+    Ok(())
+}
+
+// Runs `git log` over every repository, too slow to do as a session starts
+fn sync_contributions(config: &Config) -> crossterm::Result<()> {
+    let settings = &config.contributions;
+    let Some(path) = &settings.file else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "sync-contributions needs [contributions] file"));
+    };
+    if settings.repos.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "sync-contributions needs [contributions] repos"));
+    }
+    let since = chrono::Utc::now().date_naive() - chrono::Duration::weeks(53);
+    let calendar = contributions::scan_git(&settings.repos, settings.author.as_deref(), since)?;
+    contributions::save(path, &calendar).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+    println!("{} days of commits saved to {}", calendar.days.len(), path.display());
+    Ok(())
+}
+
+fn export_pages(config: &Config, export: Export) -> crossterm::Result<()> {
+    // An export with the wrong pages in it is worse than none
+    if let Some(e) = load_content(config).into_iter().next() {
+        return Err(e);
+    }
+    let mut written = Vec::new();
+    if let Some(dir) = &export.html {
+        written.extend(export::html::write(dir)?);
+    }
+    if let Some(dir) = &export.markdown {
+        written.extend(export::markdown::write(dir)?);
+    }
+    if let Some(path) = &export.json {
+        export::json::write(path)?;
+        written.push(path.clone());
+    }
+    if let Some(path) = &export.resume {
+        export::resume::write(path)?;
+        written.push(path.clone());
+    }
+    // Paths go to stderr when a document went to stdout
+    let to_stdout = written.iter().any(|path| path.as_os_str() == "-");
+    for path in written.iter().filter(|path| path.as_os_str() != "-") {
+        match to_stdout {
+            true => eprintln!("{}", path.display()),
+            false => println!("{}", path.display()),
+        }
+    }
+    Ok(())
+}
+
+fn open_guestbook(config: &Config, session: &Session) -> Option<Guestbook> {
+    let book = match guestbook::load(&config.guestbook) {
+        Ok(book) => book,
+        Err(e) => {
+            warn!(error = e.to_string(); "could not read the guestbook");
+            return None;
+        }
+    };
+    let visitor = visitor_hash(config, session);
+    Some(Guestbook { config: config.guestbook.clone(), visitor, book, page: 0, form: None })
+}
+
+// Who is posting, for the rate limits; never the address itself
+fn visitor_hash(config: &Config, session: &Session) -> Option<String> {
+    let client = session.client.as_deref()?;
+    match analytics::load_or_create_salt(&config.analytics.salt_file) {
+        Ok(salt) => Some(analytics::hash_visitor(&salt, client)),
+        Err(e) => {
+            warn!(error = e.to_string(); "rate limits unavailable");
+            None
+        }
+    }
+}
+
+fn record_session(
+    config: &Config,
+    session: &Session,
+    width: u16,
+    height: u16,
+    pages: Vec<analytics::PageView>,
+) -> io::Result<()> {
+    let visitor = match &session.client {
+        Some(client) => Some(analytics::hash_visitor(&analytics::load_or_create_salt(&config.analytics.salt_file)?, client)),
+        None => None,
+    };
+    let record = SessionRecord {
+        started: session.started_at,
+        duration_secs: session.started.elapsed().as_secs(),
+        visitor,
+        term: session.term.clone(),
+        width,
+        height,
+        pages,
+    };
+    analytics::record(&config.analytics, &record)
+}
+
+fn show_stats(config: &Config, days: i64) -> crossterm::Result<()> {
+    let records = analytics::load(&config.analytics.file)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", config.analytics.file.display(), e)))?;
+    print!("{}", analytics::report(&records, days, chrono::Utc::now()));
+    Ok(())
+}
+
+fn moderate_guestbook(config: &Config, moderation: Moderation) -> crossterm::Result<()> {
+    let path = &config.guestbook.file;
+    let found = match moderation {
+        Moderation::List { pending } => {
+            let book = guestbook::load(&config.guestbook).map_err(|e| files::with_path(path, e))?;
+            for entry in book.entries.iter().filter(|entry| !pending || !entry.approved) {
+                let state = if entry.approved { "" } else { "  [pending]" };
+                println!("#{}  {}  {}{}", entry.id, entry.posted.format("%Y-%m-%d %H:%M"), entry.name, state);
+                println!("    {}", entry.message);
+                if let Some(link) = &entry.link {
+                    println!("    {}", link);
+                }
+            }
+            return Ok(());
+        }
+        Moderation::Approve(id) => guestbook::approve(path, id).map_err(|e| files::with_path(path, e))?,
+        Moderation::Delete(id) => guestbook::delete(path, id).map_err(|e| files::with_path(path, e))?,
+    };
+    if !found {
+        eprintln!("No guestbook entry with that id");
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn read_inbox(config: &Config, mailbox: Mailbox) -> crossterm::Result<()> {
+    let path = &config.contact.spool;
+    let found = match mailbox {
+        Mailbox::List { unread } => {
+            let inbox = contact::load(path).map_err(|e| files::with_path(path, e))?;
+            for message in inbox.messages.iter().rev().filter(|message| !unread || !message.read) {
+                let state = if message.read { "" } else { "  [new]" };
+                println!(
+                    "#{}  {}  {} <{}>  {}{}",
+                    message.id,
+                    message.received.format("%Y-%m-%d %H:%M"),
+                    message.name,
+                    message.email,
+                    message.subject,
+                    state
+                );
+            }
+            return Ok(());
+        }
+        Mailbox::Read(id) => match contact::mark_read(path, id).map_err(|e| files::with_path(path, e))? {
+            Some(message) => {
+                print!("{}", contact::email(config.contact.to.as_deref(), &message));
+                true
+            }
+            None => false,
+        },
+        Mailbox::Delete(id) => contact::delete(path, id).map_err(|e| files::with_path(path, e))?,
+    };
+    if !found {
+        eprintln!("No message with that id");
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,146 @@
+// posts.rs
+
+use chrono::NaiveDate;
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::files;
+
+// The posts shipped with the binary, one Markdown file per post in `posts/`
+const BUILT_IN: [(&str, &str); 2] = [
+    ("2025-06-14-hello-from-the-terminal.md", include_str!("../posts/2025-06-14-hello-from-the-terminal.md")),
+    ("2025-09-02-markdown-in-80-columns.md", include_str!("../posts/2025-09-02-markdown-in-80-columns.md")),
+];
+
+const WORDS_PER_MINUTE: usize = 200;
+
+// The posts every session shows, newest first, see `install`
+static POSTS: OnceLock<Vec<Post>> = OnceLock::new();
+
+// One entry of the Blog page, read from a Markdown file with front matter
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Post {
+    pub title: String,
+    pub date: NaiveDate,
+    pub tags: Vec<String>,
+    // One or two sentences for the index, may be empty
+    pub summary: String,
+    // Markdown, after the front matter
+    pub body: String,
+    // Left out of the Blog page
+    pub draft: bool,
+}
+
+impl Post {
+    // Minutes it takes to read the body, at least one
+    pub fn reading_time(&self) -> usize {
+        self.body.split_whitespace().count().div_ceil(WORDS_PER_MINUTE).max(1)
+    }
+}
+
+// Sets the posts for the whole process. Only the first call counts.
+pub fn install(mut posts: Vec<Post>) {
+    sort(&mut posts);
+    let _ = POSTS.set(posts);
+}
+
+// The installed posts, the built-in ones until `install` is called
+pub fn posts() -> &'static [Post] {
+    POSTS.get_or_init(built_in)
+}
+
+pub fn built_in() -> Vec<Post> {
+    // The files are part of the source tree, a broken one fails the tests
+    let mut posts: Vec<Post> = BUILT_IN
+        .iter()
+        .map(|(name, text)| parse(name, text).unwrap_or_else(|e| panic!("posts/{}: {}", name, e)))
+        .collect();
+    sort(&mut posts);
+    posts
+}
+
+// Newest first, posts of the same day by title
+fn sort(posts: &mut [Post]) {
+    posts.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.title.cmp(&b.title)));
+}
+
+// Every `*.md` file in `dir` but the drafts, newest first
+pub fn load_dir(dir: &Path) -> io::Result<Vec<Post>> {
+    let mut posts = files::load_dir(dir, "md", parse)?;
+    posts.retain(|post| !post.draft);
+    sort(&mut posts);
+    Ok(posts)
+}
+
+// A post file: front matter between `---` lines, then the Markdown. The
+// front matter is `key: value` lines, tags as `[a, b]` or a `- tag` list.
+// Without a date, a Jekyll style `2025-06-14-title.md` file name has one.
+// Keys other than title, date, tags, summary and draft are ignored.
+pub fn parse(file_name: &str, text: &str) -> Result<Post, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut lines = text.lines();
+    if lines.next().map(str::trim_end) != Some("---") {
+        return Err("no front matter, the file should start with a `---` line".to_string());
+    }
+
+    let mut post = Post::default();
+    let mut date = file_name.get(..10).and_then(|prefix| NaiveDate::parse_from_str(prefix, "%Y-%m-%d").ok());
+    let mut list_key: Option<String> = None;
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if line.trim_end() == "---" {
+            closed = true;
+            break;
+        }
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        if let Some(item) = line.trim_start().strip_prefix("- ") {
+            if list_key.as_deref() == Some("tags") {
+                post.tags.push(unquote(item).to_string());
+            }
+            continue;
+        }
+        let (key, value) = line.split_once(':').ok_or_else(|| format!("front matter line `{}` has no `key: value`", line.trim()))?;
+        let (key, value) = (key.trim(), value.trim());
+        list_key = value.is_empty().then(|| key.to_string());
+        match key {
+            "title" => post.title = unquote(value).to_string(),
+            "summary" | "description" => post.summary = unquote(value).to_string(),
+            "tags" => post.tags = inline_list(value),
+            "date" => {
+                let day = unquote(value).get(..10).unwrap_or_default();
+                let parsed = NaiveDate::parse_from_str(day, "%Y-%m-%d").map_err(|_| format!("date `{}` is not YYYY-MM-DD", value))?;
+                date = Some(parsed);
+            }
+            "draft" => post.draft = value == "true",
+            _ => {}
+        }
+    }
+    if !closed {
+        return Err("the front matter has no closing `---` line".to_string());
+    }
+    if post.title.is_empty() {
+        return Err("the front matter has no title".to_string());
+    }
+    post.date = date.ok_or("the front matter has no date")?;
+    post.body = lines.collect::<Vec<_>>().join("\n").trim_matches('\n').to_string();
+    Ok(post)
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value.strip_prefix(quote).and_then(|v| v.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    value
+}
+
+// "[Rust, 'Terminal UI']", or a lone "Rust"
+fn inline_list(value: &str) -> Vec<String> {
+    let inner = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')).unwrap_or(value);
+    inner.split(',').map(unquote).filter(|tag| !tag.is_empty()).map(str::to_string).collect()
+}
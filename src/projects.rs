@@ -0,0 +1,116 @@
+// projects.rs
+
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::files;
+use crate::resume;
+
+// The catalog shipped with the binary, one file per project in `projects/`
+const BUILT_IN: [(&str, &str); 5] = [
+    ("01-koyotec-digital.toml", include_str!("../projects/01-koyotec-digital.toml")),
+    ("02-medaly-engineer.toml", include_str!("../projects/02-medaly-engineer.toml")),
+    ("03-esprit.toml", include_str!("../projects/03-esprit.toml")),
+    ("04-content-creation.toml", include_str!("../projects/04-content-creation.toml")),
+    ("05-upcoming.toml", include_str!("../projects/05-upcoming.toml")),
+];
+
+// The catalog every session shows, see `install`
+static CATALOG: OnceLock<Vec<Project>> = OnceLock::new();
+
+// One entry of the Projects page, read from a TOML file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Project {
+    pub name: String,
+    // One line for the card
+    pub tagline: String,
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<u16>,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<Link>,
+    // Page markup: paragraphs, "##" headings, "- " bullets and fences
+    pub description: String,
+    // Drawn as it is above the description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screenshot: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    #[default]
+    Active,
+    Maintained,
+    Completed,
+    Planned,
+    Archived,
+}
+
+impl Status {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Active => "Active",
+            Status::Maintained => "Maintained",
+            Status::Completed => "Completed",
+            Status::Planned => "Planned",
+            Status::Archived => "Archived",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Link {
+    pub label: String,
+    pub url: String,
+}
+
+// Sets the catalog for the whole process. Only the first call counts.
+pub fn install(projects: Vec<Project>) {
+    let _ = CATALOG.set(projects);
+}
+
+// The installed catalog, the built-in one until `install` is called
+pub fn catalog() -> &'static [Project] {
+    CATALOG.get_or_init(built_in)
+}
+
+pub fn built_in() -> Vec<Project> {
+    // The files are part of the source tree, a broken one fails the tests
+    BUILT_IN.iter().map(|(name, text)| parse(text).unwrap_or_else(|e| panic!("projects/{}: {}", name, e))).collect()
+}
+
+// Every `*.toml` file in `dir`, in file name order
+pub fn load_dir(dir: &Path) -> io::Result<Vec<Project>> {
+    files::load_dir(dir, "toml", |_, text| parse(text))
+}
+
+pub fn parse(text: &str) -> Result<Project, toml::de::Error> {
+    toml::from_str(text)
+}
+
+// A JSON Resume project as a catalog entry: ongoing ones are active
+pub fn from_resume(project: &resume::Project) -> Project {
+    let description = project.description.clone().unwrap_or_default();
+    let mut text = description.clone();
+    if !project.highlights.is_empty() {
+        text.push_str("\n\n");
+        text.push_str(&project.highlights.iter().map(|h| format!("- {}", h)).collect::<Vec<_>>().join("\n"));
+    }
+    Project {
+        name: project.name.clone().unwrap_or_else(|| "Untitled".to_string()),
+        // The first sentence of the description
+        tagline: description.split_inclusive(". ").next().unwrap_or_default().trim().to_string(),
+        tags: project.keywords.clone(),
+        year: project.start_date.as_deref().and_then(|date| date.get(..4)).and_then(|year| year.parse().ok()),
+        status: if project.end_date.is_some() { Status::Completed } else { Status::Active },
+        links: project.url.iter().map(|url| Link { label: "Website".to_string(), url: url.clone() }).collect(),
+        description: text,
+        screenshot: None,
+    }
+}
@@ -0,0 +1,43 @@
+// session.rs
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use std::time::Instant;
+
+// Who is visiting, as far as the environment sshd sets up (or the web
+// terminal's connection) tells
+#[derive(Debug, Clone)]
+pub struct Session {
+    // Short random id tying the log lines of one visit together
+    pub id: String,
+    pub client: Option<String>,
+    pub term: Option<String>,
+    pub started: Instant,
+    pub started_at: DateTime<Utc>,
+}
+
+impl Session {
+    pub fn new(client: Option<String>, term: Option<String>) -> Self {
+        Self {
+            id: format!("{:08x}", rand::thread_rng().gen::<u32>()),
+            client,
+            term,
+            started: Instant::now(),
+            started_at: Utc::now(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let var = |name| std::env::var(name).ok().filter(|v: &String| !v.is_empty());
+        Self::new(client_address(var("SSH_CLIENT").as_deref(), var("SSH_CONNECTION").as_deref()), var("TERM"))
+    }
+}
+
+// SSH_CLIENT is "addr port local_port", SSH_CONNECTION is
+// "addr port server_addr server_port"; the address comes first in both
+pub fn client_address(ssh_client: Option<&str>, ssh_connection: Option<&str>) -> Option<String> {
+    ssh_client
+        .or(ssh_connection)
+        .and_then(|value| value.split_whitespace().next())
+        .map(str::to_string)
+}
@@ -0,0 +1,53 @@
+// store.rs
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::Path;
+
+// Small JSON files shared by every session process. Writers hold an
+// exclusive lock on `<file>.lock` for the whole read-modify-write and replace
+// the file with a rename, so a reader (or a crash) never sees half of it.
+pub fn update<S, T>(path: &Path, change: impl FnOnce(&mut S) -> T) -> io::Result<(T, S)>
+where
+    S: Serialize + DeserializeOwned + Default,
+{
+    let lock = lock_file(path)?;
+    lock.lock()?;
+
+    let mut store = read(path)?;
+    let result = change(&mut store);
+    write(path, &store)?;
+    Ok((result, store))
+}
+
+// Replaces the file whatever it held, e.g. one in a format `read` does not
+// know, under the same lock as `update`
+pub fn replace<S: Serialize>(path: &Path, store: &S) -> io::Result<()> {
+    let lock = lock_file(path)?;
+    lock.lock()?;
+    write(path, store)
+}
+
+fn write<S: Serialize>(path: &Path, store: &S) -> io::Result<()> {
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, serde_json::to_vec(store).map_err(io::Error::other)?)?;
+    fs::rename(&temp, path)
+}
+
+// Readers take no lock, so they only need read access: the rename in
+// `update` shows them either the old file or the new one. A file nobody
+// wrote yet reads as the default.
+pub fn read<S: DeserializeOwned + Default>(path: &Path) -> io::Result<S> {
+    match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(S::default()),
+        Err(e) => Err(e),
+    }
+}
+
+fn lock_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path.with_extension("lock"))
+}
@@ -0,0 +1,80 @@
+// tests/analytics.rs
+
+use chrono::{DateTime, Utc};
+use std::thread;
+
+use crate::analytics::{hash_visitor, load_or_create_salt, report, PageView, SessionRecord};
+use crate::cli::{parse, Command};
+use crate::tests::harness::temp_dir;
+
+fn at(time: &str) -> DateTime<Utc> {
+    time.parse().expect("valid timestamp")
+}
+
+fn session(started: &str, visitor: &str, term: &str, pages: &[(&str, u64)]) -> SessionRecord {
+    SessionRecord {
+        started: at(started),
+        duration_secs: pages.iter().map(|(_, secs)| secs).sum(),
+        visitor: Some(visitor.to_string()),
+        term: Some(term.to_string()),
+        width: 80,
+        height: 24,
+        pages: pages.iter().map(|(page, secs)| PageView { page: page.to_string(), secs: *secs }).collect(),
+    }
+}
+
+#[test]
+fn visitor_hash_depends_on_the_salt() {
+    let a = hash_visitor(b"salt-a", "203.0.113.9");
+    assert_eq!(a, hash_visitor(b"salt-a", "203.0.113.9"));
+    assert_ne!(a, hash_visitor(b"salt-b", "203.0.113.9"));
+    assert!(!a.contains("203"));
+    assert_eq!(a.len(), 16);
+}
+
+#[test]
+fn concurrent_sessions_share_one_salt() {
+    let dir = temp_dir("salt");
+    let path = dir.join("salt");
+    let sessions: Vec<_> = (0..16)
+        .map(|_| {
+            let path = path.clone();
+            thread::spawn(move || load_or_create_salt(&path).unwrap())
+        })
+        .collect();
+    let salts: Vec<Vec<u8>> = sessions.into_iter().map(|s| s.join().unwrap()).collect();
+    assert!(salts.iter().all(|salt| *salt == salts[0] && salt.len() == 64));
+    assert_eq!(std::fs::read_dir(&*dir).unwrap().count(), 1);
+}
+
+#[test]
+fn report_counts_visitors_pages_and_terminals() {
+    let records = vec![
+        session("2026-10-01T10:00:00Z", "aa", "xterm", &[("Home", 30)]),
+        session("2026-10-10T09:00:00Z", "aa", "xterm", &[("Home", 10), ("About", 50)]),
+        session("2026-10-10T18:00:00Z", "aa", "alacritty", &[("Home", 20)]),
+        session("2026-10-11T12:00:00Z", "bb", "xterm", &[("Projects", 40), ("Home", 20)]),
+    ];
+    let stats = report(&records, 7, at("2026-10-12T00:00:00Z"));
+
+    // The first session is older than a week
+    assert_eq!(stats.sessions, 3);
+    assert_eq!(stats.visitors, 2);
+    assert_eq!(stats.average_secs, (60 + 20 + 60) / 3);
+    assert_eq!(stats.daily.len(), 2);
+    let day = stats.daily.values().next().unwrap();
+    assert_eq!((day.visitors, day.sessions), (1, 2));
+    assert_eq!(stats.pages[0].0, "Home");
+    assert_eq!((stats.pages[0].1.views, stats.pages[0].1.secs), (3, 50));
+    assert_eq!(stats.terminals[0], ("xterm".to_string(), 2));
+}
+
+#[test]
+fn owner_commands_are_not_reachable_over_ssh() {
+    let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    assert_eq!(parse(&args(&[]), false), Ok(Command::Portfolio));
+    assert_eq!(parse(&args(&["stats", "--days", "7"]), false), Ok(Command::Stats { days: 7 }));
+    assert_eq!(parse(&args(&["-c", "stats"]), false), Ok(Command::Portfolio));
+    assert_eq!(parse(&args(&["stats"]), true), Ok(Command::Portfolio));
+    assert!(parse(&args(&["stats", "--days", "0"]), false).is_err());
+}
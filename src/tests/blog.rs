@@ -0,0 +1,218 @@
+// tests/blog.rs
+
+use crossterm::style::Color;
+
+use crate::config::Config;
+use crate::posts::{self, Post};
+use crate::tests::harness::{assert_snapshot, temp_dir, TestTerminal};
+use crate::ui::blog::{article, index, summary, tag_index};
+use crate::ui::handler::Page;
+use crate::ui::markdown::{markup, render};
+use crate::ui::markup::{Line, LineKind};
+
+fn post(title: &str, date: &str, tags: &[&str]) -> Post {
+    Post {
+        title: title.to_string(),
+        date: date.parse().unwrap(),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        body: "Some words.".to_string(),
+        ..Post::default()
+    }
+}
+
+#[test]
+fn posts_load_from_markdown_files() {
+    let built_in = posts::built_in();
+    assert_eq!(built_in.len(), 2);
+    assert_eq!(built_in[0].title, "Markdown in 80 columns");
+    assert_eq!(built_in[1].tags, ["SSH", "Terminal UI"]);
+
+    let post = posts::parse(
+        "2024-01-05-first.md",
+        "---\ntitle: \"First: a post\"\ntags:\n  - Rust\n  - 'Web'\ndescription: Short.\nlayout: post\n---\n\nHello.\n",
+    )
+    .unwrap();
+    assert_eq!(post.title, "First: a post");
+    assert_eq!(post.date.to_string(), "2024-01-05");
+    assert_eq!(post.tags, ["Rust", "Web"]);
+    assert_eq!(post.summary, "Short.");
+    assert_eq!(post.body, "Hello.");
+    assert!(posts::parse("notes.md", "---\ntitle: x\n---\n").unwrap_err().contains("no date"));
+    assert!(posts::parse("notes.md", "---\ndate: 2024-13-01\n---\n").unwrap_err().contains("YYYY-MM-DD"));
+    assert!(posts::parse("2024-01-05-x.md", "---\ntags: [a]\n---\n").unwrap_err().contains("no title"));
+    assert!(posts::parse("2024-01-05-x.md", "# Just Markdown\n").is_err());
+    assert!(posts::parse("2024-01-05-x.md", "---\ntitle: x\n").is_err());
+
+    let dir = temp_dir("posts");
+    std::fs::write(dir.join("2024-01-05-old.md"), "---\ntitle: Old\n---\n").unwrap();
+    std::fs::write(dir.join("2024-03-01-new.md"), "---\ntitle: New\n---\n").unwrap();
+    std::fs::write(dir.join("2024-04-01-wip.md"), "---\ntitle: Wip\ndraft: true\n---\n").unwrap();
+    std::fs::write(dir.join("notes.txt"), "not a post").unwrap();
+    let titles: Vec<String> = posts::load_dir(&dir).unwrap().into_iter().map(|p| p.title).collect();
+    assert_eq!(titles, ["New", "Old"]);
+    std::fs::write(dir.join("broken.md"), "---\ntitle: Broken\n---\n").unwrap();
+    assert!(posts::load_dir(&dir).unwrap_err().to_string().contains("broken.md"));
+
+    let config = Config::parse("[blog]\ndir = \"/etc/ssh-portfolio/posts\"\n").unwrap();
+    assert_eq!(config.blog.dir.as_deref(), Some(std::path::Path::new("/etc/ssh-portfolio/posts")));
+}
+
+#[test]
+fn reading_time_rounds_up_to_whole_minutes() {
+    let mut post = post("x", "2024-01-01", &[]);
+    post.body = String::new();
+    assert_eq!(post.reading_time(), 1);
+    post.body = "word ".repeat(200);
+    assert_eq!(post.reading_time(), 1);
+    post.body = "word ".repeat(201);
+    assert_eq!(post.reading_time(), 2);
+}
+
+#[test]
+fn markdown_renders_to_page_markup() {
+    let lines = render(
+        "# Title\n\nSome *soft*\nwrapped **text**.\n\n### Small\n\n- one\n  - nested\n\n3. three\n\n> quoted\n\n```\nlet x = 1;\n```\n\n| a | b |\n|---|---|\n| 1 | 2 |\n\nSee [docs](https://x.dev).",
+        40,
+    );
+    assert_eq!(
+        markup(&lines),
+        "## Title\n\nSome soft wrapped text.\n\n### Small\n\n- one\n  ◦ nested\n\n3. three\n\n│ quoted\n\n```\n  let x = 1;\n```\n\n\
+         \x20 a │ b\n  ──┼──\n  1 │ 2\n\nSee docs (https://x.dev).\n"
+    );
+    let spans = &lines[2].spans;
+    let soft = spans.iter().find(|span| span.text == "soft").unwrap();
+    assert!(soft.style.italic);
+    let text = spans.iter().find(|span| span.text == "text").unwrap();
+    assert!(text.style.bold);
+    let link = lines.last().unwrap().spans.iter().find(|span| span.text == "docs").unwrap();
+    assert_eq!(link.style.fg, Some(Color::Blue));
+    assert!(link.style.underline);
+}
+
+#[test]
+fn an_unclosed_fence_runs_to_the_end() {
+    let lines = render("Before.\n\n```rust\nfn main() {}\n\n# not a heading", 40);
+    assert_eq!(markup(&lines), "Before.\n\n```rust\n  fn main() {}\n  \n  # not a heading\n```\n");
+}
+
+#[test]
+fn nested_emphasis_keeps_both_styles() {
+    let lines = render("***both*** and *a **b** c*", 40);
+    assert_eq!(markup(&lines), "both and a b c\n");
+    let style = |text: &str| lines[0].spans.iter().find(|span| span.text.contains(text)).unwrap().style;
+    assert!(style("both").bold && style("both").italic);
+    assert!(style("a ").italic && !style("a ").bold);
+    assert!(style(" c").italic && !style(" c").bold);
+    let b = lines[0].spans.iter().find(|span| span.text == "b").unwrap().style;
+    assert!(b.bold && b.italic);
+}
+
+#[test]
+fn link_text_may_hold_brackets() {
+    let lines = render("[a \\] b](https://x.dev) and [[1]](https://y.dev) and [x](https://x)", 80);
+    assert_eq!(markup(&lines), "a ] b (https://x.dev) and [1] (https://y.dev) and x (https://x)\n");
+    assert_eq!(markup(&render("<https://x.dev> ![logo](https://x.dev/logo.png)", 80)), "https://x.dev [image: logo] https://x.dev/logo.png\n");
+}
+
+#[test]
+fn wide_characters_line_up_in_tables() {
+    let lines = render("| 名前 | note |\n|---|---|\n| 東京 | 2 |\n| x | wide 字 |", 40);
+    assert_eq!(markup(&lines), "  名前 │ note\n  ─────┼────────\n  東京 │ 2\n  x    │ wide 字\n");
+}
+
+#[test]
+fn paragraphs_and_lists_wrap_at_the_width() {
+    let text = "Lorem ipsum dolor sit amet, 日本語の文章も折り返されます consectetur adipiscing elit, sed do eiusmod.";
+    let markdown = format!("{}\n\n- {}\n\n> {}\n\n{}", text, text, text, "x".repeat(50));
+    let lines = render(&markdown, 40);
+    for line in &lines {
+        let width: usize = line.spans.iter().map(|span| span.width()).sum();
+        assert!(width <= 40, "{:?} is {} wide", line.markup, width);
+    }
+    assert!(lines.iter().any(|line| line.markup.starts_with("- Lorem")));
+    assert!(lines.iter().any(|line| line.markup.starts_with("  ") && line.markup.contains("consectetur")));
+}
+
+#[test]
+fn html_is_skipped_and_tasks_are_ticked() {
+    let lines = render("<!-- draft note -->\n\n- [x] done\n- [ ] todo\n\n<div>\nraw\n</div>\n\nEnd.", 40);
+    assert_eq!(markup(&lines), "- ☑ done\n- ☐ todo\n\nEnd.\n");
+}
+
+#[test]
+fn the_index_lists_posts_with_a_tag_index() {
+    let posts = [post("Newer", "2024-03-01", &["Rust"]), post("Older", "2024-01-05", &["rust", "Web"])];
+    assert_eq!(tag_index(&posts), [("Rust".to_string(), vec![0, 1]), ("Web".to_string(), vec![1])]);
+    assert_eq!(summary(&posts[0]), "Some words.");
+
+    let listing = index("\n# Blog\n", &posts, Some("hint"));
+    assert_eq!(
+        listing.text,
+        "\n# Blog\n\nhint\n\n## Newer\n2024-03-01 · 1 min read\n[Rust]\nSome words.\n\n## Older\n2024-01-05 · 1 min read\n[rust] [Web]\nSome words.\n\n\
+         ## Tags\n\n### Rust\n- 2024-03-01  Newer\n- 2024-01-05  Older\n\n### Web\n- 2024-01-05  Older\n"
+    );
+    assert_eq!(listing.entries, [(5, 8), (10, 13)]);
+    assert_eq!(listing.post_at(7), Some(0));
+    assert_eq!(listing.post_at(9), None);
+    assert_eq!(listing.post_at(18), Some(0));
+    assert_eq!(listing.post_at(19), Some(1));
+    assert!(index("\n# Blog\n", &[], None).text.contains("Nothing written yet"));
+}
+
+#[test]
+fn articles_link_to_the_newer_and_older_post() {
+    let posts = [post("One", "2024-03-01", &[]), post("Two", "2024-02-01", &[]), post("Three", "2024-01-01", &[])];
+    let middle = article(&posts, 1, Some("hint"));
+    assert!(middle.text.starts_with("\n# Two\n\n2024-02-01 · 1 min read\n\nSome words.\n"));
+    assert!(middle.text.ends_with("\n## More posts\n\nNewer  One\nOlder  Three\n\nhint\n"));
+    assert_eq!(middle.links.iter().map(|(_, post)| *post).collect::<Vec<_>>(), [0, 2]);
+    assert_eq!(article(&posts, 0, Some("hint")).links.len(), 1);
+    assert!(!article(&posts, 0, None).text.contains("More posts"));
+}
+
+#[test]
+fn enter_opens_a_post_and_parens_page_through_them() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("7 down");
+    assert_eq!(term.portfolio.current_page, Page::Blog);
+    assert_eq!(term.portfolio.reading.selected, 1);
+    term.keys("enter");
+    assert_eq!(term.portfolio.reading.open, Some(1));
+    assert_eq!(term.portfolio.breadcrumb(), ["Blog", "Hello from the terminal"]);
+    assert!(term.portfolio.page_content().starts_with("\n# Hello from the terminal\n"));
+
+    term.keys("(");
+    assert_eq!(term.portfolio.reading.open, Some(0));
+    term.keys("(");
+    assert_eq!(term.portfolio.reading.open, Some(0));
+    term.keys(")");
+    assert_eq!(term.portfolio.reading.open, Some(1));
+
+    term.keys("esc");
+    assert!(term.running);
+    assert_eq!(term.portfolio.reading.open, None);
+    assert_eq!(term.portfolio.breadcrumb(), ["Blog"]);
+
+    // The finder opens the post a hit belongs to
+    term.keys("1 ctrl-p").type_text("friendlier handshake").keys("enter");
+    assert_eq!(term.portfolio.current_page, Page::Blog);
+    assert_eq!(term.portfolio.reading.open, Some(1));
+}
+
+#[test]
+fn post_snapshot() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("7 enter");
+    assert_snapshot("blog_post_80x24", &term.render());
+}
+
+#[test]
+fn wrapped_lines_that_look_like_markup_stay_text() {
+    let lines = render("It was cold - 5 degrees # 12 rooms", 11);
+    assert_eq!(markup(&lines), "It was cold\n\\- 5 degrees\n\\# 12 rooms\n");
+    for line in &lines {
+        let text: String = line.spans.iter().map(|span| span.text.as_str()).collect();
+        let parsed = Line::parse(&line.markup);
+        assert_eq!((parsed.kind, parsed.text), (LineKind::Text, text.as_str()));
+    }
+}
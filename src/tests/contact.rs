@@ -0,0 +1,112 @@
+// tests/contact.rs
+
+use chrono::{TimeZone, Utc};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::ContactConfig;
+use crate::contact::{self, Draft, Message};
+use crate::tests::harness::{assert_snapshot, temp_config, TestTerminal};
+use crate::ui::contact::Contact;
+
+fn draft(name: &str, email: &str, subject: &str, body: &str) -> Draft {
+    Draft { name: name.to_string(), email: email.to_string(), subject: subject.to_string(), body: body.to_string() }
+}
+
+fn config_in(dir: &Path) -> ContactConfig {
+    ContactConfig { enabled: true, spool: dir.join("inbox.json"), ..ContactConfig::default() }
+}
+
+#[test]
+fn fields_are_validated() {
+    assert!(contact::is_email("ada@example.com"));
+    assert!(contact::is_email("a.b+c@mail.example.org"));
+    assert!(!contact::is_email("ada"));
+    assert!(!contact::is_email("@example.com"));
+    assert!(!contact::is_email("ada@localhost"));
+    assert!(!contact::is_email("ada@example..com"));
+    assert!(!contact::is_email("ada@a@example.com"));
+
+    assert!(contact::validate(&draft("", "ada@example.com", "Hi", "Hello")).is_err());
+    assert!(contact::validate(&draft("Ada", "ada", "Hi", "Hello")).is_err());
+    assert!(contact::validate(&draft("Ada", "ada@example.com", " ", "Hello")).is_err());
+    assert!(contact::validate(&draft("Ada", "ada@example.com", "Hi", "\n \n")).is_err());
+
+    let clean = contact::validate(&draft("Ada\r\nBcc: x@y.z", "ada@example.com", "Hi\x1b[2J", "one\n\n\n\ttwo  \n\n")).unwrap();
+    assert_eq!(clean.name, "Ada Bcc: x@y.z");
+    assert_eq!(clean.subject, "Hi[2J");
+    assert_eq!(clean.body, "one\n\n two");
+}
+
+#[test]
+fn messages_are_spooled_and_piped_to_sendmail() {
+    let (dir, mut config) = temp_config("contact-spool", config_in);
+    let mail = dir.join("mail.txt");
+    config.sendmail = Some(format!("tee {}", mail.display()));
+    config.to = Some("me@example.com".to_string());
+
+    contact::send(&config, &draft("Ada", "ada@example.com", "Hello", "Nice site!"), Some("abc")).unwrap();
+    assert!(contact::send(&config, &draft("Ada", "ada@example.com", "Again", "Hi"), Some("abc")).is_err());
+
+    let inbox = contact::load(&config.spool).unwrap();
+    assert_eq!(inbox.messages.len(), 1);
+    assert!(!inbox.messages[0].read);
+    // sendmail finishes in the background
+    let piped = (0..100)
+        .map(|_| {
+            thread::sleep(Duration::from_millis(50));
+            std::fs::read_to_string(&mail).unwrap_or_default()
+        })
+        .find(|piped| piped.ends_with("\n\nNice site!\n"))
+        .expect("sendmail received the message");
+    assert!(piped.starts_with("To: me@example.com\nReply-To: \"Ada\" <ada@example.com>\nSubject: [portfolio] Hello\n"));
+    assert!(piped.ends_with("\n\nNice site!\n"));
+
+    assert_eq!(contact::mark_read(&config.spool, 1).unwrap().map(|m| m.read), Some(true));
+    assert!(contact::mark_read(&config.spool, 2).unwrap().is_none());
+    assert!(contact::delete(&config.spool, 1).unwrap());
+    assert!(contact::load(&config.spool).unwrap().messages.is_empty());
+}
+
+#[test]
+fn a_hung_sendmail_does_not_block_the_session() {
+    let (_dir, mut config) = temp_config("contact-hung", config_in);
+    config.sendmail = Some("sleep 5".to_string());
+    let started = Instant::now();
+    contact::send(&config, &draft("Ada", "ada@example.com", "Hello", "Nice site!"), Some("abc")).unwrap();
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert_eq!(contact::load(&config.spool).unwrap().messages.len(), 1);
+}
+
+#[test]
+fn headers_outside_ascii_are_encoded() {
+    let message = Message {
+        id: 1,
+        name: "Zoë".to_string(),
+        email: "zoe@example.com".to_string(),
+        subject: "Café".to_string(),
+        body: "Bonjour".to_string(),
+        received: Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap(),
+        visitor: None,
+        read: false,
+    };
+    let email = contact::email(None, &message);
+    assert!(email.contains("Reply-To: =?utf-8?b?Wm/Dqw==?= <zoe@example.com>\n"));
+    assert!(email.contains("Subject: =?utf-8?b?W3BvcnRmb2xpb10gQ2Fmw6k=?=\n"));
+}
+
+#[test]
+fn contact_form() {
+    let mut term = TestTerminal::new(80, 24);
+    term.portfolio.contact = Some(Contact { config: ContactConfig::default(), visitor: None, form: None });
+    term.keys("m").type_text("Ada").keys("enter").type_text("ada@example").keys("tab");
+    term.type_text("Hi!").keys("enter").type_text("Loved the portfolio, are you open to freelance work?");
+    assert_snapshot("contact_form_80x24", &term.render());
+
+    term.keys("ctrl-s");
+    assert_eq!(
+        term.portfolio.contact.as_ref().and_then(|c| c.form.as_ref()).and_then(|f| f.error.as_deref()),
+        Some("Please enter an email address I can reply to")
+    );
+}
@@ -0,0 +1,124 @@
+// tests/contributions.rs
+
+use chrono::NaiveDate;
+use std::process::Command as Process;
+
+use crate::cli::{parse, Command};
+use crate::config::Config;
+use crate::contributions::{self, Calendar};
+use crate::tests::harness::temp_dir;
+use crate::ui::heatmap::{append, layout, Layout};
+
+fn day(text: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn contributions_come_from_github_dumps_and_git_logs() {
+    let graphql = r##"{"data": {"user": {"contributionsCollection": {"contributionCalendar": {
+        "totalContributions": 5,
+        "weeks": [{"contributionDays": [{"date": "2025-03-02", "contributionCount": 2, "color": "#40c463"}]},
+                  {"contributionDays": [{"date": "2025-03-09", "contributionCount": 3}]}]}}}}}"##;
+    let calendar = contributions::parse_github(graphql).unwrap();
+    assert_eq!(calendar.count(day("2025-03-02")), 2);
+    assert_eq!(calendar.count(day("2025-03-09")), 3);
+    assert_eq!(calendar.count(day("2025-03-10")), 0);
+
+    let flat = r#"{"total": {"2025": 4}, "contributions": [{"date": "2025-01-01", "count": 4, "level": 2}]}"#;
+    assert_eq!(contributions::parse_github(flat).unwrap().days.into_iter().collect::<Vec<_>>(), [(day("2025-01-01"), 4)]);
+    assert!(contributions::parse_github("[{").is_err());
+
+    // Counts past u32 stop at its maximum instead of wrapping
+    let huge = r#"[{"date": "2025-01-01", "count": 5000000000}, {"date": "2025-01-01", "count": 1}]"#;
+    assert_eq!(contributions::parse_github(huge).unwrap().count(day("2025-01-01")), u32::MAX);
+
+    let log = contributions::parse_git_log("2025-05-01\n2025-05-01\nnot a date\n2025-05-03\n");
+    assert_eq!(log.days.into_iter().collect::<Vec<_>>(), [(day("2025-05-01"), 2), (day("2025-05-03"), 1)]);
+
+    let config = Config::parse("[contributions]\nrepos = [\"/src/a\", \"/src/b\"]\nauthor = \"me@example.com\"\n").unwrap();
+    assert_eq!(config.contributions.repos.len(), 2);
+    assert_eq!(config.contributions.author.as_deref(), Some("me@example.com"));
+    assert!(config.contributions.file.is_none());
+}
+
+#[test]
+fn local_repositories_are_scanned_with_git_log() {
+    let repo = temp_dir("contributions");
+    let git = |args: &[&str], email: &str, date: &str| {
+        let status = Process::new("git")
+            .arg("-C")
+            .arg(&*repo)
+            .args(["-c", "user.name=Ada", "-c", &format!("user.email={}", email)])
+            .args(args)
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    };
+    git(&["init", "-q"], "ada@example.com", "2025-05-01T12:00:00");
+    git(&["commit", "-q", "--allow-empty", "-m", "old"], "ada@example.com", "2024-01-01T12:00:00");
+    git(&["commit", "-q", "--allow-empty", "-m", "one"], "ada@example.com", "2025-05-01T12:00:00");
+    git(&["commit", "-q", "--allow-empty", "-m", "two"], "ada@example.com", "2025-05-01T13:00:00");
+    git(&["commit", "-q", "--allow-empty", "-m", "three"], "bob@example.com", "2025-05-02T12:00:00");
+
+    let repos = [repo.to_path_buf()];
+    let everyone = contributions::scan_git(&repos, None, day("2025-01-01")).unwrap();
+    assert_eq!(everyone.days.into_iter().collect::<Vec<_>>(), [(day("2025-05-01"), 2), (day("2025-05-02"), 1)]);
+    let ada = contributions::scan_git(&repos, Some("ada@example.com"), day("2025-01-01")).unwrap();
+    assert_eq!(ada.days.into_iter().collect::<Vec<_>>(), [(day("2025-05-01"), 2)]);
+    drop(repo);
+
+    assert!(contributions::scan_git(&repos, None, day("2025-01-01")).is_err());
+}
+
+#[test]
+fn sync_contributions_saves_what_sessions_load() {
+    let dir = temp_dir("contributions-sync");
+    let path = dir.join("contributions.json");
+    let calendar = contributions::parse_git_log("2025-05-01\n2025-05-01\n2025-05-03\n");
+    // Whatever was there before is replaced, even a file that does not parse
+    std::fs::write(&path, "[{").unwrap();
+    contributions::save(&path, &calendar).unwrap();
+    assert_eq!(contributions::load(&path).unwrap(), calendar);
+
+    assert_eq!(parse(&["sync-contributions".to_string()], false), Ok(Command::SyncContributions));
+    assert!(parse(&["sync-contributions".to_string(), "--all".to_string()], false).is_err());
+}
+
+#[test]
+fn the_calendar_shrinks_to_a_sparkline() {
+    assert_eq!(layout(120), Layout::Calendar { cell: 2 });
+    assert_eq!(layout(77), Layout::Calendar { cell: 1 });
+    assert_eq!(layout(59), Layout::Calendar { cell: 1 });
+    assert_eq!(layout(58), Layout::Sparkline { weeks: 53 });
+    assert_eq!(layout(36), Layout::Sparkline { weeks: 34 });
+
+    // Wednesday: the weeks start on Sundays, the rest of this one is empty
+    let today = day("2025-10-15");
+    let mut calendar = Calendar::default();
+    calendar.add(day("2024-10-13"), 1);
+    calendar.add(day("2025-08-10"), 2);
+    calendar.add(day("2025-10-14"), 8);
+    calendar.add(day("2025-10-16"), 50);
+
+    let mut page = String::new();
+    assert_eq!(append(&mut page, &calendar, today, 12), 5);
+    assert_eq!(page, "\n## Contributions\n\n10 contributions in the last 10 weeks\n\n  ▃▁▁▁▁▁▁▁▁█\n  Oct 15\n");
+
+    let mut page = String::from("# Home\n");
+    let first_line = append(&mut page, &calendar, today, 77);
+    let lines: Vec<&str> = page.lines().skip(first_line).collect();
+    assert_eq!(page.lines().nth(first_line - 2), Some("11 contributions in the last year"));
+    assert_eq!(lines.len(), 10);
+    // November starts too close to October to be named
+    assert_eq!(lines[0], "      Oct    Dec  Jan Feb Mar  Apr May Jun  Jul Aug  Sep");
+    assert_eq!(lines[1].chars().count(), 2 + 4 + 53);
+    assert!(lines[1].starts_with("      ░····"));
+    assert!(lines[2].starts_with("  Mon ·"));
+    assert!(lines[3].ends_with("·█"));
+    // Thursday to Saturday of this week are still to come
+    assert_eq!(lines[5].chars().count(), 2 + 4 + 52);
+    assert_eq!(lines[9], "  Less ·░▒▓█ More");
+}
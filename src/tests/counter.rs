@@ -0,0 +1,43 @@
+// tests/counter.rs
+
+use chrono::{Duration, Utc};
+use std::thread;
+
+use crate::counter::{thousands, time_ago, Counter};
+use crate::tests::harness::temp_dir;
+
+#[test]
+fn concurrent_sessions_are_all_counted() {
+    let dir = temp_dir("counter");
+    let path = dir.join("visitors.json");
+
+    let sessions: Vec<_> = (0..16)
+        .map(|i| {
+            let path = path.clone();
+            thread::spawn(move || Counter::join(&path, &format!("session-{}", i)).unwrap())
+        })
+        .collect();
+    let counters: Vec<Counter> = sessions.into_iter().map(|s| s.join().unwrap()).collect();
+
+    let last = Counter::join(&path, "last").unwrap();
+    assert_eq!(last.stats.total, 17);
+    assert_eq!(last.stats.online, 17);
+    assert!(last.stats.last_visit.is_some());
+
+    for counter in &counters {
+        counter.leave().unwrap();
+    }
+    let after = Counter::join(&path, "after").unwrap();
+    assert_eq!((after.stats.total, after.stats.online), (18, 2));
+}
+
+#[test]
+fn formats_for_the_footer() {
+    let now = Utc::now();
+    assert_eq!(time_ago(now - Duration::seconds(20), now), "just now");
+    assert_eq!(time_ago(now - Duration::minutes(5), now), "5m ago");
+    assert_eq!(time_ago(now - Duration::hours(3), now), "3h ago");
+    assert_eq!(time_ago(now - Duration::days(2), now), "2d ago");
+    assert_eq!(thousands(999), "999");
+    assert_eq!(thousands(1234567), "1,234,567");
+}
@@ -0,0 +1,134 @@
+// tests/export.rs
+
+use crossterm::style::Color;
+use std::path::PathBuf;
+
+use crate::cli::{parse, Command, Export};
+use crate::export::json::{sections, Block};
+use crate::export::{html, markdown, resume};
+use crate::tests::harness::temp_dir;
+use crate::ui::handler::Page;
+
+#[test]
+fn export_needs_a_format() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        parse(&args(&["export", "--html", "out"]), false),
+        Ok(Command::Export(Export { html: Some(PathBuf::from("out")), ..Default::default() }))
+    );
+    assert_eq!(
+        parse(&args(&["export", "--markdown", "md", "--json", "-", "--resume", "resume.json"]), false),
+        Ok(Command::Export(Export {
+            html: None,
+            markdown: Some(PathBuf::from("md")),
+            json: Some(PathBuf::from("-")),
+            resume: Some(PathBuf::from("resume.json")),
+        }))
+    );
+    assert!(parse(&args(&["export"]), false).is_err());
+    assert!(parse(&args(&["export", "--html"]), false).is_err());
+    assert_eq!(parse(&args(&["export", "--html", "out"]), true), Ok(Command::Portfolio));
+}
+
+#[test]
+fn terminal_colors_map_to_css() {
+    assert_eq!(html::css_color(Color::Reset), None);
+    assert_eq!(html::css_color(Color::Cyan).as_deref(), Some("#00ffff"));
+    assert_eq!(html::css_color(Color::AnsiValue(9)).as_deref(), Some("#ff0000"));
+    assert_eq!(html::css_color(Color::AnsiValue(196)).as_deref(), Some("#ff0000"));
+    assert_eq!(html::css_color(Color::AnsiValue(235)).as_deref(), Some("#262626"));
+    assert_eq!(html::css_color(Color::Rgb { r: 1, g: 2, b: 255 }).as_deref(), Some("#0102ff"));
+}
+
+#[test]
+fn pages_link_to_each_other_and_out() {
+    let html = html::page_html(Page::FAQ);
+    for page in Page::ALL {
+        assert!(html.contains(&format!("<a href=\"{}\">", html::file_name(page))));
+    }
+    assert!(html.contains("<a href=\"https://github.com/mohamedalidridii\">"));
+    assert!(html.contains("<span style=\"color:#00ffff;font-weight:bold\"> ● CONTACT </span>"));
+    assert!(!html.contains("\x1b"));
+}
+
+#[test]
+fn the_site_has_a_file_per_page() {
+    let dir = temp_dir("export");
+    let written = html::write(&dir).unwrap();
+    let names: Vec<_> = written.iter().map(|path| path.file_name().unwrap().to_str().unwrap()).collect();
+    assert_eq!(names, ["index.html", "projects.html", "about.html", "contact.html", "guestbook.html", "repos.html", "blog.html"]);
+    let guestbook = std::fs::read_to_string(dir.join("guestbook.html")).unwrap();
+    assert!(guestbook.contains("Sign it over SSH or in the web terminal."));
+}
+
+#[test]
+fn markdown_keeps_the_markup_and_fences_banners() {
+    let md = markdown::page_markdown(Page::FAQ);
+    assert!(md.starts_with("[Home](index.md) · [Projects](projects.md) · [About](about.md) · **Contact** ·"));
+    assert!(md.contains("\n# Get In Touch\n"));
+    // The extra space after "##" is dropped
+    assert!(md.contains("\n## Social Media & Links\n"));
+    assert!(md.contains("\n### GitHub\n\n<https://github.com/mohamedalidridii>\n"));
+    assert!(md.contains("```text\n  ╔═══"));
+    assert!(md.contains("═╝\n```\n"));
+    assert!(!md.contains("\n\n\n"));
+    assert!(markdown::page_markdown(Page::Guestbook).ends_with("Sign it over SSH or in the web terminal.\n"));
+}
+
+#[test]
+fn markup_splits_into_sections() {
+    let content = "intro\n# Title\n\n  ┌──┐\n  └──┘\n## Links\nsee https://a.example\nand more\n\n- one\n- two\n\n- three\n### Code\n```rust\nfn main() {}\n```\n";
+    let sections = sections(content);
+    let headings: Vec<_> = sections.iter().map(|s| (s.level, s.heading.as_str())).collect();
+    assert_eq!(headings, [(0, ""), (1, "Title"), (2, "Links"), (3, "Code")]);
+    assert_eq!(sections[0].blocks, [Block::Paragraph { text: "intro".into() }]);
+    assert_eq!(sections[1].blocks, [Block::Art { text: "  ┌──┐\n  └──┘".into() }]);
+    assert_eq!(
+        sections[2].blocks,
+        [
+            Block::Paragraph { text: "see https://a.example and more".into() },
+            Block::List { items: vec!["one".into(), "two".into()] },
+            Block::List { items: vec!["three".into()] },
+        ]
+    );
+    assert_eq!(sections[2].links, ["https://a.example"]);
+    assert_eq!(sections[3].blocks, [Block::Code { language: Some("rust".into()), text: "fn main() {}".into() }]);
+}
+
+#[test]
+fn resume_dates_are_iso_8601() {
+    assert_eq!(resume::dates("2024 - 2027"), Some(("2024".into(), Some("2027".into()))));
+    assert_eq!(resume::dates("2024-09 - Present"), Some(("2024-09".into(), None)));
+    assert_eq!(resume::dates("2019"), Some(("2019".into(), None)));
+    assert_eq!(resume::dates("Ongoing"), None);
+    assert_eq!(resume::dates("2024 - someday"), None);
+    assert_eq!(resume::dates("24 - 27"), None);
+}
+
+#[test]
+fn resume_comes_from_about_and_contact() {
+    let resume = resume::resume();
+    assert_eq!(resume.basics.name.as_deref(), Some("Med Ali Dridi"));
+    assert!(resume.basics.summary.as_deref().unwrap().starts_with("Hi! I'm Med Ali Dridi, and my path into tech"));
+    let networks: Vec<_> = resume.basics.profiles.iter().map(|p| (p.network.as_deref(), p.username.as_deref())).collect();
+    assert_eq!(networks[1], (Some("GitHub"), Some("mohamedalidridii")));
+
+    let founder = &resume.work[0];
+    assert_eq!(founder.name.as_deref(), Some("KOYOTEC DIGITAL"));
+    assert_eq!(founder.position.as_deref(), Some("Founder & CEO"));
+    assert_eq!((founder.start_date.as_deref(), founder.end_date.as_deref()), (Some("2024"), None));
+    assert_eq!(founder.location.as_deref(), Some("Tunisia"));
+    assert_eq!(founder.highlights.len(), 5);
+
+    let esprit = &resume.education[0];
+    assert_eq!(esprit.institution.as_deref(), Some("ESPRIT University"));
+    assert_eq!(esprit.end_date.as_deref(), Some("2027"));
+
+    let devops = resume.skills.iter().find(|s| s.name.as_deref() == Some("DevOps & Infrastructure")).unwrap();
+    assert_eq!(devops.keywords[..2], ["Linux (Arch user!)", "Docker"]);
+
+    let json = serde_json::to_value(&resume).unwrap();
+    assert_eq!(json["$schema"], "https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json");
+    assert_eq!(json["work"][0]["startDate"], "2024");
+    assert!(json["work"][0].get("endDate").is_none());
+}
@@ -0,0 +1,120 @@
+// tests/github.rs
+
+use chrono::{TimeZone, Utc};
+use crossterm::style::Color;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::cli::{parse, Command, Sync};
+use crate::config::Config;
+use crate::github::{self, Repo, Snapshot};
+use crate::tests::harness::{temp_dir, TestTerminal};
+use crate::ui::handler::Page;
+use crate::ui::repos::{language_color, list, sorted, RepoSort};
+
+// Trimmed from a real `GET /users/{user}/repos` response
+const API_RESPONSE: &str = r#"[
+  {"id": 1, "name": "ssh-portfolio", "full_name": "medaly/ssh-portfolio", "fork": false,
+   "html_url": "https://github.com/medaly/ssh-portfolio", "description": "A portfolio you visit with ssh",
+   "language": "Rust", "stargazers_count": 42, "forks_count": 3, "topics": ["ssh", "tui"], "archived": false,
+   "pushed_at": "2025-06-01T10:00:00Z", "updated_at": "2025-06-02T10:00:00Z", "owner": {"login": "medaly"}},
+  {"id": 2, "name": "dotfiles", "fork": false, "html_url": "https://github.com/medaly/dotfiles",
+   "description": null, "language": "Shell", "stargazers_count": 7, "forks_count": 0,
+   "pushed_at": "2025-09-12T08:30:00Z", "archived": true},
+  {"id": 3, "name": "awesome-rust", "fork": true, "html_url": "https://github.com/medaly/awesome-rust",
+   "language": null, "stargazers_count": 900, "forks_count": 1}
+]"#;
+
+fn snapshot() -> Snapshot {
+    let repos = github::parse_api(API_RESPONSE).unwrap();
+    github::snapshot_from(None, repos, false, Utc.with_ymd_and_hms(2025, 10, 1, 0, 0, 0).unwrap())
+}
+
+#[test]
+fn sync_github_takes_a_user_or_a_saved_response() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+    assert_eq!(parse(&args(&["sync-github"]), false), Ok(Command::SyncGithub(Sync::default())));
+    assert_eq!(
+        parse(&args(&["sync-github", "--user", "medaly", "--from", "repos.json"]), false),
+        Ok(Command::SyncGithub(Sync { from: Some(PathBuf::from("repos.json")), user: Some("medaly".to_string()) }))
+    );
+    assert!(parse(&args(&["sync-github", "--user"]), false).is_err());
+    assert_eq!(parse(&args(&["sync-github"]), true), Ok(Command::Portfolio));
+
+    let config = Config::parse("[github]\nuser = \"medaly\"\nsnapshot = \"/tmp/github.json\"\nforks = true\n").unwrap();
+    assert_eq!(config.github.user.as_deref(), Some("medaly"));
+    assert_eq!(config.github.fetch, "curl -fsSL");
+    assert!(config.github.forks);
+
+    // The fetch command gets the API URL as its last argument
+    let second = Duration::from_secs(1);
+    let printed = github::fetch("echo", "medaly", second).unwrap();
+    assert_eq!(printed.trim(), "https://api.github.com/users/medaly/repos?per_page=100&type=owner&sort=pushed");
+    assert!(github::fetch("false", "medaly", second).is_err());
+
+    // One that never finishes is killed
+    let error = github::fetch("tail -f /dev/null", "medaly", Duration::from_millis(200)).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::TimedOut);
+}
+
+#[test]
+fn snapshots_keep_what_the_page_needs() {
+    let snapshot = snapshot();
+    let names: Vec<&str> = snapshot.repos.iter().map(|repo| repo.name.as_str()).collect();
+    assert_eq!(names, ["ssh-portfolio", "dotfiles"]);
+    assert_eq!(snapshot.profile().as_deref(), Some("https://github.com/medaly"));
+    assert_eq!(snapshot.repos[0].updated(), Some(Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap()));
+    assert_eq!(snapshot.repos[0].topics, ["ssh", "tui"]);
+
+    let dir = temp_dir("github");
+    let path = dir.join("github.json");
+    assert_eq!(github::load(&path).unwrap(), Snapshot::default());
+    github::save(&path, snapshot.clone()).unwrap();
+    assert_eq!(github::load(&path).unwrap(), snapshot);
+    // A snapshot is itself a valid API response
+    let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(github::parse_api(&saved["repos"].to_string()).unwrap(), snapshot.repos);
+}
+
+#[test]
+fn the_repos_page_lists_every_repository() {
+    let snapshot = snapshot();
+    let listing = list(Some(&snapshot), RepoSort::Stars, Some("o sorts"));
+    assert_eq!(
+        listing.text,
+        "\n# Repositories\n\nOpen source on https://github.com/medaly\n2 repositories, as of 2025-10-01.\n\no sorts\n\
+         Sort  stars · updated · name\n\n\
+         ## ssh-portfolio\n\n● Rust · ★ 42 · 3 forks · updated 2025-06-01\nA portfolio you visit with ssh\n[ssh] [tui]\n\
+         https://github.com/medaly/ssh-portfolio\n\n\
+         ## dotfiles\n\n● Shell · ★ 7 · updated 2025-09-12 · archived\nhttps://github.com/medaly/dotfiles\n"
+    );
+    assert_eq!(listing.meta_lines, [(11, 0), (18, 1)]);
+    assert_eq!(listing.sort_at(7, 15), Some(RepoSort::Updated));
+
+    assert_eq!(sorted(&snapshot.repos, RepoSort::Updated), [1, 0]);
+    assert_eq!(sorted(&snapshot.repos, RepoSort::Name), [1, 0]);
+    let mut repos = vec![Repo { name: "b".into(), ..Repo::default() }, Repo { name: "A".into(), ..Repo::default() }];
+    repos[0].stargazers_count = 1;
+    assert_eq!(sorted(&repos, RepoSort::Stars), [0, 1]);
+
+    assert_eq!(language_color("Rust"), Color::Rgb { r: 0xde, g: 0xa5, b: 0x84 });
+    assert_eq!(language_color("COBOL"), Color::Grey);
+    assert!(list(None, RepoSort::Stars, None).text.contains("Nothing synced from GitHub yet"));
+}
+
+#[test]
+fn the_sort_key_orders_the_repositories() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("6");
+    assert_eq!(term.portfolio.current_page, Page::Repos);
+    assert_eq!(term.portfolio.breadcrumb(), ["Repos"]);
+    term.keys("o");
+    assert_eq!(term.portfolio.repos.sort, RepoSort::Updated);
+    assert_eq!(term.portfolio.breadcrumb(), ["Repos", "recently updated"]);
+    term.keys("o o");
+    assert_eq!(term.portfolio.repos.sort, RepoSort::Stars);
+
+    // The projects keep their own order
+    term.keys("2");
+    assert_eq!(term.portfolio.breadcrumb(), ["Projects"]);
+}
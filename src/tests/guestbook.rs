@@ -0,0 +1,127 @@
+// tests/guestbook.rs
+
+use chrono::{TimeZone, Utc};
+use std::path::Path;
+
+use crate::config::GuestbookConfig;
+use crate::guestbook::{self, Book, Draft, Entry, Posted};
+use crate::tests::harness::{assert_snapshot, temp_config, TestTerminal};
+use crate::ui::guestbook::{page, Guestbook};
+use crate::ui::keymap::Keymap;
+use crate::ui::markup::{escape, Line, LineKind};
+
+fn draft(name: &str, message: &str, link: &str) -> Draft {
+    Draft { name: name.to_string(), message: message.to_string(), link: link.to_string() }
+}
+
+fn config_in(dir: &Path) -> GuestbookConfig {
+    GuestbookConfig { enabled: true, file: dir.join("guestbook.json"), ..GuestbookConfig::default() }
+}
+
+#[test]
+fn drafts_are_cleaned_and_checked() {
+    let (name, message, link) = guestbook::validate(&draft("  Ada\x1b[31m  ", "hi\u{202e}  there", ""), &[]).unwrap();
+    assert_eq!((name.as_str(), message.as_str(), link), ("Ada[31m", "hi there", None));
+
+    assert!(guestbook::validate(&draft("", "hello", ""), &[]).is_err());
+    assert!(guestbook::validate(&draft("Ada", " \t ", ""), &[]).is_err());
+    assert!(guestbook::validate(&draft(&"a".repeat(41), "hello", ""), &[]).is_err());
+    assert!(guestbook::validate(&draft("Ada", &"a".repeat(501), ""), &[]).is_err());
+    assert!(guestbook::validate(&draft("Ada", "hello", "javascript:alert(1)"), &[]).is_err());
+    let (_, _, link) = guestbook::validate(&draft("Ada", "hello", "https://example.com"), &[]).unwrap();
+    assert_eq!(link.as_deref(), Some("https://example.com"));
+}
+
+#[test]
+fn blocked_words_are_masked() {
+    assert_eq!(guestbook::mask("Shit happens", &[]), "S*** happens");
+    assert_eq!(guestbook::mask("shitake mushrooms", &[]), "shitake mushrooms");
+    assert_eq!(guestbook::mask("nice spam!", &["SPAM".to_string()]), "nice s***!");
+}
+
+#[test]
+fn visitors_are_rate_limited() {
+    let (_dir, config) = temp_config("guestbook-rate", config_in);
+    assert_eq!(guestbook::post(&config, &draft("Ada", "first", ""), Some("abc")), Ok(Posted::Pending));
+    assert!(guestbook::post(&config, &draft("Ada", "second", ""), Some("abc")).is_err());
+    assert_eq!(guestbook::post(&config, &draft("Bob", "hello", ""), Some("def")), Ok(Posted::Pending));
+    // Without a hash every visitor counts as the same one
+    assert_eq!(guestbook::post(&config, &draft("Eve", "hi", ""), None), Ok(Posted::Pending));
+    assert!(guestbook::post(&config, &draft("Mallory", "hi", ""), None).is_err());
+
+    let book = guestbook::load(&config).unwrap();
+    assert_eq!(book.entries.len(), 3);
+    assert_eq!(book.approved().count(), 0);
+}
+
+#[test]
+fn owner_approves_and_deletes() {
+    let (_dir, config) = temp_config("guestbook-moderation", config_in);
+    guestbook::post(&config, &draft("Ada", "hello", ""), Some("abc")).unwrap();
+    guestbook::post(&config, &draft("Bob", "hi", ""), Some("def")).unwrap();
+
+    assert!(guestbook::approve(&config.file, 2).unwrap());
+    assert!(guestbook::delete(&config.file, 1).unwrap());
+    assert!(!guestbook::delete(&config.file, 7).unwrap());
+    let book = guestbook::load(&config).unwrap();
+    let names: Vec<&str> = book.approved().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(names, ["Bob"]);
+}
+
+#[test]
+fn a_fresh_install_has_an_empty_guestbook() {
+    let (dir, mut config) = temp_config("guestbook-fresh", config_in);
+    config.file = dir.join("not-created-yet/guestbook.json");
+    assert!(guestbook::load(&config).unwrap().entries.is_empty());
+}
+
+#[test]
+fn guestbook_page_and_form() {
+    let entries = (1..=12)
+        .map(|id| Entry {
+            id,
+            name: format!("Visitor {}", id),
+            message: "Lovely terminal portfolio, the banner made my day.".to_string(),
+            link: (id % 2 == 0).then(|| format!("https://example.com/{}", id)),
+            posted: Utc.with_ymd_and_hms(2024, 3, id as u32, 12, 0, 0).unwrap(),
+            visitor: None,
+            approved: id != 5,
+        })
+        .collect();
+    // Opening the page reloads the book from the file
+    let (_dir, config) = temp_config("guestbook-page", config_in);
+    let book = Book { next_id: 12, entries };
+    std::fs::write(&config.file, serde_json::to_vec(&book).unwrap()).unwrap();
+    let mut term = TestTerminal::new(80, 24);
+    term.portfolio.guestbook = Some(Guestbook { config, visitor: None, book, page: 0, form: None });
+
+    term.keys("5 >");
+    assert_snapshot("guestbook_older_80x24", &term.render());
+    term.keys("w").type_text("Ada").keys("tab").type_text("Hello from the other side");
+    assert_snapshot("guestbook_form_80x24", &term.render());
+}
+
+#[test]
+fn messages_show_as_typed() {
+    let entry = |id, message: &str| Entry {
+        id,
+        name: format!("Visitor {}", id),
+        message: message.to_string(),
+        link: None,
+        posted: Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap(),
+        visitor: None,
+        approved: true,
+    };
+    let book = Book { next_id: 4, entries: vec![entry(1, "```"), entry(2, "# not a title"), entry(3, "\\o/ - hi")] };
+    let text = page("# Guestbook\n", Some(&book), 0, &Keymap::default(), Utc::now());
+    let lines: Vec<Line> = text.lines().map(Line::parse).collect();
+
+    // The fence would have turned the rest of the page into code
+    assert!(lines.iter().all(|line| line.kind != LineKind::Fence));
+    let titles: Vec<&str> = lines.iter().filter(|line| line.kind == LineKind::Title).map(|line| line.text).collect();
+    assert_eq!(titles, ["Guestbook"]);
+    for message in ["```", "# not a title", "\\o/ - hi"] {
+        assert!(lines.iter().any(|line| line.kind == LineKind::Text && line.text == message), "{}", message);
+    }
+    assert_eq!(escape("plain words"), "plain words");
+}
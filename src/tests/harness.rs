@@ -0,0 +1,190 @@
+// tests/harness.rs
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::keymap::{KeyBinding, Keymap};
+use crate::Portfolio;
+
+// Set to rewrite the golden files instead of comparing against them
+const UPDATE_ENV: &str = "UPDATE_SNAPSHOTS";
+
+// Drives a `Portfolio` without a terminal: events go in, frames come out
+pub struct TestTerminal {
+    pub portfolio: Portfolio,
+    pub running: bool,
+}
+
+impl TestTerminal {
+    pub fn new(width: u16, height: u16) -> Self {
+        let mut portfolio = Portfolio::new(Keymap::default(), true);
+        portfolio.resize(width, height);
+        Self { portfolio, running: true }
+    }
+
+    pub fn event(&mut self, event: Event) -> &mut Self {
+        assert!(self.running, "event sent after the portfolio quit");
+        self.running = self.portfolio.handle_event(event);
+        self
+    }
+
+    pub fn key(&mut self, key: KeyEvent) -> &mut Self {
+        self.event(Event::Key(key))
+    }
+
+    // Space separated keys in config syntax, e.g. "ctrl-p down enter"
+    pub fn keys(&mut self, script: &str) -> &mut Self {
+        for key in script.split_whitespace() {
+            let binding: KeyBinding = key.parse().unwrap_or_else(|e| panic!("bad key {:?}: {}", key, e));
+            self.key(KeyEvent::new(binding.code, binding.modifiers));
+        }
+        self
+    }
+
+    // Types `text` one char at a time, as into a prompt
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        for c in text.chars() {
+            self.key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        self
+    }
+
+    // Sends `text` as a bracketed paste
+    pub fn paste(&mut self, text: &str) -> &mut Self {
+        self.event(Event::Paste(text.to_string()))
+    }
+
+    pub fn render(&mut self) -> Buffer {
+        let mut frame = Buffer::new(self.portfolio.terminal_width, self.portfolio.terminal_height);
+        self.portfolio.draw(&mut frame);
+        frame
+    }
+}
+
+// The frame as text rows, then one style letter per cell and a legend of
+// the letters. Trailing blanks are trimmed so the files diff cleanly.
+pub fn snapshot(frame: &Buffer) -> String {
+    let area = frame.area;
+    let mut letters: HashMap<Style, char> = HashMap::new();
+    let mut legend: Vec<(char, Style)> = Vec::new();
+    let alphabet: Vec<char> = ('a'..='z').chain('A'..='Z').chain('0'..='9').collect();
+
+    let mut text = String::new();
+    let mut styles = String::new();
+    for y in area.y..area.bottom() {
+        let mut row = String::new();
+        let mut style_row = String::new();
+        for x in area.x..area.right() {
+            let cell = frame.get(x, y).expect("cell inside the frame");
+            row.push_str(&cell.symbol);
+            let letter = if cell.style == Style::default() {
+                '.'
+            } else {
+                *letters.entry(cell.style).or_insert_with(|| {
+                    let letter = alphabet.get(legend.len()).copied().unwrap_or('?');
+                    legend.push((letter, cell.style));
+                    letter
+                })
+            };
+            style_row.push(letter);
+        }
+        let _ = writeln!(text, "{}", row.trim_end());
+        let _ = writeln!(styles, "{}", style_row.trim_end_matches('.'));
+    }
+
+    let mut out = format!("size {}x{}\n--- text\n{}--- style\n{}--- legend\n", area.width, area.height, text, styles);
+    for (letter, style) in legend {
+        let _ = writeln!(out, "{} {}", letter, describe(style));
+    }
+    out
+}
+
+fn describe(style: Style) -> String {
+    let mut parts = Vec::new();
+    if let Some(fg) = style.fg {
+        parts.push(format!("fg={:?}", fg));
+    }
+    if let Some(bg) = style.bg {
+        parts.push(format!("bg={:?}", bg));
+    }
+    for (on, name) in [(style.bold, "bold"), (style.dim, "dim"), (style.italic, "italic"), (style.underline, "underline")] {
+        if on {
+            parts.push(name.to_string());
+        }
+    }
+    parts.join(" ")
+}
+
+// A fresh directory under the system temp dir, removed again when the test
+// is done with it, passing or not
+pub struct TempDir(PathBuf);
+
+pub fn temp_dir(name: &str) -> TempDir {
+    let path = std::env::temp_dir().join(format!("ssh-portfolio-{}-{}", name, std::process::id()));
+    // Left over from an earlier run that was killed
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    TempDir(path)
+}
+
+// A fresh directory and a config that keeps its files in it
+pub fn temp_config<C>(name: &str, config: impl FnOnce(&Path) -> C) -> (TempDir, C) {
+    let dir = temp_dir(name);
+    let config = config(&dir);
+    (dir, config)
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/tests/snapshots")
+        .join(format!("{}.snap", name))
+}
+
+// Compares the frame with `src/tests/snapshots/<name>.snap`. The golden
+// files are only written when UPDATE_SNAPSHOTS is set, so a missing one fails.
+pub fn assert_snapshot(name: &str, frame: &Buffer) {
+    let actual = snapshot(frame);
+    let path = golden_path(name);
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        std::fs::write(&path, &actual).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        return;
+    }
+    if !path.exists() {
+        panic!("missing snapshot {}, rerun with {}=1", path.display(), UPDATE_ENV);
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    if expected != actual {
+        let mut diff = String::new();
+        for (i, (want, got)) in expected.lines().zip(actual.lines()).enumerate() {
+            if want != got {
+                let _ = writeln!(diff, "line {}:\n  expected: {}\n  actual:   {}", i + 1, want, got);
+            }
+        }
+        panic!(
+            "snapshot {} does not match {} (rerun with {}=1 to accept)\n{}",
+            name,
+            path.display(),
+            UPDATE_ENV,
+            diff
+        );
+    }
+}
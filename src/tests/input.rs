@@ -0,0 +1,146 @@
+// tests/input.rs
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+use crate::tests::harness::TestTerminal;
+use crate::ui::input::{Field, Form, FormEvent, TextInput};
+use crate::ui::keymap::{prompt_hint, EditAction, PromptAction, FORM_KEYS};
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+fn typed(text: &str) -> TextInput {
+    let mut input = TextInput::new();
+    input.insert_str(text);
+    input
+}
+
+#[test]
+fn editing_keeps_multibyte_chars_whole() {
+    let mut input = typed("héllo wörld");
+    assert!(input.edit(EditAction::Backspace));
+    input.edit(EditAction::WordLeft);
+    input.edit(EditAction::Left);
+    input.edit(EditAction::Backspace);
+    assert_eq!(input.value(), "héll wörl");
+    input.insert('ø');
+    assert_eq!(input.value(), "héllø wörl");
+    input.edit(EditAction::Home);
+    input.edit(EditAction::Delete);
+    assert_eq!(input.value(), "éllø wörl");
+}
+
+#[test]
+fn word_and_line_deletion() {
+    let mut input = typed("one two  three");
+    input.edit(EditAction::DeleteWordBack);
+    assert_eq!(input.value(), "one two  ");
+    input.edit(EditAction::WordLeft);
+    input.edit(EditAction::DeleteWordForward);
+    assert_eq!(input.value(), "one   ");
+    input.edit(EditAction::DeleteToStart);
+    assert_eq!(input.value(), "  ");
+    input.edit(EditAction::DeleteToEnd);
+    assert_eq!(input.value(), "");
+
+    let mut input = typed("hello");
+    assert!(input.handle_key(&key(KeyCode::Char('a'), KeyModifiers::CONTROL)));
+    assert!(input.handle_key(&key(KeyCode::Char('k'), KeyModifiers::CONTROL)));
+    assert!(input.is_empty());
+}
+
+#[test]
+fn inputs_stop_at_the_maximum_length() {
+    let mut input = TextInput::new().max_len(3);
+    assert!(input.insert('a'));
+    input.insert_str("bcdef");
+    assert_eq!(input.value(), "abc");
+    assert!(!input.insert('x'));
+}
+
+#[test]
+fn pastes_are_normalized() {
+    let mut line = typed("a\r\nb\tc\x1b[31md");
+    assert_eq!(line.value(), "a b c[31md");
+    line.clear();
+    assert_eq!(line.len(), 0);
+
+    let mut area = TextInput::new().multiline();
+    area.insert_str("one\r\ntwo\rthree");
+    assert_eq!(area.value(), "one\ntwo\nthree");
+}
+
+#[test]
+fn up_and_down_move_between_lines() {
+    let mut area = TextInput::new().multiline();
+    area.insert_str("first line\nab\nthird line");
+    assert!(area.edit(EditAction::Up));
+    area.insert('X');
+    assert_eq!(area.value(), "first line\nabX\nthird line");
+    assert!(area.edit(EditAction::Up));
+    area.insert('Y');
+    assert_eq!(area.value(), "firYst line\nabX\nthird line");
+    assert!(!area.edit(EditAction::Up));
+
+    let mut line = typed("single");
+    assert!(!line.edit(EditAction::Up));
+    assert!(!line.edit(EditAction::Down));
+}
+
+#[test]
+fn forms_move_focus_and_check_fields() {
+    fn no_digits(value: &str) -> Result<(), String> {
+        match value.chars().any(|c| c.is_ascii_digit()) {
+            true => Err("No digits please".to_string()),
+            false => Ok(()),
+        }
+    }
+    let mut form = Form::new(vec![
+        Field::new("Name", TextInput::new()).check(no_digits),
+        Field::new("Message", TextInput::new().multiline()),
+    ]);
+
+    form.focused().insert_str("R2D2");
+    assert_eq!(form.handle_key(&key(KeyCode::Tab, KeyModifiers::NONE)), FormEvent::Edited);
+    assert_eq!(form.focus, 1);
+    assert_eq!(form.fields[0].error.as_deref(), Some("No digits please"));
+
+    // Enter is a new line in the text area, not a submit
+    form.handle_key(&key(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(form.value(1), "\n");
+    assert_eq!(form.handle_key(&key(KeyCode::BackTab, KeyModifiers::SHIFT)), FormEvent::Edited);
+    assert_eq!(form.focus, 0);
+
+    form.focused().clear();
+    form.handle_key(&key(KeyCode::Down, KeyModifiers::NONE));
+    assert_eq!(form.focus, 1);
+    assert_eq!(form.fields[0].error, None);
+    assert_eq!(form.handle_key(&key(KeyCode::Char('s'), KeyModifiers::CONTROL)), FormEvent::Submit);
+    assert_eq!(form.handle_key(&key(KeyCode::Esc, KeyModifiers::NONE)), FormEvent::Cancel);
+}
+
+#[test]
+fn pastes_go_into_the_search_prompt() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("/").paste("rust\nq");
+    assert!(term.running);
+    assert!(term.portfolio.search.editing);
+    assert_eq!(term.portfolio.search.input.value(), "rust q");
+}
+
+#[test]
+fn typed_bursts_are_still_keys() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("/");
+    let burst = "rust".chars().map(|c| key(KeyCode::Char(c), KeyModifiers::NONE)).chain([key(KeyCode::Enter, KeyModifiers::NONE)]);
+    assert!(term.portfolio.handle_events(burst.map(Event::Key).collect()));
+    assert!(!term.portfolio.search.editing);
+    assert_eq!(term.portfolio.search.input.value(), "rust");
+}
+
+#[test]
+fn form_hint_follows_the_form_keys() {
+    let hint = prompt_hint(FORM_KEYS, &[(PromptAction::Next, "next"), (PromptAction::Confirm, "send"), (PromptAction::Cancel, "close")]);
+    assert_eq!(hint, " Tab next · C-s send · Esc close ");
+}
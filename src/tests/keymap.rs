@@ -0,0 +1,83 @@
+// tests/keymap.rs
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::config::Config;
+use crate::tests::harness::TestTerminal;
+use crate::ui::handler::Page;
+use crate::ui::keymap::{prompt_hint, Action, KeyBinding, Keymap, Preset, PromptAction, HELP_KEYS, SEARCH_PROMPT_KEYS};
+
+#[test]
+fn the_home_page_names_the_active_page_keys() {
+    let mut term = TestTerminal::new(80, 24);
+    assert!(term.portfolio.page_content().contains("\nPress 'a' for Projects | 's' for About | 'd' for Contact\n"));
+    term.portfolio.keymap = Keymap::preset(Preset::Vim);
+    assert!(term.portfolio.page_content().contains("\nPress '2' for Projects | '3' for About | '4' for Contact\n"));
+}
+
+#[test]
+fn keys_parse_and_print_back() {
+    let parsed = |key: &str| key.parse::<KeyBinding>().unwrap();
+    assert_eq!(parsed("ctrl-d"), KeyBinding::ctrl('d'));
+    assert_eq!(parsed("C-d"), KeyBinding::ctrl('d'));
+    assert_eq!(parsed("alt-left"), KeyBinding::alt(KeyCode::Left));
+    assert_eq!(parsed("meta-v"), KeyBinding::alt(KeyCode::Char('v')));
+    assert_eq!(parsed("shift-g"), KeyBinding::plain(KeyCode::Char('G')));
+    assert_eq!(parsed("shift-tab"), KeyBinding::plain(KeyCode::BackTab));
+    assert_eq!(parsed("shift-up"), KeyBinding::new(KeyCode::Up, KeyModifiers::SHIFT));
+    assert_eq!(parsed("ctrl-alt-x"), KeyBinding::new(KeyCode::Char('x'), KeyModifiers::CONTROL | KeyModifiers::ALT));
+    assert_eq!(parsed("-"), KeyBinding::plain(KeyCode::Char('-')));
+    assert_eq!(parsed("ctrl--"), KeyBinding::ctrl('-'));
+    assert_eq!(parsed("space"), KeyBinding::plain(KeyCode::Char(' ')));
+    assert_eq!(parsed("F12"), KeyBinding::plain(KeyCode::F(12)));
+
+    for key in ["q", "G", "C-d", "M-v", "C-M-x", "M-←", "S-↑", "Space", "PgDn", "S-Tab", "Bksp", "Ins", "F1", "Esc"] {
+        assert_eq!(parsed(key).to_string(), key);
+    }
+
+    assert_eq!("".parse::<KeyBinding>(), Err("unknown key ``".to_string()));
+    assert_eq!("ctrl-".parse::<KeyBinding>(), Err("unknown key `ctrl-`".to_string()));
+    assert_eq!("hyper-x".parse::<KeyBinding>(), Err("unknown key `hyper-x`".to_string()));
+    assert_eq!("fx".parse::<KeyBinding>(), Err("unknown key `fx`".to_string()));
+    assert_eq!("ctrl-pagedwn".parse::<KeyBinding>(), Err("unknown key `ctrl-pagedwn`".to_string()));
+}
+
+#[test]
+fn config_bindings_replace_the_presets_keys() {
+    let config = Config::parse("[keys]\npreset = \"vim\"\n\n[keys.bindings]\nquit = [\"x\", \"ctrl-c\"]\nabout = \"j\"\n").unwrap();
+    let keymap = Keymap::from_config(&config.keys).unwrap();
+    let keys = |action| keymap.keys_for(action).map(|key| key.to_string()).collect::<Vec<_>>();
+    assert_eq!(keys(Action::Quit), ["x", "C-c"]);
+    // `j` moves from scrolling to the About page, the other scroll keys stay
+    assert_eq!(keys(Action::GoTo(Page::About)), ["j"]);
+    assert_eq!(keys(Action::ScrollDown), ["C-e", "↓"]);
+    assert_eq!(keymap.lookup(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)), None);
+    assert_eq!(keymap.lookup(&KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE)), Some(Action::ScrollUp));
+
+    let bad = |toml: &str| Keymap::from_config(&Config::parse(toml).unwrap().keys).unwrap_err();
+    assert_eq!(bad("[keys.bindings]\nexplode = \"x\"\n"), "unknown action `explode`");
+    assert_eq!(bad("[keys.bindings]\nquit = \"hyper-x\"\n"), "unknown key `hyper-x`");
+}
+
+#[test]
+fn no_preset_binds_a_key_twice() {
+    for preset in [Preset::Default, Preset::Vim, Preset::Emacs] {
+        let keymap = Keymap::preset(preset);
+        let mut seen: Vec<(KeyBinding, Action)> = Vec::new();
+        for action in Action::ALL {
+            for key in keymap.keys_for(action) {
+                if let Some((_, other)) = seen.iter().find(|(seen, _)| *seen == key) {
+                    panic!("{:?} binds {} to both {} and {}", preset, key, other.name(), action.name());
+                }
+                seen.push((key, action));
+            }
+        }
+    }
+}
+
+#[test]
+fn prompt_hints_share_a_label() {
+    let shown = [(PromptAction::Prev, "scroll"), (PromptAction::Next, "scroll"), (PromptAction::Cancel, "close")];
+    assert_eq!(prompt_hint(HELP_KEYS, &shown), " ↑↓ scroll · Esc close ");
+    assert_eq!(prompt_hint(SEARCH_PROMPT_KEYS, &shown), " Esc close ");
+}
@@ -0,0 +1,17 @@
+// tests/mod.rs
+
+mod analytics;
+mod blog;
+mod contact;
+mod contributions;
+mod counter;
+mod export;
+mod github;
+mod guestbook;
+mod harness;
+mod input;
+mod keymap;
+mod projects;
+mod resume;
+mod snapshots;
+mod web;
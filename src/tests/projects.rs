@@ -0,0 +1,223 @@
+// tests/projects.rs
+
+use crate::config::Config;
+use crate::projects::{self, Link, Project, Status};
+use crate::resume;
+use crate::tests::harness::{assert_snapshot, temp_dir, TestTerminal};
+use crate::ui::handler::Page;
+use crate::ui::projects::{all_tags, card, detail, list, visible, Catalog, Controls, Sort};
+
+fn sample() -> Project {
+    Project {
+        name: "medaly.engineer".to_string(),
+        tagline: "An interactive terminal portfolio".to_string(),
+        tags: vec!["Rust".to_string(), "SSH".to_string(), "Terminal UI".to_string()],
+        year: Some(2025),
+        status: Status::Maintained,
+        links: vec![Link { label: "Website".to_string(), url: "https://www.medaly.engineer".to_string() }],
+        description: "Because portfolios don't have to be websites.\n\n- Keyboard first".to_string(),
+        screenshot: Some("┌──┐\n└──┘".to_string()),
+    }
+}
+
+fn card_text(project: &Project, width: usize) -> Vec<String> {
+    card(project, width, false).iter().map(|line| line.iter().map(|span| span.text.as_str()).collect()).collect()
+}
+
+#[test]
+fn projects_load_from_toml_files() {
+    let built_in = projects::built_in();
+    assert_eq!(built_in.len(), 5);
+    assert_eq!(built_in[0].name, "KOYOTEC DIGITAL");
+    assert_eq!(built_in[1].status, Status::Maintained);
+    assert!(built_in[1].screenshot.is_some());
+
+    let project = projects::parse("name = \"x\"\nstatus = \"archived\"\n\n[[links]]\nlabel = \"Code\"\nurl = \"https://x\"\n").unwrap();
+    assert_eq!(project.status, Status::Archived);
+    assert_eq!(project.links[0].label, "Code");
+    assert!(projects::parse("name = \"x\"\nstars = 3\n").is_err());
+    assert!(projects::parse("status = \"abandoned\"\n").is_err());
+
+    let dir = temp_dir("projects");
+    std::fs::write(dir.join("2-second.toml"), "name = \"Second\"\n").unwrap();
+    std::fs::write(dir.join("1-first.toml"), "name = \"First\"\n").unwrap();
+    std::fs::write(dir.join("notes.txt"), "not a project").unwrap();
+    let names: Vec<String> = projects::load_dir(&dir).unwrap().into_iter().map(|p| p.name).collect();
+    assert_eq!(names, ["First", "Second"]);
+    std::fs::write(dir.join("3-broken.toml"), "name = ").unwrap();
+    assert!(projects::load_dir(&dir).unwrap_err().to_string().contains("3-broken.toml"));
+
+    let config = Config::parse("[projects]\ndir = \"/etc/ssh-portfolio/projects\"\n").unwrap();
+    assert_eq!(config.projects.dir.as_deref(), Some(std::path::Path::new("/etc/ssh-portfolio/projects")));
+}
+
+#[test]
+fn cards_have_a_fixed_height() {
+    assert_eq!(
+        card_text(&sample(), 40),
+        [
+            "╭─ medaly.engineer ───────────── 2025 ─╮",
+            "│ An interactive terminal portfolio    │",
+            "│ [Rust] [SSH] +1         ● Maintained │",
+            "╰──────────────────────────────────────╯",
+        ]
+    );
+    let mut bare = Project { name: "A very long project name indeed".to_string(), ..Project::default() };
+    bare.tags = vec!["Rust".to_string()];
+    assert_eq!(
+        card_text(&bare, 30),
+        [
+            "╭─ A very long project na ───╮",
+            "│                            │",
+            "│ [Rust]            ● Active │",
+            "╰────────────────────────────╯",
+        ]
+    );
+
+    let project = sample();
+    let tags = all_tags(&[sample()]);
+    let controls = Controls { hints: vec!["hint".to_string()], tags: &tags, total: 3 };
+    let listing = list("\n# Projects\n", &[&project, &project], Some(&controls), 40);
+    assert_eq!(
+        listing.text.lines().take(10).collect::<Vec<_>>(),
+        ["", "# Projects", "", "hint", "", "Tags  [Rust] [SSH] [Terminal UI]", "Sort  featured · newest · name · status", "", "2 of 3 projects", ""]
+    );
+    assert_eq!(listing.first_card, 10);
+    assert_eq!(listing.text.lines().nth(10), Some("╭─ medaly.engineer ───────────── 2025 ─╮"));
+    assert_eq!(listing.card_at(2, 9), None);
+    assert_eq!(listing.card_at(2, 13), Some(0));
+    assert_eq!(listing.card_at(2, 14), None);
+    assert_eq!(listing.card_at(2, 15), Some(1));
+    assert_eq!(listing.card_at(2, 20), None);
+    assert_eq!(listing.tag_at(&tags, 5, 6), Some(0));
+    assert_eq!(listing.tag_at(&tags, 5, 12), None);
+    assert_eq!(listing.tag_at(&tags, 5, 13), Some(1));
+    assert_eq!(listing.sort_at(6, 17), Some(Sort::Newest));
+    assert_eq!(listing.sort_at(6, 23), None);
+}
+
+#[test]
+fn tags_filter_and_sorts_order_the_catalog() {
+    let project = |name: &str, tags: &[&str], year, status| Project {
+        name: name.to_string(),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        year,
+        status,
+        ..Project::default()
+    };
+    let catalog = [
+        project("beta", &["Rust", "CLI"], Some(2023), Status::Planned),
+        project("Alpha", &["rust"], None, Status::Active),
+        project("gamma", &["Web", "CLI", "Rust"], Some(2025), Status::Archived),
+    ];
+    assert_eq!(all_tags(&catalog), ["Rust", "CLI", "Web"]);
+
+    let filter = |tags: &[&str]| tags.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+    assert_eq!(visible(&catalog, &[], Sort::Featured), [0, 1, 2]);
+    assert_eq!(visible(&catalog, &filter(&["RUST"]), Sort::Featured), [0, 1, 2]);
+    assert_eq!(visible(&catalog, &filter(&["Rust", "CLI"]), Sort::Featured), [0, 2]);
+    assert_eq!(visible(&catalog, &filter(&["Web", "Go"]), Sort::Featured), [] as [usize; 0]);
+    assert_eq!(visible(&catalog, &[], Sort::Newest), [2, 0, 1]);
+    assert_eq!(visible(&catalog, &[], Sort::Name), [1, 0, 2]);
+    assert_eq!(visible(&catalog, &[], Sort::Status), [1, 0, 2]);
+
+    let mut state = Catalog::default();
+    assert!(state.state().is_empty());
+    state.toggle("Rust");
+    state.toggle("CLI");
+    state.sort = Sort::Name;
+    assert_eq!(state.state(), ["Rust + CLI", "by name"]);
+    state.toggle("rust");
+    state.sort = Sort::Newest;
+    assert_eq!(state.state(), ["CLI", "newest first"]);
+}
+
+#[test]
+fn the_detail_page_has_everything_about_a_project() {
+    let page = detail(&sample(), Some("Press Esc to go back."));
+    assert_eq!(
+        page,
+        "\n# medaly.engineer\n\nAn interactive terminal portfolio\n\n2025 · ● Maintained\n[Rust] [SSH] [Terminal UI]\n\n\
+         \x20 ┌──┐\n  └──┘\n\nBecause portfolios don't have to be websites.\n\n- Keyboard first\n\n\
+         ## Links\n\n- Website: https://www.medaly.engineer\n\nPress Esc to go back.\n"
+    );
+}
+
+#[test]
+fn resume_projects_become_cards() {
+    let resume = resume::parse(
+        r#"{"projects": [{ "name": "Engine", "description": "Computes numbers. With loops.",
+            "highlights": ["Bernoulli"], "keywords": ["Math"], "startDate": "1842-10", "endDate": "1843" }]}"#,
+    )
+    .unwrap();
+    let project = projects::from_resume(&resume.projects[0]);
+    assert_eq!(project.tagline, "Computes numbers.");
+    assert_eq!(project.year, Some(1842));
+    assert_eq!(project.status, Status::Completed);
+    assert_eq!(project.tags, ["Math"]);
+    assert_eq!(project.description, "Computes numbers. With loops.\n\n- Bernoulli");
+}
+
+#[test]
+fn enter_opens_a_project_and_esc_goes_back() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("2 down down");
+    assert_eq!(term.portfolio.catalog.selected, 2);
+    let list_scroll = term.portfolio.scroll_offset;
+    term.keys("enter");
+    assert_eq!(term.portfolio.catalog.open, Some(2));
+    assert_eq!(term.portfolio.scroll_offset, 0);
+    assert!(term.portfolio.page_content().starts_with("\n# IT Engineering @ ESPRIT\n"));
+    term.keys("esc");
+    assert!(term.running);
+    assert_eq!(term.portfolio.catalog.open, None);
+    assert_eq!(term.portfolio.scroll_offset, list_scroll);
+
+    // The finder opens the project a hit belongs to
+    term.keys("1 ctrl-p").type_text("Instagram for Reels").keys("enter");
+    assert_eq!(term.portfolio.current_page, Page::Store);
+    assert_eq!(term.portfolio.catalog.open, Some(3));
+}
+
+#[test]
+fn project_list_and_detail_snapshots() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("2 down");
+    assert_snapshot("projects_selected_80x24", &term.render());
+    term.keys("enter");
+    assert_snapshot("project_detail_80x24", &term.render());
+}
+
+#[test]
+fn tag_and_sort_keys_narrow_the_list() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("2 f");
+    assert_eq!(term.portfolio.catalog.filter, ["DevOps"]);
+    assert_eq!(term.portfolio.breadcrumb(), ["Projects", "DevOps"]);
+    assert!(term.portfolio.page_content().contains("\n3 of 5 projects\n"));
+
+    term.keys("o");
+    assert_eq!(term.portfolio.catalog.sort, Sort::Newest);
+    assert_eq!(term.portfolio.breadcrumb(), ["Projects", "DevOps", "newest first"]);
+    assert_snapshot("projects_filtered_80x24", &term.render());
+
+    // Positions count the cards that are shown
+    term.keys("enter");
+    assert_eq!(term.portfolio.catalog.open, Some(2));
+    assert_eq!(term.portfolio.breadcrumb(), ["Projects", "IT Engineering @ ESPRIT"]);
+
+    // Tag keys act on the list, closing the project
+    term.keys(". f");
+    assert_eq!(term.portfolio.catalog.open, None);
+    assert_eq!(term.portfolio.catalog.filter, ["DevOps", "Three.js"]);
+    assert!(term.portfolio.page_content().contains("\n1 of 5 projects\n"));
+    term.keys(". f");
+    assert!(term.portfolio.page_content().contains("\nNo project has all of these tags.\n"));
+    term.keys("F o o o");
+    assert!(term.portfolio.catalog.filter.is_empty());
+    assert_eq!(term.portfolio.breadcrumb(), ["Projects"]);
+
+    // Elsewhere they do nothing
+    term.keys("3 f");
+    assert!(term.portfolio.catalog.filter.is_empty());
+}
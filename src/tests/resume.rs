@@ -0,0 +1,139 @@
+// tests/resume.rs
+
+use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+use crate::config::Config;
+use crate::resume::{self, Resume};
+use crate::tests::harness::TestTerminal;
+use crate::ui::markup::chips;
+use crate::ui::resume::{all_pages, bar, page, pages, period, text, timeline, Entry, ResumePage, ResumePages};
+
+const RESUME: &str = r#"{
+  "basics": {
+    "name": "Ada Lovelace",
+    "label": "Analyst & Programmer",
+    "url": "https://ada.example",
+    "summary": "First to publish an algorithm intended for a machine.",
+    "location": { "city": "London", "countryCode": "GB" },
+    "profiles": [{ "network": "GitHub", "username": "ada", "url": "https://github.com/ada" }]
+  },
+  "work": [
+    { "name": "Analytical Engine", "position": "Programmer", "startDate": "1842-10",
+      "highlights": ["Wrote the first published program, computing Bernoulli numbers with a loop"] },
+    { "position": "Correspondent", "startDate": "1833", "endDate": "1842" }
+  ],
+  "education": [{ "institution": "Home schooling", "area": "Mathematics", "startDate": "1828", "endDate": "1835" }],
+  "skills": [
+    { "name": "Mathematics", "level": "Master", "keywords": ["Calculus", "Logic"] },
+    { "name": "Poetry", "keywords": ["Poetical science"] }
+  ],
+  "awards": [{ "title": "Not shown, but allowed" }]
+}"#;
+
+#[test]
+fn resumes_load_with_fields_we_do_not_show() {
+    let resume = resume::parse(RESUME).unwrap();
+    assert_eq!(resume.basics.name.as_deref(), Some("Ada Lovelace"));
+    assert_eq!(resume.work[0].start_date.as_deref(), Some("1842-10"));
+    assert_eq!(resume.work[1].end_date.as_deref(), Some("1842"));
+    assert!(resume.projects.is_empty());
+    assert_eq!(resume::parse("{}").unwrap(), Resume::default());
+    assert!(resume::parse("\"resume\"").is_err());
+
+    let config = Config::parse("[resume]\nfile = \"/etc/ssh-portfolio/resume.json\"\n").unwrap();
+    assert_eq!(config.resume.file.as_deref(), Some(std::path::Path::new("/etc/ssh-portfolio/resume.json")));
+    assert!(Config::parse("[resume]\npath = \"x\"\n").is_err());
+}
+
+#[test]
+fn resume_pages_have_timelines_and_skill_bars() {
+    let resume = resume::parse(RESUME).unwrap();
+    let about = page(&resume, ResumePage::About);
+    assert!(about.starts_with("\n# Ada Lovelace\n\n## Analyst & Programmer\n\nFirst to publish"));
+    assert!(about.contains("\nLondon, GB · https://ada.example\n"));
+    assert!(about.contains("\n- GitHub: https://github.com/ada\n"));
+    assert!(!about.contains("Experience"));
+    assert!(!about.contains("## Languages"));
+    assert_eq!(
+        page(&resume, ResumePage::Experience),
+        "\n# Experience\n\n  Oct 1842 – Present  ● Programmer · Analytical Engine\n\
+         \x20                     │ • Wrote the first published program,\n\
+         \x20                     │   computing Bernoulli numbers with\n\
+         \x20                     │   a loop\n\
+         \x20                     │\n\
+         \x20 1833 – 1842         ● Correspondent\n"
+    );
+    assert_eq!(
+        page(&resume, ResumePage::Education),
+        "\n# Education\n\n  1828 – 1835  ● Mathematics\n               │ Home schooling\n"
+    );
+    assert_eq!(
+        page(&resume, ResumePage::Skills),
+        "\n# Skills\n\n  Mathematics  ██████████ Master\n               [Calculus] [Logic]\n  Poetry       [Poetical science]\n"
+    );
+}
+
+#[test]
+fn pages_without_entries_are_left_out() {
+    let mut resume = resume::parse(RESUME).unwrap();
+    resume.education.clear();
+    assert_eq!(pages(&resume), [ResumePage::About, ResumePage::Experience, ResumePage::Skills]);
+    assert!(!all_pages(&resume).contains("# Education"));
+    assert!(text(&resume, ResumePage::Skills, None).starts_with("\nResume  About · Experience · Skills\n\n# Skills\n"));
+}
+
+#[test]
+fn timeline_entries_share_the_date_column() {
+    let entries = [
+        Entry { when: "2024 – Present".into(), title: "Founder".into(), lines: vec!["Tunisia".into()] },
+        Entry { when: "2021".into(), title: "Student".into(), lines: Vec::new() },
+    ];
+    assert_eq!(
+        timeline(&entries),
+        "  2024 – Present  ● Founder\n                  │ Tunisia\n                  │\n  2021            ● Student\n"
+    );
+}
+
+#[test]
+fn levels_periods_and_chips() {
+    assert_eq!(bar("Intermediate").as_deref(), Some("██████░░░░"));
+    assert_eq!(bar("advanced").as_deref(), Some("████████░░"));
+    assert_eq!(bar("35%").as_deref(), Some("████░░░░░░"));
+    assert_eq!(bar("150%").as_deref(), Some("██████████"));
+    assert_eq!(bar("Wizard"), None);
+
+    assert_eq!(period(Some("2024-03"), None), "Mar 2024 – Present");
+    assert_eq!(period(Some("2019-09-01"), Some("2023")), "Sep 2019 – 2023");
+    assert_eq!(period(Some("2021"), Some("2021")), "2021");
+    assert_eq!(period(None, Some("2020-13")), "2020-13");
+    assert_eq!(period(None, None), "");
+
+    assert_eq!(chips(&["Rust", "Docker", "Next.js"], 16), ["[Rust] [Docker]", "[Next.js]"]);
+    assert_eq!(chips(&["a-very-long-tag"], 4), ["[a-very-long-tag]"]);
+    assert!(chips::<&str>(&[], 10).is_empty());
+}
+
+#[test]
+fn braces_and_clicks_switch_the_resume_pages() {
+    let resume: &'static Resume = Box::leak(Box::new(resume::parse(RESUME).unwrap()));
+    let mut term = TestTerminal::new(80, 24);
+    term.portfolio.about = Some(ResumePages::new(resume));
+    term.keys("3");
+    assert_eq!(term.portfolio.breadcrumb(), ["About"]);
+    assert!(term.portfolio.page_content().contains("\n# Ada Lovelace\n"));
+    term.keys("}");
+    assert_eq!(term.portfolio.breadcrumb(), ["About", "Experience"]);
+    assert!(term.portfolio.page_content().contains("\n# Experience\n"));
+    term.keys("{ {");
+    assert_eq!(term.portfolio.breadcrumb(), ["About", "Skills"]);
+
+    // "Resume  About · Experience · Education · Skills" on the second line
+    let area = term.portfolio.screen().content;
+    term.event(Event::Mouse(MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column: area.x + 30,
+        row: area.y + 1,
+        modifiers: KeyModifiers::NONE,
+    }));
+    assert_eq!(term.portfolio.breadcrumb(), ["About", "Education"]);
+}
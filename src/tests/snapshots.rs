@@ -0,0 +1,66 @@
+// tests/snapshots.rs
+
+use crate::tests::harness::{assert_snapshot, TestTerminal};
+use crate::ui::handler::Page;
+
+const SIZES: [(u16, u16); 3] = [(80, 24), (120, 40), (40, 20)];
+
+fn page_key(page: Page) -> &'static str {
+    match page {
+        Page::Home => "1",
+        Page::Store => "2",
+        Page::About => "3",
+        Page::FAQ => "4",
+        Page::Guestbook => "5",
+        Page::Repos => "6",
+        Page::Blog => "7",
+    }
+}
+
+#[test]
+fn every_page_at_every_size() {
+    for (width, height) in SIZES {
+        for page in Page::ALL {
+            let mut term = TestTerminal::new(width, height);
+            term.keys(page_key(page));
+            assert_eq!(term.portfolio.current_page, page);
+            let name = format!("{}_{}x{}", page.label().to_lowercase(), width, height);
+            assert_snapshot(&name, &term.render());
+        }
+    }
+}
+
+#[test]
+fn scrolled_to_the_bottom() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("3 end");
+    assert_snapshot("about_bottom_80x24", &term.render());
+}
+
+#[test]
+fn search_highlights_matches() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("/").type_text("arch").keys("enter");
+    assert_snapshot("search_80x24", &term.render());
+}
+
+#[test]
+fn finder_overlay() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("ctrl-p").type_text("rust");
+    assert_snapshot("finder_80x24", &term.render());
+}
+
+#[test]
+fn help_overlay() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("?");
+    assert_snapshot("help_80x24", &term.render());
+}
+
+#[test]
+fn quit_stops_the_event_loop() {
+    let mut term = TestTerminal::new(80, 24);
+    term.keys("q");
+    assert!(!term.running);
+}
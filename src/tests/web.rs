@@ -0,0 +1,208 @@
+// tests/web.rs
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use std::io::{self, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+use crate::ui::handler::Page;
+use crate::web::http::{self, Response};
+use crate::tests::harness::temp_dir;
+use crate::web::{check_assets, keys, pages};
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+    Event::Key(KeyEvent::new(code, modifiers))
+}
+
+#[test]
+fn terminal_bytes_become_key_events() {
+    assert_eq!(
+        keys::parse("aÉ€\r\t\x7f".as_bytes()),
+        vec![
+            key(KeyCode::Char('a'), KeyModifiers::NONE),
+            key(KeyCode::Char('É'), KeyModifiers::SHIFT),
+            key(KeyCode::Char('€'), KeyModifiers::NONE),
+            key(KeyCode::Enter, KeyModifiers::NONE),
+            key(KeyCode::Tab, KeyModifiers::NONE),
+            key(KeyCode::Backspace, KeyModifiers::NONE),
+        ]
+    );
+    assert_eq!(
+        keys::parse(b"\x10\x1bb\x1b\x7f\x1b"),
+        vec![
+            key(KeyCode::Char('p'), KeyModifiers::CONTROL),
+            key(KeyCode::Char('b'), KeyModifiers::ALT),
+            key(KeyCode::Backspace, KeyModifiers::ALT),
+            key(KeyCode::Esc, KeyModifiers::NONE),
+        ]
+    );
+}
+
+#[test]
+fn escape_sequences_become_special_keys() {
+    assert_eq!(
+        keys::parse(b"\x1b[A\x1bOB\x1b[1;5C\x1b[1;3D\x1b[H\x1b[4~\x1b[5~\x1b[3~\x1b[Z\x1bOP\x1b[24~"),
+        vec![
+            key(KeyCode::Up, KeyModifiers::NONE),
+            key(KeyCode::Down, KeyModifiers::NONE),
+            key(KeyCode::Right, KeyModifiers::CONTROL),
+            key(KeyCode::Left, KeyModifiers::ALT),
+            key(KeyCode::Home, KeyModifiers::NONE),
+            key(KeyCode::End, KeyModifiers::NONE),
+            key(KeyCode::PageUp, KeyModifiers::NONE),
+            key(KeyCode::Delete, KeyModifiers::NONE),
+            key(KeyCode::BackTab, KeyModifiers::SHIFT),
+            key(KeyCode::F(1), KeyModifiers::NONE),
+            key(KeyCode::F(12), KeyModifiers::NONE),
+        ]
+    );
+    // Unknown sequences are dropped whole
+    assert_eq!(keys::parse(b"\x1b[?99xq"), vec![key(KeyCode::Char('q'), KeyModifiers::NONE)]);
+}
+
+#[test]
+fn bracketed_pastes_become_one_event() {
+    assert_eq!(
+        keys::parse("a\x1b[200~line\r\n\tté\x1b[A\x1b[201~b".as_bytes()),
+        vec![
+            key(KeyCode::Char('a'), KeyModifiers::NONE),
+            Event::Paste("line\r\n\tté\x1b[A".to_string()),
+            key(KeyCode::Char('b'), KeyModifiers::NONE),
+        ]
+    );
+    assert_eq!(keys::parse(b"\x1b[200~cut short"), vec![Event::Paste("cut short".to_string())]);
+}
+
+#[test]
+fn sgr_mouse_reports_become_mouse_events() {
+    let mouse = |kind, column, row| Event::Mouse(MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE });
+    assert_eq!(
+        keys::parse(b"\x1b[<0;10;5M\x1b[<32;11;5M\x1b[<0;11;5m\x1b[<65;1;1M\x1b[<64;1;1M"),
+        vec![
+            mouse(MouseEventKind::Down(MouseButton::Left), 9, 4),
+            mouse(MouseEventKind::Drag(MouseButton::Left), 10, 4),
+            mouse(MouseEventKind::Up(MouseButton::Left), 10, 4),
+            mouse(MouseEventKind::ScrollDown, 0, 0),
+            mouse(MouseEventKind::ScrollUp, 0, 0),
+        ]
+    );
+}
+
+#[test]
+fn requests_are_parsed_once_complete() {
+    assert_eq!(http::parse(b"GET /ws HTTP/1.1\r\nHost: a").unwrap(), None);
+
+    let head = b"GET /about?width=100 HTTP/1.1\r\nHost: example.com\r\nUpgrade: WebSocket\r\n\r\nrest";
+    let (request, len) = http::parse(head).unwrap().unwrap();
+    assert_eq!(len, head.len() - 4);
+    assert_eq!((request.method.as_str(), request.path.as_str()), ("GET", "/about"));
+    assert_eq!(request.query.as_deref(), Some("width=100"));
+    assert_eq!(request.header("host"), Some("example.com"));
+    assert!(request.is_upgrade());
+
+    assert!(http::parse(b"\x16\x03\x01 not http\r\n\r\n").is_err());
+}
+
+#[test]
+fn a_trickled_request_head_times_out() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    client.write_all(b"GET / HTTP/1.1\r\nHost: a").unwrap();
+
+    let started = Instant::now();
+    let error = http::peek_request(&server, Duration::from_millis(200)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    client.write_all(b"\r\n\r\n").unwrap();
+    let (request, _) = http::peek_request(&server, Duration::from_secs(5)).unwrap();
+    assert_eq!(request.header("host"), Some("a"));
+}
+
+#[test]
+fn responses_carry_their_length() {
+    let mut out = Vec::new();
+    Response::text(404, "Not found").write(&mut out, false).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    assert!(out.contains("Content-Length: 10\r\n"));
+    assert!(out.ends_with("\r\n\r\nNot found\n"));
+
+    let mut head = Vec::new();
+    Response::text(200, "hello").write(&mut head, true).unwrap();
+    assert!(String::from_utf8(head).unwrap().ends_with("\r\n\r\n"));
+}
+
+#[test]
+fn pages_are_found_by_path_and_client() {
+    assert_eq!(pages::page_at("/"), Some(Page::Home));
+    assert_eq!(pages::page_at("/about/"), Some(Page::About));
+    assert_eq!(pages::page_at("/contact"), Some(Page::FAQ));
+    assert_eq!(pages::page_at("/guestbook"), None);
+
+    assert!(pages::is_terminal_client("curl/8.5.0"));
+    assert!(pages::is_terminal_client("Wget/1.21.4"));
+    assert!(pages::is_terminal_client("HTTPie/3.2.2"));
+    assert!(!pages::is_terminal_client("Mozilla/5.0 (X11; Linux x86_64) curl/8"));
+
+    assert_eq!(pages::width(Some("width=120"), Some("90")), 120);
+    assert_eq!(pages::width(Some("x=1&cols=100"), None), 100);
+    assert_eq!(pages::width(None, Some(" 90 ")), 90);
+    assert_eq!(pages::width(Some("width=5000"), None), pages::MAX_WIDTH);
+    assert_eq!(pages::width(Some("width=abc"), None), pages::DEFAULT_WIDTH);
+}
+
+#[test]
+fn curl_gets_the_page_as_drawn() {
+    let text = pages::ansi(Page::About, 60);
+    let plain: Vec<String> = text
+        .lines()
+        .map(|line| {
+            let mut plain = String::new();
+            let mut escape = false;
+            for c in line.chars() {
+                match c {
+                    '\x1b' => escape = true,
+                    'm' if escape => escape = false,
+                    c if !escape => plain.push(c),
+                    _ => {}
+                }
+            }
+            plain
+        })
+        .collect();
+    assert!(plain.iter().any(|line| line.contains("● ABOUT")));
+    assert!(plain.iter().any(|line| line == "  About Me"));
+    assert!(plain.iter().all(|line| unicode_width::UnicodeWidthStr::width(line.as_str()) <= 60));
+    assert!(plain.iter().all(|line| !line.ends_with(' ')));
+    assert!(text.contains("\x1b[0m\x1b[38;5;14m\x1b[49m\x1b[1mAbout Me\x1b[0m"));
+}
+
+#[test]
+fn browsers_get_plain_html() {
+    let html = pages::html(Page::Home, "https://www.medaly.engineer/");
+    assert!(html.contains("<h1>Welcome to medaly.engineer</h1>"));
+    assert!(html.contains("<li>🚀 Build next-gen websites with cutting-edge tech</li>"));
+    assert!(html.contains("<pre><code class=\"language-rust\">let skills = vec![\n"));
+    assert!(html.contains("Engineering &amp; DevOps because I&#39;m passionate"));
+    assert!(html.contains("<a href=\"/\" aria-current=\"page\">Home</a>"));
+    assert!(html.contains("<a href=\"/terminal\">"));
+    assert!(html.contains("<code>curl https://www.medaly.engineer/</code>"));
+    assert_eq!(html.matches("<pre").count(), html.matches("</pre>").count());
+
+    let contact = pages::html(Page::FAQ, "http://localhost:8080");
+    assert!(contact.contains("<code>curl http://localhost:8080/contact</code>"));
+    assert!(contact.contains("<a href=\"https://"));
+}
+
+#[test]
+fn serving_needs_the_xterm_files() {
+    let dir = temp_dir("assets");
+    std::fs::write(dir.join("xterm.js"), "").unwrap();
+    let error = check_assets(&dir).unwrap_err();
+    assert!(error.to_string().contains("xterm.css is missing"), "{}", error);
+    std::fs::write(dir.join("xterm.css"), "").unwrap();
+    std::fs::write(dir.join("addon-fit.js"), "").unwrap();
+    assert!(check_assets(&dir).is_ok());
+}
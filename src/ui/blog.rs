@@ -0,0 +1,426 @@
+// ui/blog.rs
+
+use crossterm::event::KeyEvent;
+use crossterm::style::Color;
+use log::info;
+
+use crate::posts::{self, Post};
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::handler::PageContent;
+use crate::ui::keymap::{lookup_prompt, Action, Keymap, PromptAction, BLOG_KEYS};
+use crate::ui::layout::Rect;
+use crate::ui::markdown::{self, Rendered};
+use crate::ui::markup::chips;
+use crate::ui::search::Search;
+use crate::ui::view::{Context, Handled, View};
+use crate::ui::widget::{Span, Text, Widget};
+
+const WRAP_WIDTH: usize = 64;
+
+// Where the visitor is on the Blog page during a session
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reading {
+    // Index of the selected post in the list
+    pub selected: usize,
+    // Index of the post shown in the reader
+    pub open: Option<usize>,
+    // Where the list was scrolled to when the post was opened
+    pub list_scroll: u16,
+}
+
+impl Reading {
+    // The list of posts with the keys of `keymap` above it
+    pub fn index(&self, keymap: &Keymap) -> Index {
+        let key = |action| keymap.first_key(action).map(|k| k.to_string()).unwrap_or_default();
+        let read = BLOG_KEYS.iter().find(|(_, action, _)| *action == PromptAction::Confirm);
+        let hint = format!(
+            "{}{} choose a post · {} reads it",
+            key(Action::ScrollUp),
+            key(Action::ScrollDown),
+            read.map(|(key, _, _)| key.to_string()).unwrap_or_default()
+        );
+        index(&PageContent::blog_intro(), posts::posts(), Some(&hint))
+    }
+
+    // The open post, if there is one
+    pub fn article(&self, keymap: &Keymap) -> Option<Article> {
+        let index = self.open.filter(|&i| i < posts::posts().len())?;
+        let key = |action| keymap.first_key(action).map(|k| k.to_string()).unwrap_or_default();
+        let back = BLOG_KEYS.iter().find(|(_, action, _)| *action == PromptAction::Cancel);
+        let hint = format!(
+            "{} newer · {} older · {} all posts",
+            key(Action::NewerPost),
+            key(Action::OlderPost),
+            back.map(|(key, _, _)| key.to_string()).unwrap_or_default()
+        );
+        Some(article(posts::posts(), index, Some(&hint)))
+    }
+
+    fn open_post(&mut self, index: usize, ctx: &mut Context) -> Handled {
+        let Some(post) = posts::posts().get(index) else {
+            return Handled::Yes;
+        };
+        info!(post = post.title.as_str(); "post opened");
+        if self.open.is_none() {
+            self.list_scroll = ctx.scroll;
+        }
+        self.selected = index;
+        self.open = Some(index);
+        ctx.scroll = 0;
+        Handled::Replaced
+    }
+
+    fn close(&mut self, ctx: &mut Context) {
+        self.open = None;
+        let text = self.text(ctx);
+        ctx.scroll_to(self.list_scroll, &text);
+        self.reveal_post(self.selected, ctx);
+    }
+
+    // Moves the selection one post up or down, scrolling past the first and last
+    fn select(&mut self, down: bool, ctx: &mut Context) {
+        let selected = self.selected;
+        let last = posts::posts().len().saturating_sub(1);
+        let text = self.text(ctx);
+        match down {
+            true if selected < last => self.reveal_post(selected + 1, ctx),
+            false if selected > 0 => self.reveal_post(selected - 1, ctx),
+            true => ctx.scroll_to(ctx.scroll.saturating_add(1), &text),
+            false => ctx.scroll_to(ctx.scroll.saturating_sub(1), &text),
+        }
+    }
+
+    // Selects a post and scrolls just enough to show all of its entry
+    fn reveal_post(&mut self, index: usize, ctx: &mut Context) {
+        self.selected = index;
+        let list = self.index(ctx.keymap);
+        if let Some(&(first, last)) = list.entries.get(index) {
+            ctx.show(first, last, &list.text);
+        }
+    }
+}
+
+impl View for Reading {
+    // The list of posts, or the open one
+    fn text(&self, ctx: &Context) -> String {
+        match self.article(ctx.keymap) {
+            Some(article) => article.text,
+            None => self.index(ctx.keymap).text,
+        }
+    }
+
+    fn breadcrumb(&self) -> Vec<String> {
+        self.open.and_then(|i| posts::posts().get(i)).map(|post| post.title.clone()).into_iter().collect()
+    }
+
+    fn handle_key(&mut self, key: &KeyEvent, ctx: &mut Context) -> Handled {
+        match lookup_prompt(BLOG_KEYS, key) {
+            Some(PromptAction::Confirm) if self.open.is_none() && !posts::posts().is_empty() => {
+                self.open_post(self.selected, ctx)
+            }
+            Some(PromptAction::Cancel) if self.open.is_some() => {
+                self.close(ctx);
+                Handled::Replaced
+            }
+            _ => Handled::No,
+        }
+    }
+
+    fn perform(&mut self, action: Action, ctx: &mut Context) -> Handled {
+        match action {
+            Action::ScrollUp | Action::ScrollDown if self.open.is_none() && !posts::posts().is_empty() => {
+                self.select(action == Action::ScrollDown, ctx);
+                Handled::Yes
+            }
+            // Posts are newest first
+            Action::NewerPost | Action::OlderPost => {
+                let next = match (self.open, action) {
+                    (Some(open), Action::NewerPost) => open.checked_sub(1),
+                    (Some(open), _) => Some(open + 1).filter(|&i| i < posts::posts().len()),
+                    (None, _) => None,
+                };
+                match next {
+                    Some(next) => self.open_post(next, ctx),
+                    None => Handled::Yes,
+                }
+            }
+            _ => Handled::No,
+        }
+    }
+
+    // Entries and the tag index on the list, the newer and older post in the reader
+    fn click(&mut self, line: usize, _column: usize, ctx: &mut Context) -> Handled {
+        let post = match self.article(ctx.keymap) {
+            Some(article) => article.post_at(line),
+            None => self.index(ctx.keymap).post_at(line),
+        };
+        match post {
+            Some(post) => self.open_post(post, ctx),
+            None => Handled::Yes,
+        }
+    }
+
+    fn open(&mut self, item: Option<usize>, ctx: &mut Context) -> Handled {
+        match item {
+            _ if item == self.open => Handled::No,
+            Some(index) => self.open_post(index, ctx),
+            None => {
+                self.close(ctx);
+                Handled::Replaced
+            }
+        }
+    }
+
+    fn draw(&self, ctx: &Context, search: &Search, buf: &mut Buffer) {
+        let posts = posts::posts();
+        match (self.open, self.article(ctx.keymap)) {
+            (Some(post), Some(article)) => {
+                ArticleView { posts, post, article: &article, scroll: ctx.scroll, search }.render(ctx.area, buf)
+            }
+            _ => PostList { posts, index: &self.index(ctx.keymap), selected: self.selected, scroll: ctx.scroll, search }
+                .render(ctx.area, buf),
+        }
+    }
+}
+
+// The Blog page text and the lines its parts are on
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Index {
+    pub text: String,
+    // First and last line of each post's entry
+    pub entries: Vec<(usize, usize)>,
+    // (line, post) of the "date · 3 min read" lines and of the tag index
+    pub meta_lines: Vec<(usize, usize)>,
+    pub tag_lines: Vec<(usize, usize)>,
+}
+
+impl Index {
+    // The post whose entry or tag index line is on `line`
+    pub fn post_at(&self, line: usize) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|(first, last)| (*first..=*last).contains(&line))
+            .or_else(|| self.tag_lines.iter().find(|(l, _)| *l == line).map(|(_, post)| *post))
+    }
+}
+
+// "2025-06-14 · 3 min read"
+pub fn meta(post: &Post) -> String {
+    format!("{} · {} min read", post.date.format("%Y-%m-%d"), post.reading_time())
+}
+
+// The summary from the front matter, or else the first paragraph
+pub fn summary(post: &Post) -> String {
+    if !post.summary.trim().is_empty() {
+        return post.summary.trim().to_string();
+    }
+    let first = post.body.split("\n\n").map(str::trim).find(|block| {
+        !block.is_empty() && !block.starts_with(['#', '`', '~', '>', '|', '<', '-', '*', '!'])
+    });
+    markdown::plain(&first.unwrap_or_default().replace('\n', " "))
+}
+
+// Every tag with its posts, by name
+pub fn tag_index(posts: &[Post]) -> Vec<(String, Vec<usize>)> {
+    let mut tags: Vec<(String, Vec<usize>)> = Vec::new();
+    for (i, post) in posts.iter().enumerate() {
+        for tag in &post.tags {
+            match tags.iter_mut().find(|(t, _)| t.eq_ignore_ascii_case(tag)) {
+                Some((_, tagged)) => tagged.push(i),
+                None => tags.push((tag.trim().to_string(), vec![i])),
+            }
+        }
+    }
+    tags.sort_by_key(|(tag, _)| tag.to_lowercase());
+    tags
+}
+
+// The intro from `PageContent`, `hint` under it, an entry per post and the tag index
+pub fn index(intro: &str, posts: &[Post], hint: Option<&str>) -> Index {
+    let mut text = intro.to_string();
+    let mut index = Index::default();
+    if posts.is_empty() {
+        text.push_str("\nNothing written yet, check back soon!\n");
+        index.text = text;
+        return index;
+    }
+    if let Some(hint) = hint {
+        text.push_str(&format!("\n{}\n", hint));
+    }
+
+    for (i, post) in posts.iter().enumerate() {
+        text.push_str(&format!("\n## {}\n", post.title));
+        let first = text.lines().count() - 1;
+        index.meta_lines.push((text.lines().count(), i));
+        text.push_str(&format!("{}\n", meta(post)));
+        for row in chips(&post.tags, WRAP_WIDTH) {
+            text.push_str(&format!("{}\n", row));
+        }
+        for line in textwrap::wrap(&summary(post), WRAP_WIDTH) {
+            text.push_str(&format!("{}\n", line));
+        }
+        index.entries.push((first, text.lines().count() - 1));
+    }
+
+    let tags = tag_index(posts);
+    if !tags.is_empty() {
+        text.push_str("\n## Tags\n");
+        for (tag, tagged) in tags {
+            text.push_str(&format!("\n### {}\n", tag));
+            for i in tagged {
+                index.tag_lines.push((text.lines().count(), i));
+                text.push_str(&format!("- {}  {}\n", posts[i].date.format("%Y-%m-%d"), posts[i].title));
+            }
+        }
+    }
+    index.text = text;
+    index
+}
+
+// A post on a page of its own, rendered from its Markdown
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Article {
+    pub text: String,
+    pub meta_line: usize,
+    // The rendered body, from `body_line` on
+    pub body_line: usize,
+    pub body: Vec<Rendered>,
+    // (line, post) of the newer and older post at the bottom
+    pub links: Vec<(usize, usize)>,
+}
+
+impl Article {
+    pub fn post_at(&self, line: usize) -> Option<usize> {
+        self.links.iter().find(|(l, _)| *l == line).map(|(_, post)| *post)
+    }
+}
+
+// Post `index` of `posts`, newest first. A session passes the keys `hint`
+// and gets the newer and older post at the bottom to go on reading.
+pub fn article(posts: &[Post], index: usize, hint: Option<&str>) -> Article {
+    let post = &posts[index];
+    let mut article = Article { text: format!("\n# {}\n\n", post.title), ..Article::default() };
+    article.meta_line = article.text.lines().count();
+    article.text.push_str(&format!("{}\n", meta(post)));
+    for row in chips(&post.tags, WRAP_WIDTH) {
+        article.text.push_str(&format!("{}\n", row));
+    }
+    article.text.push('\n');
+    article.body_line = article.text.lines().count();
+    article.body = markdown::render(&post.body, WRAP_WIDTH);
+    article.text.push_str(&markdown::markup(&article.body));
+
+    let Some(hint) = hint else {
+        return article;
+    };
+    let newer = index.checked_sub(1).map(|i| ("Newer", i));
+    let older = (index + 1 < posts.len()).then_some(("Older", index + 1));
+    if newer.is_some() || older.is_some() {
+        article.text.push_str("\n## More posts\n\n");
+        for (label, i) in newer.into_iter().chain(older) {
+            article.links.push((article.text.lines().count(), i));
+            article.text.push_str(&format!("{}  {}\n", label, posts[i].title));
+        }
+    }
+    article.text.push_str(&format!("\n{}\n", hint));
+    article
+}
+
+fn meta_spans(post: &Post) -> Vec<Span> {
+    let grey = Style::new().fg(Color::DarkGrey);
+    vec![
+        Span::styled(post.date.format("%Y-%m-%d").to_string(), Style::new().fg(Color::Cyan)),
+        Span::styled(format!(" · {} min read", post.reading_time()), grey),
+    ]
+}
+
+// The row of the area `line` is drawn on, unless it is scrolled away or has
+// search matches, which keep their highlights
+fn row_of(area: Rect, scroll: u16, search: &Search, line: usize) -> Option<Rect> {
+    let row = line.checked_sub(scroll as usize)?;
+    let shown = row < area.height as usize && search.matches_on(line).next().is_none();
+    shown.then(|| Rect::new(area.x, area.y + row as u16, area.width, 1))
+}
+
+fn draw_line(buf: &mut Buffer, area: Rect, spans: Vec<Span>) {
+    buf.fill(area, ' ', Style::default());
+    Text::line(spans).render(area, buf);
+}
+
+// Draws the dates and tags of the list in their colors and marks the
+// selected post
+pub struct PostList<'a> {
+    pub posts: &'a [Post],
+    pub index: &'a Index,
+    pub selected: usize,
+    pub scroll: u16,
+    pub search: &'a Search,
+}
+
+impl Widget for PostList<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let row = |line: usize| row_of(area, self.scroll, self.search, line);
+        for (i, &(first, last)) in self.index.entries.iter().enumerate() {
+            let post = &self.posts[i];
+            if let Some(row_area) = row(first).filter(|_| i == self.selected) {
+                draw_line(buf, row_area, vec![Span::styled(format!("▸ {}", post.title), Style::new().fg(Color::Cyan).bold())]);
+            }
+            for (j, chip_row) in chips(&post.tags, WRAP_WIDTH).into_iter().enumerate() {
+                if let Some(row_area) = row(first + 2 + j).filter(|_| first + 2 + j <= last) {
+                    draw_line(buf, row_area, vec![Span::styled(chip_row, Style::new().fg(Color::Magenta))]);
+                }
+            }
+        }
+        for &(line, i) in &self.index.meta_lines {
+            if let Some(row_area) = row(line) {
+                draw_line(buf, row_area, meta_spans(&self.posts[i]));
+            }
+        }
+        for &(line, i) in &self.index.tag_lines {
+            if let Some(row_area) = row(line) {
+                let post = &self.posts[i];
+                let bullet = Span::styled("● ", Style::new().fg(Color::Yellow));
+                let date = Span::styled(format!("{}  ", post.date.format("%Y-%m-%d")), Style::new().fg(Color::DarkGrey));
+                let title = Span::styled(post.title.clone(), Style::new().fg(Color::White));
+                draw_line(buf, row_area, vec![bullet, date, title]);
+            }
+        }
+    }
+}
+
+// Draws an open post: the rendered Markdown with its emphasis, code and
+// links, the date line and the newer and older post
+pub struct ArticleView<'a> {
+    pub posts: &'a [Post],
+    pub post: usize,
+    pub article: &'a Article,
+    pub scroll: u16,
+    pub search: &'a Search,
+}
+
+impl Widget for ArticleView<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let row = |line: usize| row_of(area, self.scroll, self.search, line);
+        if let Some(row_area) = row(self.article.meta_line) {
+            draw_line(buf, row_area, meta_spans(&self.posts[self.post]));
+        }
+        let tags = chips(&self.posts[self.post].tags, WRAP_WIDTH);
+        for (j, chip_row) in tags.into_iter().enumerate() {
+            if let Some(row_area) = row(self.article.meta_line + 1 + j) {
+                draw_line(buf, row_area, vec![Span::styled(chip_row, Style::new().fg(Color::Magenta))]);
+            }
+        }
+        for (j, line) in self.article.body.iter().enumerate() {
+            if let Some(row_area) = row(self.article.body_line + j).filter(|_| !line.spans.is_empty()) {
+                draw_line(buf, row_area, line.spans.clone());
+            }
+        }
+        for &(line, i) in &self.article.links {
+            if let Some(row_area) = row(line) {
+                let label = if i < self.post { "Newer  " } else { "Older  " };
+                let title = Span::styled(self.posts[i].title.clone(), Style::new().fg(Color::Cyan).bold());
+                draw_line(buf, row_area, vec![Span::styled(label, Style::new().fg(Color::DarkGrey)), title]);
+            }
+        }
+    }
+}
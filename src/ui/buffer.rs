@@ -0,0 +1,249 @@
+// ui/buffer.rs
+
+use crossterm::{
+    cursor, queue,
+    style::{Attribute, Color, Print, SetAttribute, SetBackgroundColor, SetForegroundColor},
+};
+use std::io::Write;
+use unicode_width::UnicodeWidthChar;
+
+use crate::ui::layout::Rect;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+impl Style {
+    pub const fn new() -> Self {
+        Self { fg: None, bg: None, bold: false, dim: false, italic: false, underline: false }
+    }
+
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    pub const fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub const fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    pub const fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    pub const fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    // A char plus any zero-width chars after it, empty for the second half
+    // of a double-width char
+    pub symbol: String,
+    pub style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self { symbol: " ".to_string(), style: Style::default() }
+    }
+}
+
+// In-memory grid of styled cells that widgets draw into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Buffer {
+    pub area: Rect,
+    cells: Vec<Cell>,
+}
+
+impl Buffer {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            area: Rect::new(0, 0, width, height),
+            cells: vec![Cell::default(); width as usize * height as usize],
+        }
+    }
+
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        self.area
+            .contains(x, y)
+            .then(|| (y - self.area.y) as usize * self.area.width as usize + (x - self.area.x) as usize)
+    }
+
+    #[cfg(test)]
+    pub fn get(&self, x: u16, y: u16) -> Option<&Cell> {
+        self.index(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn row(&self, y: u16) -> &[Cell] {
+        match self.index(self.area.x, y) {
+            Some(start) => &self.cells[start..start + self.area.width as usize],
+            None => &[],
+        }
+    }
+
+    // Replaces one cell, blanking what is left of a double-width char it cuts in half
+    fn put(&mut self, x: u16, y: u16, cell: Cell) -> Option<usize> {
+        let i = self.index(x, y)?;
+        if self.cells[i].symbol.is_empty() && x > self.area.x {
+            self.cells[i - 1].symbol = " ".to_string();
+        }
+        let old_width = self.cells[i].symbol.chars().next().and_then(UnicodeWidthChar::width).unwrap_or(1);
+        for extra in 1..old_width as u16 {
+            if let Some(j) = self.index(x + extra, y) {
+                self.cells[j].symbol = " ".to_string();
+            }
+        }
+        self.cells[i] = cell;
+        Some(i)
+    }
+
+    // Fills `area` with `symbol` in `style`
+    pub fn fill(&mut self, area: Rect, symbol: char, style: Style) {
+        for y in area.y..area.bottom() {
+            for x in area.x..area.right() {
+                self.put(x, y, Cell { symbol: symbol.to_string(), style });
+            }
+        }
+    }
+
+    // Writes `text` from (x, y) without going past `max_x`, returns the column after it
+    pub fn set_string_clipped(&mut self, x: u16, y: u16, text: &str, style: Style, max_x: u16) -> u16 {
+        let max_x = max_x.min(self.area.right());
+        let mut x = x;
+        let mut last: Option<usize> = None;
+        for c in text.chars() {
+            let width = UnicodeWidthChar::width(c).unwrap_or(0) as u16;
+            if width == 0 {
+                // Combining marks and variation selectors stick to the previous char
+                if let Some(i) = last {
+                    self.cells[i].symbol.push(c);
+                }
+                continue;
+            }
+            if x + width > max_x {
+                break;
+            }
+            for extra in 0..width {
+                self.put(x + extra, y, Cell { symbol: String::new(), style });
+            }
+            last = self.index(x, y);
+            if let Some(i) = last {
+                self.cells[i].symbol = c.to_string();
+            }
+            x += width;
+        }
+        x
+    }
+
+    pub fn set_string(&mut self, x: u16, y: u16, text: &str, style: Style) -> u16 {
+        self.set_string_clipped(x, y, text, style, u16::MAX)
+    }
+
+    // Draws the rows that differ from `previous` (every row without one),
+    // writing nothing at all when none do
+    pub fn flush(&self, previous: Option<&Buffer>, out: &mut impl Write) -> std::io::Result<()> {
+        let previous = previous.filter(|p| p.area == self.area);
+        let mut current = Style::default();
+        let mut drawn = false;
+
+        for y in self.area.y..self.area.bottom() {
+            let start = self.index(self.area.x, y).unwrap_or(0);
+            let end = start + self.area.width as usize;
+            if let Some(previous) = previous {
+                if previous.cells[start..end] == self.cells[start..end] {
+                    continue;
+                }
+            }
+
+            if !drawn {
+                queue!(out, SetAttribute(Attribute::Reset))?;
+                drawn = true;
+            }
+            queue!(out, cursor::MoveTo(self.area.x, y))?;
+            for cell in &self.cells[start..end] {
+                if cell.symbol.is_empty() {
+                    continue;
+                }
+                if cell.style != current {
+                    apply_style(out, cell.style)?;
+                    current = cell.style;
+                }
+                queue!(out, Print(&cell.symbol))?;
+            }
+        }
+        if drawn {
+            queue!(out, SetAttribute(Attribute::Reset))?;
+        }
+        Ok(())
+    }
+
+    // The whole buffer as lines of styled text, for output that is printed
+    // rather than drawn (e.g. `curl`). Trailing blanks are left out.
+    pub fn write_ansi(&self, out: &mut impl Write) -> std::io::Result<()> {
+        for y in self.area.y..self.area.bottom() {
+            let row = self.row(y);
+            let blank = |cell: &Cell| cell.symbol == " " && cell.style.bg.is_none() && !cell.style.underline;
+            let end = row.iter().rposition(|cell| !blank(cell)).map_or(0, |i| i + 1);
+
+            let mut current = Style::default();
+            for cell in &row[..end] {
+                if cell.symbol.is_empty() {
+                    continue;
+                }
+                if cell.style != current {
+                    apply_style(out, cell.style)?;
+                    current = cell.style;
+                }
+                queue!(out, Print(&cell.symbol))?;
+            }
+            if current != Style::default() {
+                queue!(out, SetAttribute(Attribute::Reset))?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+}
+
+fn apply_style(out: &mut impl Write, style: Style) -> std::io::Result<()> {
+    queue!(
+        out,
+        SetAttribute(Attribute::Reset),
+        SetForegroundColor(style.fg.unwrap_or(Color::Reset)),
+        SetBackgroundColor(style.bg.unwrap_or(Color::Reset)),
+    )?;
+    if style.bold {
+        queue!(out, SetAttribute(Attribute::Bold))?;
+    }
+    if style.dim {
+        queue!(out, SetAttribute(Attribute::Dim))?;
+    }
+    if style.italic {
+        queue!(out, SetAttribute(Attribute::Italic))?;
+    }
+    if style.underline {
+        queue!(out, SetAttribute(Attribute::Underlined))?;
+    }
+    Ok(())
+}
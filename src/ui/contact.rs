@@ -0,0 +1,83 @@
+// ui/contact.rs
+
+use crossterm::event::KeyEvent;
+use log::info;
+
+use crate::config::ContactConfig;
+use crate::contact::{self, is_email, Draft, MAX_EMAIL, MAX_MESSAGE, MAX_NAME, MAX_SUBJECT};
+use crate::ui::input::{self, Field, Form, FormEvent, FormView, TextInput};
+use crate::ui::keymap::{Action, Keymap};
+use crate::ui::layout::Size;
+use crate::ui::widget::{Block, Overlay};
+
+// The contact form as this session sees it
+#[derive(Debug)]
+pub struct Contact {
+    pub config: ContactConfig,
+    // Salted hash of the client address, for the rate limit
+    pub visitor: Option<String>,
+    pub form: Option<Form>,
+}
+
+impl Contact {
+    // Keys typed while the form is open, the thanks to show once it is sent
+    pub fn form_key(&mut self, key: &KeyEvent) -> Option<String> {
+        let form = self.form.as_mut()?;
+        match form.handle_key(key) {
+            FormEvent::Cancel => self.form = None,
+            FormEvent::Submit => match contact::send(&self.config, &draft(form), self.visitor.as_deref()) {
+                Ok(()) => {
+                    info!("contact message sent");
+                    self.form = None;
+                    return Some("Thanks for your message, I will get back to you soon".to_string());
+                }
+                Err(e) => form.error = Some(e),
+            },
+            FormEvent::Edited => {}
+        }
+        None
+    }
+}
+
+pub fn form() -> Form {
+    Form::new(vec![
+        Field::new("Name", TextInput::new().max_len(MAX_NAME)),
+        Field::new("Email", TextInput::new().max_len(MAX_EMAIL).placeholder("So I can reply")).check(check_email),
+        Field::new("Subject", TextInput::new().max_len(MAX_SUBJECT)),
+        Field::new("Message", TextInput::new().max_len(MAX_MESSAGE).multiline()),
+    ])
+}
+
+pub fn draft(form: &Form) -> Draft {
+    Draft {
+        name: form.value(0).to_string(),
+        email: form.value(1).to_string(),
+        subject: form.value(2).to_string(),
+        body: form.value(3).to_string(),
+    }
+}
+
+fn check_email(email: &str) -> Result<(), String> {
+    if is_email(email.trim()) {
+        Ok(())
+    } else {
+        Err("That does not look like an email address".to_string())
+    }
+}
+
+// Added to the Contact page under its first section
+pub fn page(content: &str, keymap: &Keymap) -> String {
+    let key = keymap.first_key(Action::WriteMessage).map(|k| k.to_string()).unwrap_or_default();
+    let section = format!(
+        "\n## Send me a message\n\nPress {} to write to me without leaving the terminal.\n\n",
+        key
+    );
+    let mut text = content.to_string();
+    let at = content.match_indices("\n## ").nth(1).map_or(content.len(), |(i, _)| i);
+    text.insert_str(at, &section);
+    text
+}
+
+pub fn form_overlay(form: &Form) -> Overlay<Block<FormView<'_>>> {
+    input::form_overlay(form, "Send me a message", Size::new(72, 18))
+}
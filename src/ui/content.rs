@@ -0,0 +1,109 @@
+// ui/content.rs
+
+use crossterm::style::Color;
+
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::handler::Page;
+use crate::ui::header::{self, Header};
+use crate::ui::layout::{Rect, Size};
+use crate::ui::markup::{char_slice, display_width, links, truncate, Line, LineKind};
+use crate::ui::search::Search;
+use crate::ui::widget::Widget;
+
+// The visible part of a page, with search matches and links highlighted
+pub struct ContentView<'a> {
+    pub content: &'a str,
+    pub scroll: u16,
+    pub search: &'a Search,
+}
+
+impl Widget for ContentView<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let lines = self.content.lines().enumerate().skip(self.scroll as usize);
+        for (row, (i, line)) in lines.take(area.height as usize).enumerate() {
+            let y = area.y + row as u16;
+
+            // Parse and render colored lines
+            let mut line = Line::parse(line);
+            let width = (area.width as usize).saturating_sub(display_width(line.marker()));
+            line.text = truncate(line.text, width);
+            let style = match line.kind {
+                LineKind::Title => Style::new().fg(Color::Cyan).bold(),
+                LineKind::Heading => Style::new().fg(Color::Green).bold(),
+                LineKind::Fence => Style::new().fg(Color::Magenta).dim(),
+                LineKind::Subheading | LineKind::Bullet | LineKind::Text => Style::new().fg(Color::White),
+            };
+            let marker_style = match line.kind {
+                LineKind::Bullet => Style::new().fg(Color::Yellow),
+                _ => style,
+            };
+            let mut x = buf.set_string(area.x, y, line.marker(), marker_style);
+
+            // Split the text where search matches and links start or end
+            let links = links(line.text);
+            let mut bounds = vec![0, line.text.chars().count()];
+            for (_, m) in self.search.matches_on(i) {
+                bounds.extend([m.start, m.end]);
+            }
+            for (start, end) in &links {
+                bounds.extend([*start, *end]);
+            }
+            bounds.sort_unstable();
+            bounds.dedup();
+
+            for span in bounds.windows(2) {
+                let (start, end) = (span[0], span[1]);
+                let matched = self.search.matches_on(i).find(|(_, m)| m.start <= start && end <= m.end);
+                let linked = links.iter().any(|(s, e)| *s <= start && end <= *e);
+                let span_style = match matched {
+                    Some((index, _)) => {
+                        let highlight = if index == self.search.current { Color::Yellow } else { Color::DarkYellow };
+                        Style::new().bg(highlight).fg(Color::Black)
+                    }
+                    None if linked => Style::new().fg(Color::Blue).underline(),
+                    None => style,
+                };
+                x = buf.set_string_clipped(x, y, char_slice(line.text, start, end), span_style, area.right());
+            }
+        }
+    }
+}
+
+// The whole page with the banner and navigation above it, for output that is
+// printed or exported instead of scrolled
+pub fn full_page(page: Page, content: &str, width: u16) -> Buffer {
+    let lines = content.lines().count() as u16;
+    let mut buf = Buffer::new(width, header::HEIGHT + 1 + lines);
+    Header { page }.render(Rect::new(0, 0, width, header::HEIGHT), &mut buf);
+    let area = Rect::new(2, header::HEIGHT + 1, width.saturating_sub(2), lines);
+    ContentView { content, scroll: 0, search: &Search::default() }.render(area, &mut buf);
+    buf
+}
+
+// One column tall as the area, the thumb showing which part of the page is in view
+pub struct Scrollbar {
+    pub offset: u16,
+    pub max_scroll: u16,
+}
+
+impl Widget for Scrollbar {
+    fn measure(&self, available: Size) -> Size {
+        Size::new(available.width.min(1), available.height)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let height = area.height;
+        if self.max_scroll == 0 || height == 0 {
+            return;
+        }
+        let lines = self.max_scroll + height;
+        let thumb = (height as u32 * height as u32 / lines as u32).max(1) as u16;
+        let thumb_y = (self.offset.min(self.max_scroll) as u32 * (height - thumb) as u32 / self.max_scroll as u32) as u16;
+
+        for row in 0..height {
+            let on_thumb = row >= thumb_y && row < thumb_y + thumb;
+            let (symbol, color) = if on_thumb { ("┃", Color::Cyan) } else { ("│", Color::DarkGrey) };
+            buf.set_string(area.x, area.y + row, symbol, Style::new().fg(color));
+        }
+    }
+}
@@ -0,0 +1,338 @@
+// ui/finder.rs
+
+use crossterm::event::KeyEvent;
+use crossterm::style::Color;
+
+use crate::ui::buffer::{Buffer, Style};
+use crate::{posts, projects};
+use crate::ui::handler::{Page, PageContent};
+use crate::ui::input::{InputLine, TextInput};
+use crate::ui::keymap::{lookup_prompt, prompt_hint, PromptAction, FINDER_KEYS};
+use crate::ui::layout::{Constraint, Rect, Size};
+use crate::ui::markup::{display_width, truncate, Line, LineKind};
+use crate::ui::widget::{Block, Overlay, Rule, Stack, Widget};
+
+const MAX_RESULTS: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub page: Page,
+    // Set for lines of a project's or a post's own page, which `line` counts in
+    pub item: Option<usize>,
+    pub line: usize,
+    pub text: String,
+    // Closest heading above the line, empty before the first one
+    pub section: String,
+    pub is_heading: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Hit {
+    pub entry: usize,
+    pub score: i32,
+    // Char positions in the entry text that matched the query
+    pub positions: Vec<usize>,
+}
+
+#[derive(Debug)]
+pub struct Finder {
+    pub open: bool,
+    pub input: TextInput,
+    pub entries: Vec<Entry>,
+    pub hits: Vec<Hit>,
+    pub selected: usize,
+}
+
+impl Finder {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            input: TextInput::new(),
+            entries: index_pages(),
+            hits: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.input.clear();
+        self.update();
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn update(&mut self) {
+        self.selected = 0;
+        if self.input.is_empty() {
+            // Without a query the finder doubles as a table of contents
+            self.hits = self
+                .entries
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| entry.is_heading)
+                .map(|(i, _)| Hit { entry: i, score: 0, positions: Vec::new() })
+                .collect();
+            return;
+        }
+
+        let mut hits: Vec<Hit> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| {
+                fuzzy_match(&entry.text, self.input.value()).map(|(score, positions)| Hit {
+                    entry: i,
+                    score: score + if entry.is_heading { 10 } else { 0 },
+                    positions,
+                })
+            })
+            .collect();
+        // Stable sort keeps page order among equal scores
+        hits.sort_by_key(|hit| std::cmp::Reverse(hit.score));
+        hits.truncate(MAX_RESULTS);
+        self.hits = hits;
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.hits.is_empty() {
+            self.selected = (self.selected + 1) % self.hits.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.hits.is_empty() {
+            self.selected = (self.selected + self.hits.len() - 1) % self.hits.len();
+        }
+    }
+
+    pub fn selected_entry(&self) -> Option<&Entry> {
+        self.hits.get(self.selected).map(|hit| &self.entries[hit.entry])
+    }
+
+    // Keys typed while the finder is open, the entry to jump to once one is picked
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Option<Entry> {
+        match lookup_prompt(FINDER_KEYS, key) {
+            Some(PromptAction::Cancel) => self.close(),
+            Some(PromptAction::Confirm) => {
+                self.close();
+                return self.selected_entry().cloned();
+            }
+            Some(PromptAction::Prev) => self.select_prev(),
+            Some(PromptAction::Next) => self.select_next(),
+            None => {
+                let before = self.input.value().to_string();
+                self.input.handle_key(key);
+                if self.input.value() != before {
+                    self.update();
+                }
+            }
+        }
+        None
+    }
+
+    pub fn paste(&mut self, text: &str) {
+        self.input.insert_str(text);
+        self.update();
+    }
+}
+
+impl Default for Finder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The finder overlay: the query, a rule, then the results
+pub fn finder_overlay(finder: &Finder) -> Overlay<Block<Stack<'_>>> {
+    let count = format!("{} results ", finder.hits.len());
+    let input = QueryLine { input: &finder.input, count };
+    let body = Stack::vertical()
+        .push(Constraint::Length(1), input)
+        .push(Constraint::Length(1), Rule::horizontal(Style::new().fg(Color::Cyan)))
+        .push(Constraint::Min(0), Results { finder });
+    let block = Block::new(body)
+        .title(" Find anywhere ")
+        .hint(prompt_hint(
+            FINDER_KEYS,
+            &[(PromptAction::Prev, "select"), (PromptAction::Next, "select"), (PromptAction::Confirm, "open"), (PromptAction::Cancel, "close")],
+        ));
+    Overlay::new(block, Size::new(90, 24))
+        .min(Size::new(20, 6))
+        .margin(Size::new(4, 4))
+}
+
+struct QueryLine<'a> {
+    input: &'a TextInput,
+    count: String,
+}
+
+impl Widget for QueryLine<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        buf.set_string_clipped(area.x, area.y, " > ", Style::new().fg(Color::Yellow), area.right());
+        let width = area.width.saturating_sub(3 + self.count.len() as u16 + 2);
+        let input = InputLine { input: self.input, focused: true, style: Style::new().fg(Color::White) };
+        input.render(Rect::new(area.x + 3, area.y, width, 1), buf);
+        let count_x = area.right().saturating_sub(display_width(&self.count) as u16);
+        buf.set_string_clipped(count_x, area.y, &self.count, Style::new().fg(Color::DarkGrey), area.right());
+    }
+}
+
+struct Results<'a> {
+    finder: &'a Finder,
+}
+
+impl Widget for Results<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let finder = self.finder;
+        let inner = area.width as usize;
+        let rows = area.height as usize;
+        if rows == 0 {
+            return;
+        }
+
+        // Keep the selection in view
+        let first = finder.selected.saturating_sub(rows - 1);
+        let show_section = inner >= 60;
+        for (row, hit) in finder.hits.iter().skip(first).take(rows).enumerate() {
+            let y = area.y + row as u16;
+            let entry = &finder.entries[hit.entry];
+            let selected = first + row == finder.selected;
+            let background = if selected { Style::new().bg(Color::DarkGrey) } else { Style::new() };
+            buf.fill(area.row(row as u16), ' ', background);
+
+            let page = format!("{}{:<9}", if selected { "›" } else { " " }, entry.page.label());
+            let section = if show_section && !entry.is_heading {
+                format!("{:<22} ", truncate(&entry.section, 21))
+            } else if show_section {
+                " ".repeat(23)
+            } else {
+                String::new()
+            };
+            let mut x = buf.set_string_clipped(area.x, y, &page, background.fg(Color::Cyan), area.right());
+            x = buf.set_string_clipped(x, y, &section, background.fg(Color::DarkGrey), area.right());
+
+            let color = if entry.is_heading { Color::Green } else { Color::White };
+            let text = truncate(&entry.text, (area.right() - x) as usize);
+            for (i, c) in text.chars().enumerate() {
+                let style = if hit.positions.contains(&i) {
+                    background.fg(Color::Yellow).bold()
+                } else {
+                    background.fg(color)
+                };
+                x = buf.set_string_clipped(x, y, c.encode_utf8(&mut [0; 4]), style, area.right());
+            }
+        }
+    }
+}
+
+// Every non-blank line of every page, with the section it belongs to. The
+// Projects and Blog pages count as their intro plus each project's or post's
+// own page, and About with a resume as each of its pages.
+fn index_pages() -> Vec<Entry> {
+    let mut entries = Vec::new();
+    for page in Page::ALL {
+        match page {
+            Page::Store => {
+                index_text(&mut entries, page, None, &PageContent::projects_intro());
+                for (i, project) in projects::catalog().iter().enumerate() {
+                    index_text(&mut entries, page, Some(i), &crate::ui::projects::detail(project, None));
+                }
+            }
+            Page::Blog => {
+                index_text(&mut entries, page, None, &PageContent::blog_intro());
+                let posts = posts::posts();
+                for i in 0..posts.len() {
+                    index_text(&mut entries, page, Some(i), &crate::ui::blog::article(posts, i, None).text);
+                }
+            }
+            Page::About => match PageContent::resume() {
+                Some(resume) => {
+                    for shown in crate::ui::resume::pages(resume) {
+                        index_text(&mut entries, page, Some(shown.index()), &crate::ui::resume::text(resume, shown, None));
+                    }
+                }
+                None => index_text(&mut entries, page, None, &PageContent::get_content(&page)),
+            },
+            _ => index_text(&mut entries, page, None, &PageContent::get_content(&page)),
+        }
+    }
+    entries
+}
+
+fn index_text(entries: &mut Vec<Entry>, page: Page, item: Option<usize>, content: &str) {
+    let mut section = String::new();
+    let mut in_code = false;
+    for (i, raw) in content.lines().enumerate() {
+        let line = Line::parse(raw);
+        if line.kind == LineKind::Fence {
+            in_code = !in_code;
+            continue;
+        }
+        let (text, is_heading) = match line.kind {
+            _ if in_code => (raw.trim(), false),
+            _ => (line.text.trim(), line.level().is_some()),
+        };
+        if text.is_empty() {
+            continue;
+        }
+        if is_heading {
+            section = text.to_string();
+        }
+        entries.push(Entry {
+            page,
+            item,
+            line: i,
+            text: text.to_string(),
+            section: section.clone(),
+            is_heading,
+        });
+    }
+}
+
+// Subsequence match, case-insensitive. Consecutive characters and matches at the
+// start of words score higher, gaps cost a little. Every occurrence of the first
+// query character is tried as a starting point and the best run wins.
+pub fn fuzzy_match(text: &str, query: &str) -> Option<(i32, Vec<usize>)> {
+    let chars: Vec<char> = text.chars().map(fold).collect();
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).map(fold).collect();
+    let first = *query.first()?;
+
+    let best = (0..chars.len())
+        .filter(|&i| chars[i] == first)
+        .filter_map(|start| match_from(&chars, &query, start))
+        .max_by_key(|(score, _)| *score)?;
+
+    // Shorter lines are usually the more precise hit
+    Some((best.0 - (chars.len() as i32) / 20, best.1))
+}
+
+fn match_from(chars: &[char], query: &[char], start: usize) -> Option<(i32, Vec<usize>)> {
+    let mut positions = Vec::with_capacity(query.len());
+    let mut score = -(start.min(10) as i32) / 2;
+    let mut next = start;
+
+    for &q in query {
+        let found = (next..chars.len()).find(|&i| chars[i] == q)?;
+
+        score += 1;
+        if found == 0 || !chars[found - 1].is_alphanumeric() {
+            score += 8;
+        }
+        match positions.last() {
+            Some(&p) if p + 1 == found => score += 5,
+            Some(&p) => score -= ((found - p - 1) as i32).min(5),
+            None => {}
+        }
+
+        positions.push(found);
+        next = found + 1;
+    }
+    Some((score, positions))
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
@@ -0,0 +1,95 @@
+// ui/footer.rs
+
+use chrono::{DateTime, Utc};
+use crossterm::style::Color;
+
+use crate::counter::{thousands, time_ago, Stats};
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::input::input_spans;
+use crate::ui::keymap::{prompt_hint, Action, Keymap, PromptAction, SEARCH_PROMPT_KEYS};
+use crate::ui::layout::{Constraint, Rect, Size};
+use crate::ui::search::Search;
+use crate::ui::widget::{Padding, Rule, Span, Stack, Text, Widget};
+
+pub const HEIGHT: u16 = 2;
+
+// A rule across the screen and one line under it, `right` aligned to its end
+pub struct Footer {
+    pub line: Vec<Span>,
+    pub right: Vec<Span>,
+}
+
+impl Widget for Footer {
+    fn measure(&self, available: Size) -> Size {
+        Size::new(available.width, HEIGHT.min(available.height))
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        Stack::vertical()
+            .push(Constraint::Length(1), Rule::horizontal(Style::new().fg(Color::DarkGrey)))
+            .push(Constraint::Length(1), Padding::horizontal(Text::line(self.line.clone()), 2))
+            .render(area, buf);
+
+        let line_width: usize = self.line.iter().map(Span::width).sum();
+        let right_width: usize = self.right.iter().map(Span::width).sum();
+        if area.height < 2 || 2 + line_width + 2 + right_width + 2 > area.width as usize {
+            return;
+        }
+        let row = Rect::new(area.right() - 2 - right_width as u16, area.y + 1, right_width as u16, 1);
+        Text::line(self.right.clone()).render(row, buf);
+    }
+}
+
+// As many key hints as fit in `width` columns
+pub fn hints(keymap: &Keymap, width: u16) -> Vec<Span> {
+    let grey = Style::new().fg(Color::Grey);
+    let mut spans = vec![Span::styled("Navigation: ", grey)];
+    let mut used = "Navigation: ".len();
+    for (i, (keys, label)) in keymap.hints().iter().enumerate() {
+        let separator = if i == 0 { "" } else { " | " };
+        let hint_width = separator.chars().count() + keys.chars().count() + 1 + label.len();
+        if used + hint_width > width as usize {
+            break;
+        }
+        used += hint_width;
+        spans.push(Span::styled(separator, grey));
+        spans.push(Span::styled(keys.clone(), Style::new().fg(Color::Cyan)));
+        spans.push(Span::styled(format!(" {}", label), grey));
+    }
+    spans
+}
+
+pub fn search_prompt(search: &Search, keymap: &Keymap) -> Vec<Span> {
+    let hint = if search.editing {
+        prompt_hint(SEARCH_PROMPT_KEYS, &[(PromptAction::Confirm, "confirm"), (PromptAction::Cancel, "cancel")]).trim().to_string()
+    } else {
+        let key = |action| keymap.first_key(action).map(|k| k.to_string()).unwrap_or_default();
+        format!("{}/{} next/prev | Esc clear", key(Action::SearchNext), key(Action::SearchPrev))
+    };
+    let mut spans = vec![Span::styled("/", Style::new().fg(Color::Cyan))];
+    spans.extend(input_spans(&search.input, search.editing, Style::new().fg(Color::White)));
+    spans.push(Span::styled(format!("  {}", search.counter()), Style::new().fg(Color::Yellow)));
+    spans.push(Span::styled(format!("  {}", hint), Style::new().fg(Color::DarkGrey)));
+    spans
+}
+
+pub fn status(text: &str) -> Vec<Span> {
+    vec![Span::styled(text, Style::new().fg(Color::Green))]
+}
+
+// e.g. "1,234 visitors · 2 online · last visit 5m ago"
+pub fn counter(stats: &Stats, now: DateTime<Utc>) -> Vec<Span> {
+    let number = Style::new().fg(Color::Cyan);
+    let grey = Style::new().fg(Color::DarkGrey);
+    let mut spans = vec![
+        Span::styled(thousands(stats.total), number),
+        Span::styled(if stats.total == 1 { " visitor" } else { " visitors" }, grey),
+        Span::styled(" · ", grey),
+        Span::styled(stats.online.to_string(), Style::new().fg(Color::Green)),
+        Span::styled(" online", grey),
+    ];
+    if let Some(last_visit) = stats.last_visit {
+        spans.push(Span::styled(format!(" · last visit {}", time_ago(last_visit, now)), grey));
+    }
+    spans
+}
@@ -0,0 +1,160 @@
+// ui/guestbook.rs
+
+use chrono::{DateTime, Utc};
+use crossterm::event::KeyEvent;
+use log::{info, warn};
+
+use crate::config::GuestbookConfig;
+use crate::guestbook::{self, Book, Draft, Posted, MAX_LINK, MAX_MESSAGE, MAX_NAME};
+use crate::ui::handler::{Page, PageContent};
+use crate::ui::input::{self, Field, Form, FormEvent, FormView, TextInput};
+use crate::ui::keymap::{Action, Keymap};
+use crate::ui::layout::Size;
+use crate::ui::markup::escape;
+use crate::ui::view::{Context, Handled, View};
+use crate::ui::widget::{Block, Overlay};
+
+pub const ENTRIES_PER_PAGE: usize = 10;
+const WRAP_WIDTH: usize = 72;
+
+// The open guestbook as this session sees it
+#[derive(Debug)]
+pub struct Guestbook {
+    pub config: GuestbookConfig,
+    // Salted hash of the client address, for the rate limit
+    pub visitor: Option<String>,
+    pub book: Book,
+    // Which screen of entries is shown, 0 is the newest
+    pub page: usize,
+    pub form: Option<Form>,
+}
+
+impl Guestbook {
+    // Picks up entries other sessions added or the owner approved
+    pub fn reload(&mut self) {
+        match guestbook::load(&self.config) {
+            Ok(book) => self.book = book,
+            Err(e) => warn!(error = e.to_string(); "could not read the guestbook"),
+        }
+    }
+
+    // Keys typed while the form is open, the thanks to show once it is signed
+    pub fn form_key(&mut self, key: &KeyEvent) -> Option<String> {
+        let form = self.form.as_mut()?;
+        match form.handle_key(key) {
+            FormEvent::Cancel => self.form = None,
+            FormEvent::Submit => match guestbook::post(&self.config, &draft(form), self.visitor.as_deref()) {
+                Ok(posted) => {
+                    info!(published = posted == Posted::Published; "guestbook signed");
+                    self.form = None;
+                    self.page = 0;
+                    self.reload();
+                    return Some(match posted {
+                        Posted::Published => "Thanks for signing the guestbook!".to_string(),
+                        Posted::Pending => "Thanks! Your entry will show up once it is approved".to_string(),
+                    });
+                }
+                Err(e) => form.error = Some(e),
+            },
+            FormEvent::Edited => {}
+        }
+        None
+    }
+}
+
+impl View for Guestbook {
+    fn text(&self, ctx: &Context) -> String {
+        page(&PageContent::get_content(&Page::Guestbook), Some(&self.book), self.page, ctx.keymap, Utc::now())
+    }
+
+    fn perform(&mut self, action: Action, ctx: &mut Context) -> Handled {
+        let last = pages(&self.book) - 1;
+        self.page = match action {
+            Action::NewerEntries => self.page.saturating_sub(1),
+            Action::OlderEntries => (self.page + 1).min(last),
+            _ => return Handled::No,
+        };
+        ctx.scroll = 0;
+        Handled::Yes
+    }
+}
+
+// The "sign the guestbook" form
+pub fn form() -> Form {
+    Form::new(vec![
+        Field::new("Name", TextInput::new().max_len(MAX_NAME).placeholder("How should I call you?")),
+        Field::new("Message", TextInput::new().max_len(MAX_MESSAGE).placeholder("Say hi!")),
+        Field::new("Link", TextInput::new().max_len(MAX_LINK).placeholder("https://... (optional)")).check(check_link),
+    ])
+}
+
+pub fn draft(form: &Form) -> Draft {
+    Draft { name: form.value(0).to_string(), message: form.value(1).to_string(), link: form.value(2).to_string() }
+}
+
+fn check_link(link: &str) -> Result<(), String> {
+    if link.starts_with("https://") || link.starts_with("http://") {
+        Ok(())
+    } else {
+        Err("Links must start with https:// or http://".to_string())
+    }
+}
+
+// The guestbook page: the intro from `PageContent`, then one screen of entries
+pub fn page(intro: &str, book: Option<&Book>, index: usize, keymap: &Keymap, now: DateTime<Utc>) -> String {
+    let key = |action| keymap.first_key(action).map(|k| k.to_string()).unwrap_or_default();
+    let mut text = intro.to_string();
+    let Some(book) = book else {
+        text.push_str("\nThe guestbook is closed at the moment, please come back later.\n");
+        return text;
+    };
+
+    let entries: Vec<_> = book.approved().collect();
+    if entries.is_empty() {
+        text.push_str(&format!("\nNobody has signed yet. Press {} to be the first!\n", key(Action::WriteGuestbook)));
+        return text;
+    }
+    text.push_str(&format!("\nPress {} to sign it.\n", key(Action::WriteGuestbook)));
+
+    let pages = pages(book);
+    let index = index.min(pages - 1);
+    for entry in entries.iter().skip(index * ENTRIES_PER_PAGE).take(ENTRIES_PER_PAGE) {
+        text.push_str(&format!("\n## {} · {}\n\n", entry.name, posted(entry.posted, now)));
+        // Visitors write plain text, not page markup
+        for line in textwrap::wrap(&entry.message, WRAP_WIDTH) {
+            text.push_str(&escape(&line));
+            text.push('\n');
+        }
+        if let Some(link) = &entry.link {
+            text.push_str(&format!("{}\n", escape(link)));
+        }
+    }
+    if pages > 1 {
+        text.push_str(&format!(
+            "\nPage {} of {} · {} newer · {} older\n",
+            index + 1,
+            pages,
+            key(Action::NewerEntries),
+            key(Action::OlderEntries)
+        ));
+    }
+    text
+}
+
+// Number of screens of approved entries, at least one
+pub fn pages(book: &Book) -> usize {
+    book.approved().count().div_ceil(ENTRIES_PER_PAGE).max(1)
+}
+
+// "2024-03-01", or "today" for entries from the last day
+fn posted(then: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    if then.date_naive() == now.date_naive() {
+        "today".to_string()
+    } else {
+        then.format("%Y-%m-%d").to_string()
+    }
+}
+
+pub fn form_overlay(form: &Form) -> Overlay<Block<FormView<'_>>> {
+    input::form_overlay(form, "Sign the guestbook", Size::new(70, 6))
+}
@@ -1,55 +1,167 @@
 // ui/handler.rs
 
-#[derive(Debug, Clone, PartialEq)]
+use std::sync::OnceLock;
+
+use crate::resume::Resume;
+use crate::ui::projects::CARD_WIDTH;
+use crate::ui::heatmap;
+use crate::ui::repos::RepoSort;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Page {
     Home,
     Store,  // Projects page
     About,
     FAQ,    // Contact page
+    Guestbook,
+    Repos,
+    Blog,
 }
 
-#[derive(Debug, Clone)]
-pub struct UIState {
-    pub current_page: Page,
-    pub scroll_offset: usize,
-    pub selected_item: usize,
-}
+impl Page {
+    // Order of the pages in the navigation bar
+    pub const ALL: [Page; 7] =
+        [Page::Home, Page::Store, Page::About, Page::FAQ, Page::Guestbook, Page::Repos, Page::Blog];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Page::Home => "Home",
+            Page::Store => "Projects",
+            Page::About => "About",
+            Page::FAQ => "Contact",
+            Page::Guestbook => "Guestbook",
+            Page::Repos => "Repos",
+            Page::Blog => "Blog",
+        }
+    }
 
-impl UIState {
-    pub fn new() -> Self {
-        Self {
-            current_page: Page::Home,
-            scroll_offset: 0,
-            selected_item: 0,
+    // File and URL name of the page, e.g. in an exported site
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Page::Home => "home",
+            Page::Store => "projects",
+            Page::About => "about",
+            Page::FAQ => "contact",
+            Page::Guestbook => "guestbook",
+            Page::Repos => "repos",
+            Page::Blog => "blog",
         }
     }
 
-    pub fn reset_scroll(&mut self) {
-        self.scroll_offset = 0;
-        self.selected_item = 0;
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|page| page == self).unwrap_or(0)
+    }
+
+    pub fn next(&self) -> Page {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
     }
-}
 
-impl Default for UIState {
-    fn default() -> Self {
-        Self::new()
+    pub fn prev(&self) -> Page {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
     }
 }
 
 pub struct PageContent;
 
+// Pages generated from the `[resume]` file, the same for every session
+static GENERATED: OnceLock<Generated> = OnceLock::new();
+
+struct Generated {
+    resume: Resume,
+    about: String,
+}
+
+// Who the pages are about, for exports that need it as data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub url: &'static str,
+    // ISO 3166-1 alpha-2
+    pub country_code: &'static str,
+}
+
 impl PageContent {
+    pub fn profile() -> Profile {
+        Profile {
+            name: "Med Ali Dridi",
+            label: "Engineer | Content Creator | Founder @ KOYOTEC DIGITAL",
+            url: "https://www.medaly.engineer",
+            country_code: "TN",
+        }
+    }
+
+    // Replaces the built-in About page. Only the first call counts.
+    pub fn use_resume(resume: Resume) {
+        let about = crate::ui::resume::all_pages(&resume);
+        let _ = GENERATED.set(Generated { resume, about });
+    }
+
+    pub fn resume() -> Option<&'static Resume> {
+        GENERATED.get().map(|generated| &generated.resume)
+    }
+
     pub fn get_content(page: &Page) -> String {
+        let generated = GENERATED.get();
         match page {
-            Page::Home => Self::home_content(),
+            Page::Home => {
+                let mut home = Self::home_content(None);
+                if let Some(calendar) = crate::contributions::calendar() {
+                    heatmap::append(&mut home, calendar, chrono::Utc::now().date_naive(), heatmap::EXPORT_WIDTH);
+                }
+                home
+            }
             Page::Store => Self::projects_content(),
-            Page::About => Self::about_content(),
+            Page::About => match generated {
+                Some(generated) => generated.about.clone(),
+                None => Self::about_content(),
+            },
             Page::FAQ => Self::contact_content(),
+            Page::Guestbook => Self::guestbook_content(),
+            Page::Repos => crate::ui::repos::list(crate::github::snapshot(), RepoSort::default(), None).text,
+            Page::Blog => Self::blog_content(),
         }
     }
 
-    fn home_content() -> String {
+    // Every card, then every project's page, for output nobody can press
+    // Enter in: the web pages, the exports and the finder's index
+    fn projects_content() -> String {
+        let projects = crate::projects::catalog();
+        let cards: Vec<_> = projects.iter().collect();
+        let mut text = crate::ui::projects::list(&Self::projects_intro(), &cards, None, CARD_WIDTH).text;
+        for project in projects {
+            text.push_str(&crate::ui::projects::detail(project, None));
+        }
+        text
+    }
+
+    // The list, then every post, like the projects
+    fn blog_content() -> String {
+        let posts = crate::posts::posts();
+        let mut text = crate::ui::blog::index(&Self::blog_intro(), posts, None).text;
+        for i in 0..posts.len() {
+            text.push_str(&crate::ui::blog::article(posts, i, None).text);
+        }
+        text
+    }
+
+    // The entries themselves are added below by `ui::guestbook::page`
+    fn guestbook_content() -> String {
         r#"
+# Guestbook
+
+## Say hi!
+
+Thanks for stopping by. Leave a note for me and the next visitors:
+who you are, what brought you here, or a link to your corner
+of the web.
+"#.to_string()
+    }
+
+    // `hint` is the line about the page keys, for a session that has them
+    pub fn home_content(hint: Option<&str>) -> String {
+        let mut home = r#"
     ███╗   ███╗███████╗██████╗  █████╗ ██╗  ██╗   ██╗
     ████╗ ████║██╔════╝██╔══██╗██╔══██╗██║  ╚██╗ ██╔╝  
     ██╔████╔██║█████╗  ██║  ██║███████║██║   ╚████╔╝ 
@@ -128,110 +240,35 @@ every project at KOYOTEC and everything I create.
 
 
 Navigate using the menu above to explore my work!
-
-Press 'a' for Projects | 's' for About | 'd' for Contact
-"#.to_string()
+"#.to_string();
+        if let Some(hint) = hint {
+            home.push('\n');
+            home.push_str(hint);
+            home.push('\n');
+        }
+        home
     }
 
-    fn projects_content() -> String {
+    // The cards and the projects' own pages are added by `ui::projects`
+    pub fn projects_intro() -> String {
         r#"
 # My Projects
 
 ## Innovation Meets Creativity
 
+Things I build, run and dream up.
+Want to collaborate on the next one? Check out my contact page!
+"#.to_string()
+    }
 
-## 🏢 KOYOTEC DIGITAL
-## Tech: Next.js, React, GSAP, Three.js, Modern Web Stack
-
-A top 1% creative digital agency based in Tunisia, building next-generation
-websites that push the boundaries of what's possible on the web.
-
-- High-performance web applications
-- Cutting-edge animation techniques
-- 3D interactive experiences
-- Modern, responsive designs that feel alive
-- Storytelling through technology
-
-Our mission: Making technology feel like art, bringing together design,
-tech, and narrative into unforgettable digital experiences.
-
-```javascript
-// Our approach
-const koyotec = {
-  creativity: "top 1%",
-  tech: ["Next.js", "React", "GSAP", "Three.js"],
-  philosophy: "Technology should always feel like art"
-};
-```
-
-
-## 🌐 medaly.engineer
-## Tech: Terminal UI, ASCII Art, SSH Access
-
-An interactive terminal-based portfolio accessible via web and SSH.
-Because portfolios don't have to be limited to traditional websites.
-
-- Beautiful ASCII art and terminal aesthetics
-- Fully keyboard-navigable interface
-- Clean, minimalist design
-- Remote SSH access capabilities
-- Rust-powered backend for performance
-
-```bash
-# Access it remotely
-curl https://www.medaly.engineer
-```
-
-
-## 🎓 Academic & Learning Projects
-
-### IT Engineering @ ESPRIT University
-Currently pursuing advanced studies in Software Engineering, with focus on:
-- Modern web architecture
-- Cloud infrastructure and DevOps
-- System design and scalability
-- Advanced programming concepts
-
-### Mechatronics Engineering Background
-Original engineering foundation providing unique perspective on:
-- Systems thinking and integration
-- Hardware-software interaction
-- Problem-solving from multiple angles
-
-
-## 📱 Content Creation
-
-### Tech & DevOps Content
-Creating engaging content about:
-- DevOps best practices
-- Linux tips and tricks (yes, Arch!)
-- Creative coding and web animations
-- Behind-the-scenes at KOYOTEC
-- Developer life and workflows
-
-Follow along on Instagram for Reels and tech insights!
-
-
-## 🚀 Upcoming Projects
-
-- Advanced 3D web experiences
-- Interactive storytelling platforms
-- Open-source contributions
-- DevOps automation tools
-- Creative experiments with emerging tech
-
-
-## 💡 Collaboration Opportunities
-
-Interested in working together? I'm open to:
-- Freelance web development projects
-- Creative collaborations
-- Content partnerships
-- Tech community initiatives
-- Mentorship and knowledge sharing
+    // The posts and the tag index are added by `ui::blog`
+    pub fn blog_intro() -> String {
+        r#"
+# Blog
 
+## Notes from the Workshop
 
-Want to collaborate? Check out my contact page!
+What I learn building things, written down so I don't forget it.
 "#.to_string()
     }
 
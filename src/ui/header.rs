@@ -1,101 +1,143 @@
-use crossterm::{
-    cursor,
-    execute,
-    style::{SetForegroundColor, Color, Print, ResetColor, SetAttribute, Attribute},
-};
-use std::io::{self, Write};
+use crossterm::style::Color;
+use unicode_width::UnicodeWidthStr;
+use crate::ui::buffer::{Buffer, Style};
 use crate::ui::handler::Page;
+use crate::ui::layout::{Rect, Size};
+use crate::ui::widget::Widget;
 
-const NAV_ITEM_WIDTH: usize = 12;
+// Columns around a label, for "● " and a space on both sides
+const NAV_ITEM_PADDING: usize = 4;
 
 pub fn center_text(text: &str, width: usize) -> String {
-    let padding = (width.saturating_sub(text.len())) / 2;
-    format!("{:padding$}{}{:padding$}", "", text, "", padding = padding)
+    let text_width = UnicodeWidthStr::width(text);
+    let padding = width.saturating_sub(text_width) / 2;
+    let right = width.saturating_sub(text_width + padding);
+    format!("{:padding$}{}{:right$}", "", text, "", padding = padding, right = right)
 }
 
-pub fn nav_item(label: &str, highlighted: bool) -> String {
+pub fn nav_item(label: &str, highlighted: bool, width: usize) -> String {
     if highlighted {
-        center_text(&format!("● {}", label), NAV_ITEM_WIDTH)
+        center_text(&format!("● {}", label), width)
     } else {
-        center_text(&format!("○ {}", label), NAV_ITEM_WIDTH)
+        center_text(&format!("○ {}", label), width)
     }
 }
 
-pub fn draw_header(stdout: &mut io::Stdout, start_x: u16, start_y: u16, current_page: &Page) -> crossterm::Result<u16> {
-    // Draw animated gradient header banner
-    draw_banner(stdout, start_x, start_y)?;
-    
-    // Navigation below banner
-    let nav_y = start_y + 5;
-    execute!(stdout, cursor::MoveTo(start_x, nav_y), SetForegroundColor(Color::DarkGrey))?;
-    
-    let home = nav_item("HOME", matches!(current_page, Page::Home));
-    let projects = nav_item("PROJECTS", matches!(current_page, Page::Store));
-    let about = nav_item("ABOUT", matches!(current_page, Page::About));
-    let contact = nav_item("CONTACT", matches!(current_page, Page::FAQ));
-    
-    let components = vec![home, projects, about, contact];
-    
-    let mut x = start_x + 2;
-    let mut top_border = String::from("╭");
-    let mut bottom_border = String::from("╰");
-    
-    for (i, component) in components.iter().enumerate() {
-        top_border.push_str(&"─".repeat(component.len()));
-        bottom_border.push_str(&"─".repeat(component.len()));
-        
-        if i < components.len() - 1 {
-            top_border.push('┬');
-            bottom_border.push('┴');
+// Row of the navigation labels, relative to the top of the header
+pub const NAV_ROW: u16 = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NavItem {
+    pub page: Page,
+    pub x: u16,
+    pub width: u16,
+}
+
+// Where each navigation label is drawn, shared by drawing and mouse hit-testing.
+// Each is as wide as its label so all of them fit in 80 columns.
+pub fn nav_layout(start_x: u16) -> Vec<NavItem> {
+    let mut x = start_x + 1;
+    Page::ALL
+        .iter()
+        .map(|&page| {
+            let width = UnicodeWidthStr::width(page.label()) + NAV_ITEM_PADDING;
+            let item = NavItem { page, x, width: width as u16 };
+            x += item.width + 1;
+            item
+        })
+        .collect()
+}
+
+pub fn nav_hit(start_x: u16, start_y: u16, column: u16, row: u16) -> Option<Page> {
+    if row != start_y + NAV_ROW {
+        return None;
+    }
+    nav_layout(start_x)
+        .into_iter()
+        .find(|item| column >= item.x && column < item.x + item.width)
+        .map(|item| item.page)
+}
+
+// Banner plus the navigation bar, always `HEIGHT` rows tall
+pub struct Header {
+    pub page: Page,
+}
+
+pub const HEIGHT: u16 = 8;
+
+impl Widget for Header {
+    fn measure(&self, available: Size) -> Size {
+        Size::new(available.width, HEIGHT.min(available.height))
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        draw_banner(area, buf);
+
+        // Navigation below banner
+        let nav_y = area.y + NAV_ROW - 1;
+        let items = nav_layout(area.x);
+
+        let mut top_border = String::from("╭");
+        let mut bottom_border = String::from("╰");
+        for (i, item) in items.iter().enumerate() {
+            top_border.push_str(&"─".repeat(item.width as usize));
+            bottom_border.push_str(&"─".repeat(item.width as usize));
+
+            if i < items.len() - 1 {
+                top_border.push('┬');
+                bottom_border.push('┴');
+            }
         }
+        top_border.push('╮');
+        bottom_border.push('╯');
+
+        let border = Style::new().fg(Color::DarkGrey);
+        let right = area.right();
+        buf.set_string_clipped(area.x, nav_y, &top_border, border, right);
+        buf.set_string_clipped(area.x, nav_y + 1, "│", border, right);
+
+        for item in &items {
+            let is_highlighted = item.page == self.page;
+            let component = nav_item(&item.page.label().to_uppercase(), is_highlighted, item.width as usize);
+            let style = if is_highlighted {
+                Style::new().fg(Color::Cyan).bold()
+            } else {
+                Style::new().fg(Color::Grey)
+            };
+            buf.set_string_clipped(item.x, nav_y + 1, &component, style, right);
+            buf.set_string_clipped(item.x + item.width, nav_y + 1, "│", border, right);
+        }
+
+        buf.set_string_clipped(area.x, nav_y + 2, &bottom_border, border, right);
     }
-    
-    top_border.push('╮');
-    bottom_border.push('╯');
-    
-    execute!(stdout, cursor::MoveTo(start_x, nav_y), Print(&top_border))?;
-    execute!(stdout, cursor::MoveTo(start_x, nav_y + 1), Print("│"))?;
-    
-    for (i, component) in components.iter().enumerate() {
-        execute!(stdout, cursor::MoveTo(x, nav_y + 1))?;
-        
-        let is_highlighted = (i == 0 && matches!(current_page, Page::Home)) ||
-                           (i == 1 && matches!(current_page, Page::Store)) ||
-                           (i == 2 && matches!(current_page, Page::About)) ||
-                           (i == 3 && matches!(current_page, Page::FAQ));
-        
-        if is_highlighted {
-            execute!(
-                stdout,
-                SetAttribute(Attribute::Bold),
-                SetForegroundColor(Color::Cyan),
-                Print(component),
-                SetAttribute(Attribute::Reset),
-                ResetColor,
-                SetForegroundColor(Color::DarkGrey)
-            )?;
-        } else {
-            execute!(stdout, SetForegroundColor(Color::Grey), Print(component), SetForegroundColor(Color::DarkGrey))?;
+}
+
+// "Projects › Rust + Docker › newest first" under the navigation, drawn
+// when there is more to it than the page name
+pub struct Breadcrumb<'a> {
+    pub parts: &'a [String],
+}
+
+impl Widget for Breadcrumb<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if self.parts.len() < 2 || area.height == 0 {
+            return;
         }
-        
-        x += component.len() as u16;
-        
-        if i < components.len() - 1 {
-            execute!(stdout, cursor::MoveTo(x, nav_y + 1), Print("│"))?;
-            x += 1;
+        let right = area.right();
+        let mut x = area.x + 2;
+        for (i, part) in self.parts.iter().enumerate() {
+            if i > 0 {
+                x = buf.set_string_clipped(x, area.y, " › ", Style::new().fg(Color::DarkGrey), right);
+            }
+            let style = if i == 0 { Style::new().fg(Color::Cyan) } else { Style::new().fg(Color::White).bold() };
+            x = buf.set_string_clipped(x, area.y, part, style, right);
         }
     }
-    
-    execute!(stdout, cursor::MoveTo(x, nav_y + 1), Print("│"))?;
-    execute!(stdout, cursor::MoveTo(start_x, nav_y + 2), Print(&bottom_border))?;
-    execute!(stdout, ResetColor)?;
-    
-    Ok(8) // Total header height including banner
 }
 
-fn draw_banner(stdout: &mut io::Stdout, start_x: u16, start_y: u16) -> crossterm::Result<()> {
+fn draw_banner(area: Rect, buf: &mut Buffer) {
     // ASCII art banner with gradient effect
-    let banner_lines = vec![
+    let banner_lines = [
         "  ██████╗  ██████╗ ██████╗ ████████╗███████╗ ██████╗ ██╗     ██╗ ██████╗ ",
         "  ██╔══██╗██╔═══██╗██╔══██╗╚══██╔══╝██╔════╝██╔═══██╗██║     ██║██╔═══██╗",
         "  ██████╔╝██║   ██║██████╔╝   ██║   █████╗  ██║   ██║██║     ██║██║   ██║",
@@ -103,8 +145,8 @@ fn draw_banner(stdout: &mut io::Stdout, start_x: u16, start_y: u16) -> crossterm
         "  ██║     ╚██████╔╝██║  ██║   ██║   ██║     ╚██████╔╝███████╗██║╚██████╔╝",
         "  ╚═╝      ╚═════╝ ╚═╝  ╚═╝   ╚═╝   ╚═╝      ╚═════╝ ╚══════╝╚═╝ ╚═════╝ ",
     ];
-    
-    let colors = vec![
+
+    let colors = [
         Color::Magenta,
         Color::Blue,
         Color::Cyan,
@@ -112,17 +154,9 @@ fn draw_banner(stdout: &mut io::Stdout, start_x: u16, start_y: u16) -> crossterm
         Color::Yellow,
         Color::Red,
     ];
-    
+
     for (i, line) in banner_lines.iter().enumerate() {
-        execute!(
-            stdout,
-            cursor::MoveTo(start_x, start_y + i as u16),
-            SetForegroundColor(colors[i % colors.len()]),
-            SetAttribute(Attribute::Bold),
-            Print(line),
-            SetAttribute(Attribute::Reset)
-        )?;
+        let style = Style::new().fg(colors[i % colors.len()]).bold();
+        buf.set_string_clipped(area.x, area.y + i as u16, line, style, area.right());
     }
-    
-    Ok(())
 }
@@ -0,0 +1,213 @@
+// ui/heatmap.rs
+
+use chrono::{Datelike, Duration, NaiveDate};
+use crossterm::style::Color;
+
+use crate::contributions::Calendar;
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::layout::Rect;
+use crate::ui::search::Search;
+use crate::ui::widget::{Span, Text, Widget};
+
+// Indented so the markup takes the block as art and leaves it alone
+const INDENT: &str = "  ";
+const DAY_LABELS: [&str; 7] = ["    ", "Mon ", "    ", "Wed ", "    ", "Fri ", "    "];
+const LABEL_WIDTH: usize = 4;
+const WEEKS: usize = 53;
+// What a level looks like without colors, in the web pages and exports
+const SHADES: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+const SPARKS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+// The web pages are as wide as an 80 column terminal
+pub const EXPORT_WIDTH: usize = 80;
+
+fn green(level: usize) -> Color {
+    let rgb = |hex: u32| Color::Rgb { r: (hex >> 16) as u8, g: (hex >> 8) as u8, b: hex as u8 };
+    match level {
+        0 => Color::DarkGrey,
+        1 => rgb(0x0e4429),
+        2 => rgb(0x006d32),
+        3 => rgb(0x26a641),
+        _ => rgb(0x39d353),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    // A year of weeks side by side, `cell` columns each
+    Calendar { cell: usize },
+    // A bar per week, as many as fit
+    Sparkline { weeks: usize },
+}
+
+pub fn layout(width: usize) -> Layout {
+    let room = width.saturating_sub(INDENT.len() + LABEL_WIDTH);
+    if room >= WEEKS * 2 {
+        Layout::Calendar { cell: 2 }
+    } else if room >= WEEKS {
+        Layout::Calendar { cell: 1 }
+    } else {
+        Layout::Sparkline { weeks: width.saturating_sub(INDENT.len()).clamp(1, WEEKS) }
+    }
+}
+
+// Adds the "## Contributions" section for the weeks up to `today` to `page`
+// and returns the line its calendar starts on
+pub fn append(page: &mut String, calendar: &Calendar, today: NaiveDate, width: usize) -> usize {
+    let layout = layout(width);
+    let weeks = match layout {
+        Layout::Calendar { .. } => WEEKS,
+        Layout::Sparkline { weeks } => weeks,
+    };
+    let start = first_day(today, weeks);
+    let total: u32 = days(start, today).map(|day| calendar.count(day)).sum();
+    let plural = if total == 1 { "" } else { "s" };
+    let period = if weeks == WEEKS { "the last year".to_string() } else { format!("the last {} weeks", weeks) };
+    page.push_str(&format!("\n## Contributions\n\n{} contribution{} in {}\n\n", total, plural, period));
+
+    let first_line = page.lines().count();
+    for line in block(calendar, today, width, false) {
+        let text: String = line.iter().map(|span| span.text.as_str()).collect();
+        page.push_str(text.trim_end());
+        page.push('\n');
+    }
+    first_line
+}
+
+// The calendar or sparkline lines, in shades of green when `colored`
+pub fn block(calendar: &Calendar, today: NaiveDate, width: usize, colored: bool) -> Vec<Vec<Span>> {
+    match layout(width) {
+        Layout::Calendar { cell } => calendar_lines(calendar, today, cell, colored),
+        Layout::Sparkline { weeks } => sparkline_lines(calendar, today, weeks, colored),
+    }
+}
+
+// Sunday of the first of `weeks` weeks ending with the one of `today`
+fn first_day(today: NaiveDate, weeks: usize) -> NaiveDate {
+    let sunday = today - Duration::days(today.weekday().num_days_from_sunday() as i64);
+    sunday - Duration::weeks(weeks as i64 - 1)
+}
+
+fn days(start: NaiveDate, end: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    start.iter_days().take_while(move |day| *day <= end)
+}
+
+// 1 to 4 in quarters of the busiest, 0 for nothing at all
+fn level(count: u32, max: u32) -> usize {
+    match count {
+        0 => 0,
+        _ => ((count * 4).div_ceil(max.max(1)) as usize).clamp(1, 4),
+    }
+}
+
+fn cell_span(level: usize, colored: bool) -> Span {
+    match colored {
+        true => Span::styled("■", Style::new().fg(green(level))),
+        false => Span::styled(SHADES[level], Style::default()),
+    }
+}
+
+//       Nov   Dec   Jan
+//   Mon ·░··▓·█··
+//   ...
+//   Less ·░▒▓█ More
+fn calendar_lines(calendar: &Calendar, today: NaiveDate, cell: usize, colored: bool) -> Vec<Vec<Span>> {
+    let grey = Style::new().fg(Color::DarkGrey);
+    let start = first_day(today, WEEKS);
+    let max = days(start, today).map(|day| calendar.count(day)).max().unwrap_or(0);
+    let gap = " ".repeat(cell - 1);
+
+    // A month's name over its first week, unless the previous name is in the way
+    let mut months = " ".repeat(LABEL_WIDTH);
+    for week in 0..WEEKS {
+        let sunday = start + Duration::weeks(week as i64);
+        let column = LABEL_WIDTH + week * cell;
+        let new_month = week == 0 || (sunday - Duration::weeks(1)).month() != sunday.month();
+        let taken = months.trim_end().chars().count() + if months.trim().is_empty() { 0 } else { 1 };
+        if new_month && taken <= column && column + 3 <= LABEL_WIDTH + WEEKS * cell {
+            months.push_str(&" ".repeat(column - months.chars().count()));
+            months.push_str(&sunday.format("%b").to_string());
+        }
+    }
+    let mut lines = vec![vec![Span::styled(INDENT, Style::default()), Span::styled(months, grey)]];
+
+    for (weekday, label) in DAY_LABELS.iter().enumerate() {
+        let mut line = vec![Span::styled(INDENT, Style::default()), Span::styled(*label, grey)];
+        for week in 0..WEEKS {
+            let day = start + Duration::days((week * 7 + weekday) as i64);
+            match day <= today {
+                true => line.push(cell_span(level(calendar.count(day), max), colored)),
+                false => line.push(Span::styled(" ", Style::default())),
+            }
+            line.push(Span::styled(gap.clone(), Style::default()));
+        }
+        lines.push(line);
+    }
+
+    let mut legend = vec![Span::styled(INDENT, Style::default()), Span::styled("Less ", grey)];
+    for level in 0..SHADES.len() {
+        legend.push(cell_span(level, colored));
+        legend.push(Span::styled(gap.clone(), Style::default()));
+    }
+    legend.push(Span::styled(if cell == 1 { " More" } else { "More" }, grey));
+    lines.push(Vec::new());
+    lines.push(legend);
+    lines
+}
+
+//   ▁▃▂▅█▁▂▃
+//   May 4       Oct 19
+fn sparkline_lines(calendar: &Calendar, today: NaiveDate, weeks: usize, colored: bool) -> Vec<Vec<Span>> {
+    let start = first_day(today, weeks);
+    let totals: Vec<u32> = (0..weeks)
+        .map(|week| {
+            let sunday = start + Duration::weeks(week as i64);
+            days(sunday, today).take(7).map(|day| calendar.count(day)).sum()
+        })
+        .collect();
+    let max = totals.iter().copied().max().unwrap_or(0);
+
+    let mut bars = vec![Span::styled(INDENT, Style::default())];
+    for &total in &totals {
+        let bar = SPARKS[(total as usize * (SPARKS.len() - 1)).div_ceil(max.max(1) as usize)];
+        let style = if colored { Style::new().fg(green(level(total, max))) } else { Style::default() };
+        bars.push(Span::styled(bar, style));
+    }
+
+    let from = start.format("%b %-d").to_string();
+    let to = today.format("%b %-d").to_string();
+    let room = weeks.saturating_sub(from.len() + to.len());
+    let dates = match room {
+        0 => to.clone(),
+        room => format!("{}{}{}", from, " ".repeat(room), to),
+    };
+    let dates = vec![Span::styled(INDENT, Style::default()), Span::styled(dates, Style::new().fg(Color::DarkGrey))];
+    vec![bars, dates]
+}
+
+// Draws the calendar over its plain text in colored blocks. Lines with
+// search matches keep the highlights.
+pub struct Heatmap<'a> {
+    pub calendar: &'a Calendar,
+    pub today: NaiveDate,
+    pub width: usize,
+    pub first_line: usize,
+    pub scroll: u16,
+    pub search: &'a Search,
+}
+
+impl Widget for Heatmap<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        for (i, spans) in block(self.calendar, self.today, self.width, true).into_iter().enumerate() {
+            let line = self.first_line + i;
+            let Some(row) = line.checked_sub(self.scroll as usize).filter(|row| *row < area.height as usize) else {
+                continue;
+            };
+            if self.search.matches_on(line).next().is_some() {
+                continue;
+            }
+            let row_area = Rect::new(area.x, area.y + row as u16, area.width, 1);
+            buf.fill(row_area, ' ', Style::default());
+            Text::line(spans).render(row_area, buf);
+        }
+    }
+}
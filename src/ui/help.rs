@@ -0,0 +1,199 @@
+// ui/help.rs
+
+use crossterm::event::KeyEvent;
+use crossterm::style::Color;
+
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::keymap::{
+    lookup_prompt, prompt_hint, Action, KeyBinding, Keymap, PromptAction, BLOG_KEYS, EDIT_KEYS, FINDER_KEYS, FORM_KEYS,
+    HELP_KEYS, PROJECT_KEYS, SEARCH_KEYS, SEARCH_PROMPT_KEYS,
+};
+use crate::ui::layout::{Rect, Size};
+use crate::ui::markup::{display_width, truncate};
+use crate::ui::widget::{Block, Overlay, Padding, Widget};
+
+const GROUPS: [&str; 9] =
+    ["General", "Pages", "Scrolling", "Sections", "Search", "Projects", "Blog", "Guestbook", "Contact"];
+
+// What the mouse does, see `Portfolio::handle_mouse`
+const MOUSE: [(&str, &str); 8] = [
+    ("Click tab", "Open the page"),
+    ("Click project", "Open it"),
+    ("Click post", "Read it"),
+    ("Click tag", "Filter by it"),
+    ("Wheel", "Scroll"),
+    ("Drag scrollbar", "Scroll"),
+    ("Click heading", "Jump to it in the sidebar"),
+    ("Click link", "Copy it to the clipboard"),
+];
+
+// The open help overlay
+#[derive(Debug, Default)]
+pub struct Help {
+    pub scroll: u16,
+}
+
+impl Help {
+    // Keys pressed while the overlay is open, false once they close it
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        match lookup_prompt(HELP_KEYS, key) {
+            Some(PromptAction::Cancel) => return false,
+            Some(PromptAction::Next) => self.scroll = self.scroll.saturating_add(1),
+            Some(PromptAction::Prev) => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+        true
+    }
+
+    // Draws the overlay over `area`. Scrolling is clamped here so scrolling
+    // past the end does not need the line count.
+    pub fn draw(&mut self, keymap: &Keymap, mouse: bool, area: Rect, buf: &mut Buffer) {
+        let lines = help_lines(keymap, mouse);
+        let Some(max_scroll) = max_scroll(&lines, area) else {
+            return;
+        };
+        let scroll = (self.scroll as usize).min(max_scroll);
+        self.scroll = scroll as u16;
+        let shown: &[_] = if max_scroll > 0 {
+            &[(PromptAction::Prev, "scroll"), (PromptAction::Next, "scroll"), (PromptAction::Cancel, "close")]
+        } else {
+            &[(PromptAction::Cancel, "close")]
+        };
+        help_overlay(&lines, scroll, &prompt_hint(HELP_KEYS, shown)).render(area, buf);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HelpLine {
+    Group(&'static str),
+    // keys, description
+    Entry(String, String),
+    Blank,
+}
+
+// Everything the visitor can press, built from the active keymap and the
+// fixed overlay keys so it always matches what the handlers do
+pub fn help_lines(keymap: &Keymap, mouse: bool) -> Vec<HelpLine> {
+    let mut lines = Vec::new();
+    for group in GROUPS {
+        let mut entries: Vec<HelpLine> = Action::ALL
+            .iter()
+            .filter(|action| action.category() == group)
+            .filter_map(|&action| {
+                let keys: Vec<String> = keymap.keys_for(action).map(|key| key.to_string()).collect();
+                (!keys.is_empty()).then(|| HelpLine::Entry(keys.join(" "), action.description()))
+            })
+            .collect();
+        if group == "Search" {
+            entries.extend(prompt_entries(SEARCH_KEYS));
+        }
+        if group == "Projects" {
+            entries.extend(prompt_entries(PROJECT_KEYS));
+        }
+        if group == "Blog" {
+            entries.extend(prompt_entries(BLOG_KEYS));
+        }
+        push_group(&mut lines, group, entries);
+    }
+
+    push_group(&mut lines, "Search prompt", prompt_entries(SEARCH_PROMPT_KEYS));
+    push_group(&mut lines, "Finder", prompt_entries(FINDER_KEYS));
+    push_group(&mut lines, "Forms", prompt_entries(FORM_KEYS));
+    push_group(&mut lines, "Text fields", prompt_entries(EDIT_KEYS));
+    if mouse {
+        let entries = MOUSE
+            .iter()
+            .map(|(what, description)| HelpLine::Entry(what.to_string(), description.to_string()))
+            .collect();
+        push_group(&mut lines, "Mouse", entries);
+    }
+    push_group(&mut lines, "This help", prompt_entries(HELP_KEYS));
+    lines
+}
+
+fn push_group(lines: &mut Vec<HelpLine>, group: &'static str, entries: Vec<HelpLine>) {
+    if entries.is_empty() {
+        return;
+    }
+    if !lines.is_empty() {
+        lines.push(HelpLine::Blank);
+    }
+    lines.push(HelpLine::Group(group));
+    lines.extend(entries);
+}
+
+// One line per description, with every key that does it
+fn prompt_entries<T>(keys: &[(KeyBinding, T, &str)]) -> Vec<HelpLine> {
+    let mut entries: Vec<(Vec<String>, &str)> = Vec::new();
+    for (key, _, description) in keys {
+        match entries.iter_mut().find(|(_, d)| d == description) {
+            Some((keys, _)) => keys.push(key.to_string()),
+            None => entries.push((vec![key.to_string()], description)),
+        }
+    }
+    entries
+        .into_iter()
+        .map(|(keys, description)| HelpLine::Entry(keys.join(" "), description.to_string()))
+        .collect()
+}
+
+// The help overlay scrolled to `scroll`, which `Help::draw` keeps in range
+// with `max_scroll`
+pub fn help_overlay<'a>(lines: &'a [HelpLine], scroll: usize, hint: &str) -> Overlay<Block<Padding<HelpView<'a>>>> {
+    let view = HelpView { lines, scroll };
+    let block = Block::new(Padding::horizontal(view, 1))
+        .title(" Keyboard shortcuts ")
+        .hint(hint);
+    Overlay::new(block, Size::new(64, u16::MAX))
+        .min(Size::new(20, 4))
+        .margin(Size::new(4, 2))
+}
+
+// How far the help can scroll once the overlay is placed in `area`, and
+// whether it needs to at all
+pub fn max_scroll(lines: &[HelpLine], area: Rect) -> Option<usize> {
+    let rect = help_overlay(lines, 0, "").area(area)?;
+    Some(lines.len().saturating_sub(rect.height.saturating_sub(2) as usize))
+}
+
+pub struct HelpView<'a> {
+    lines: &'a [HelpLine],
+    scroll: usize,
+}
+
+impl Widget for HelpView<'_> {
+    fn measure(&self, available: Size) -> Size {
+        Size::new(available.width, (self.lines.len() as u16).min(available.height))
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let inner = area.width as usize + 2;
+        let keys_width = self
+            .lines
+            .iter()
+            .map(|line| match line {
+                HelpLine::Entry(keys, _) => display_width(keys),
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0)
+            .min(inner / 2);
+
+        let rows = self.lines.iter().skip(self.scroll).take(area.height as usize);
+        for (row, line) in rows.enumerate() {
+            let y = area.y + row as u16;
+            match line {
+                HelpLine::Group(name) => {
+                    buf.set_string_clipped(area.x, y, name, Style::new().fg(Color::Cyan).bold(), area.right());
+                }
+                HelpLine::Entry(keys, description) => {
+                    let keys = truncate(keys, keys_width);
+                    buf.set_string(area.x, y, keys, Style::new().fg(Color::Yellow));
+                    let x = area.x + keys_width as u16 + 2;
+                    buf.set_string_clipped(x, y, description, Style::new().fg(Color::White), area.right());
+                }
+                HelpLine::Blank => {}
+            }
+        }
+    }
+}
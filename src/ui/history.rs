@@ -0,0 +1,58 @@
+// ui/history.rs
+
+use std::collections::HashMap;
+
+use crate::ui::handler::Page;
+
+const MAX_HISTORY: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub page: Page,
+    pub scroll: u16,
+}
+
+// Browser-style back/forward stacks, plus the last scroll position of every page
+#[derive(Debug, Default)]
+pub struct History {
+    back: Vec<Location>,
+    forward: Vec<Location>,
+    positions: HashMap<Page, u16>,
+}
+
+impl History {
+    // Records `from` before navigating somewhere new
+    pub fn visit(&mut self, from: Location) {
+        self.remember(from);
+        if self.back.last() != Some(&from) {
+            self.back.push(from);
+        }
+        if self.back.len() > MAX_HISTORY {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    pub fn back(&mut self, current: Location) -> Option<Location> {
+        let target = self.back.pop()?;
+        self.remember(current);
+        self.forward.push(current);
+        Some(target)
+    }
+
+    pub fn forward(&mut self, current: Location) -> Option<Location> {
+        let target = self.forward.pop()?;
+        self.remember(current);
+        self.back.push(current);
+        Some(target)
+    }
+
+    pub fn remember(&mut self, location: Location) {
+        self.positions.insert(location.page, location.scroll);
+    }
+
+    // Where the reader left `page`, the top if it was never opened
+    pub fn position(&self, page: Page) -> u16 {
+        self.positions.get(&page).copied().unwrap_or(0)
+    }
+}
@@ -0,0 +1,484 @@
+// ui/input.rs
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::style::Color;
+
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::keymap::{lookup_prompt, prompt_hint, EditAction, KeyBinding, PromptAction, EDIT_KEYS, FORM_KEYS};
+use crate::ui::layout::{Constraint, Rect, Size};
+use crate::ui::markup::display_width;
+use crate::ui::widget::{Block, Overlay, Span, Stack, Text, Widget};
+
+const CURSOR: Style = Style::new().fg(Color::Black).bg(Color::White);
+
+// Editable text with a cursor. Positions count chars, never bytes, so
+// multi-byte input can not be split in half.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+    max_len: Option<usize>,
+    placeholder: String,
+    multiline: bool,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    // Enter types a new line instead of being left to the form
+    pub fn multiline(mut self) -> Self {
+        self.multiline = true;
+        self
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.multiline
+    }
+
+    pub fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    // Returns false when the input is full
+    pub fn insert(&mut self, c: char) -> bool {
+        if self.max_len.is_some_and(|max| self.len() >= max) {
+            return false;
+        }
+        let at = self.byte(self.cursor);
+        self.value.insert(at, c);
+        self.cursor += 1;
+        true
+    }
+
+    // Pasted text: line breaks become spaces on a single line, other control
+    // characters (escape sequences included) are dropped, the rest is cut at
+    // the maximum length
+    pub fn insert_str(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        for c in text.chars() {
+            let c = match c {
+                '\n' if self.multiline => '\n',
+                '\n' | '\t' => ' ',
+                c if c.is_control() => continue,
+                c => c,
+            };
+            if !self.insert(c) {
+                break;
+            }
+        }
+    }
+
+    // Applies an editing key, returns false when it does not apply here
+    // (e.g. moving up from the first line) so the caller can use it instead
+    pub fn edit(&mut self, action: EditAction) -> bool {
+        let chars: Vec<char> = self.value.chars().collect();
+        let (start, end) = self.line_bounds(&chars);
+        match action {
+            EditAction::Left if self.cursor > 0 => self.cursor -= 1,
+            EditAction::Right if self.cursor < chars.len() => self.cursor += 1,
+            EditAction::WordLeft => self.cursor = word_left(&chars, self.cursor),
+            EditAction::WordRight => self.cursor = word_right(&chars, self.cursor),
+            EditAction::Home => self.cursor = start,
+            EditAction::End => self.cursor = end,
+            EditAction::Backspace if self.cursor > 0 => self.delete(self.cursor - 1, self.cursor),
+            EditAction::Delete if self.cursor < chars.len() => self.delete(self.cursor, self.cursor + 1),
+            EditAction::DeleteWordBack => self.delete(word_left(&chars, self.cursor), self.cursor),
+            EditAction::DeleteWordForward => self.delete(self.cursor, word_right(&chars, self.cursor)),
+            EditAction::DeleteToStart => self.delete(start, self.cursor),
+            EditAction::DeleteToEnd => self.delete(self.cursor, end),
+            EditAction::Up if self.multiline && start > 0 => {
+                let column = self.cursor - start;
+                let above = chars[..start - 1].iter().rposition(|&c| c == '\n').map_or(0, |i| i + 1);
+                self.cursor = (above + column).min(start - 1);
+            }
+            EditAction::Down if self.multiline && end < chars.len() => {
+                let column = self.cursor - start;
+                let below_end = chars[end + 1..].iter().position(|&c| c == '\n').map_or(chars.len(), |i| end + 1 + i);
+                self.cursor = (end + 1 + column).min(below_end);
+            }
+            EditAction::Left | EditAction::Right | EditAction::Backspace | EditAction::Delete => {}
+            EditAction::Up | EditAction::Down => return false,
+        }
+        true
+    }
+
+    // Editing keys and typed text, returns false for keys it leaves alone
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if let Some(action) = lookup_edit(key) {
+            return self.edit(action);
+        }
+        match key.code {
+            KeyCode::Enter if self.multiline => {
+                self.insert('\n');
+                true
+            }
+            KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                self.insert(c);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Start and end of the line the cursor is on
+    fn line_bounds(&self, chars: &[char]) -> (usize, usize) {
+        let start = chars[..self.cursor].iter().rposition(|&c| c == '\n').map_or(0, |i| i + 1);
+        let end = chars[self.cursor..].iter().position(|&c| c == '\n').map_or(chars.len(), |i| self.cursor + i);
+        (start, end)
+    }
+
+    fn delete(&mut self, from: usize, to: usize) {
+        let (from_byte, to_byte) = (self.byte(from), self.byte(to));
+        self.value.replace_range(from_byte..to_byte, "");
+        self.cursor = from;
+    }
+
+    fn byte(&self, index: usize) -> usize {
+        self.value.char_indices().nth(index).map_or(self.value.len(), |(i, _)| i)
+    }
+}
+
+fn lookup_edit(key: &KeyEvent) -> Option<EditAction> {
+    let binding = KeyBinding::from_event(key);
+    EDIT_KEYS.iter().find(|(k, _, _)| *k == binding).map(|(_, action, _)| *action)
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn word_left(chars: &[char], mut at: usize) -> usize {
+    while at > 0 && !is_word(chars[at - 1]) {
+        at -= 1;
+    }
+    while at > 0 && is_word(chars[at - 1]) {
+        at -= 1;
+    }
+    at
+}
+
+fn word_right(chars: &[char], mut at: usize) -> usize {
+    while at < chars.len() && !is_word(chars[at]) {
+        at += 1;
+    }
+    while at < chars.len() && is_word(chars[at]) {
+        at += 1;
+    }
+    at
+}
+
+// A one-line input as spans, for prompts that are part of a longer line
+pub fn input_spans(input: &TextInput, focused: bool, style: Style) -> Vec<Span> {
+    let chars: Vec<char> = input.value.chars().collect();
+    let mut spans = Vec::new();
+    if focused {
+        spans.push(Span::styled(chars[..input.cursor].iter().collect::<String>(), style));
+        match chars.get(input.cursor) {
+            Some(c) => {
+                spans.push(Span::styled(c.to_string(), CURSOR));
+                spans.push(Span::styled(chars[input.cursor + 1..].iter().collect::<String>(), style));
+            }
+            None => spans.push(Span::styled("█", style)),
+        }
+    } else {
+        spans.push(Span::styled(input.value.clone(), style));
+    }
+    if input.is_empty() && !input.placeholder.is_empty() {
+        spans.push(Span::styled(input.placeholder.clone(), Style::new().fg(Color::DarkGrey)));
+    }
+    spans
+}
+
+// A one-line input, scrolled sideways to keep the cursor in view
+pub struct InputLine<'a> {
+    pub input: &'a TextInput,
+    pub focused: bool,
+    pub style: Style,
+}
+
+impl Widget for InputLine<'_> {
+    fn measure(&self, available: Size) -> Size {
+        Size::new(available.width, available.height.min(1))
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let chars: Vec<char> = self.input.value.chars().collect();
+        let width = |cs: &[char]| cs.iter().map(|c| display_width(c.encode_utf8(&mut [0; 4]))).sum::<usize>();
+        let mut first = 0;
+        while first < self.input.cursor && width(&chars[first..self.input.cursor]) + 1 > area.width as usize {
+            first += 1;
+        }
+        let shown = TextInput { value: chars[first..].iter().collect(), cursor: self.input.cursor - first, ..self.input.clone() };
+        Text::line(input_spans(&shown, self.focused, self.style)).render(area, buf);
+    }
+}
+
+// A multi-line input, word wrapped, following the cursor
+pub struct TextArea<'a> {
+    pub input: &'a TextInput,
+    pub focused: bool,
+    pub style: Style,
+}
+
+impl Widget for TextArea<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        buf.fill(area, ' ', self.style);
+        if area.is_empty() {
+            return;
+        }
+        let chars: Vec<char> = self.input.value.chars().collect();
+        let rows = wrap(&chars, area.width.saturating_sub(1) as usize);
+        let cursor_row = rows
+            .iter()
+            .rposition(|&(start, _)| start <= self.input.cursor)
+            .unwrap_or(0);
+        let first = (cursor_row + 1).saturating_sub(area.height as usize);
+
+        if chars.is_empty() && !self.input.placeholder.is_empty() {
+            let x = if self.focused { area.x + 1 } else { area.x };
+            buf.set_string_clipped(x, area.y, &self.input.placeholder, self.style.fg(Color::DarkGrey), area.right());
+        }
+        for (row, &(start, end)) in rows.iter().enumerate().skip(first).take(area.height as usize) {
+            let y = area.y + (row - first) as u16;
+            let text: String = chars[start..end].iter().filter(|&&c| c != '\n').collect();
+            let x = buf.set_string_clipped(area.x, y, &text, self.style, area.right());
+            if self.focused && row == cursor_row {
+                let before: String = chars[start..self.input.cursor].iter().collect();
+                let cursor_x = area.x + display_width(&before) as u16;
+                let symbol = match chars.get(self.input.cursor) {
+                    Some(&c) if c != '\n' => c.to_string(),
+                    _ => " ".to_string(),
+                };
+                buf.set_string_clipped(cursor_x.min(x), y, &symbol, CURSOR, area.right());
+            }
+        }
+    }
+}
+
+// Rows as char ranges, breaking after spaces where possible and always after
+// a new line. A range ending in '\n' includes it.
+fn wrap(chars: &[char], width: usize) -> Vec<(usize, usize)> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut start = 0;
+    let mut used = 0;
+    let mut last_space = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            rows.push((start, i + 1));
+            (start, used, last_space) = (i + 1, 0, None);
+            i += 1;
+            continue;
+        }
+        let w = display_width(c.encode_utf8(&mut [0; 4]));
+        if used + w > width && i > start {
+            let end = last_space.map_or(i, |space| space + 1);
+            rows.push((start, end));
+            (start, used, last_space) = (end, 0, None);
+            i = end;
+            continue;
+        }
+        if c == ' ' {
+            last_space = Some(i);
+        }
+        used += w;
+        i += 1;
+    }
+    rows.push((start, chars.len()));
+    rows
+}
+
+// What a key did to a form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormEvent {
+    Submit,
+    Cancel,
+    Edited,
+}
+
+// Validates a field when the visitor leaves it
+pub type Check = fn(&str) -> Result<(), String>;
+
+#[derive(Debug)]
+pub struct Field {
+    pub label: &'static str,
+    pub input: TextInput,
+    // Set when the visitor leaves the field with a value `check` rejects
+    pub error: Option<String>,
+    pub check: Option<Check>,
+}
+
+impl Field {
+    pub fn new(label: &'static str, input: TextInput) -> Self {
+        Self { label, input, error: None, check: None }
+    }
+
+    pub fn check(mut self, check: Check) -> Self {
+        self.check = Some(check);
+        self
+    }
+}
+
+// Labelled inputs with one of them focused
+#[derive(Debug)]
+pub struct Form {
+    pub fields: Vec<Field>,
+    pub focus: usize,
+    // Why the last attempt to submit was refused
+    pub error: Option<String>,
+}
+
+impl Form {
+    pub fn new(fields: Vec<Field>) -> Self {
+        Self { fields, focus: 0, error: None }
+    }
+
+    pub fn value(&self, index: usize) -> &str {
+        self.fields[index].input.value()
+    }
+
+    pub fn focused(&mut self) -> &mut TextInput {
+        &mut self.fields[self.focus].input
+    }
+
+    pub fn next(&mut self) {
+        self.leave();
+        self.focus = (self.focus + 1) % self.fields.len();
+    }
+
+    pub fn prev(&mut self) {
+        self.leave();
+        self.focus = (self.focus + self.fields.len() - 1) % self.fields.len();
+    }
+
+    // Checks the field losing focus, empty fields are left to the submit
+    fn leave(&mut self) {
+        let field = &mut self.fields[self.focus];
+        field.error = match field.check {
+            Some(check) if !field.input.is_empty() => check(field.input.value()).err(),
+            _ => None,
+        };
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> FormEvent {
+        let last = self.focus + 1 == self.fields.len();
+        match lookup_prompt(FORM_KEYS, key) {
+            Some(PromptAction::Cancel) => return FormEvent::Cancel,
+            Some(PromptAction::Confirm) => return FormEvent::Submit,
+            Some(PromptAction::Next) if key.code == KeyCode::Enter && self.focused().is_multiline() => {
+                self.focused().insert('\n');
+            }
+            Some(PromptAction::Next) if key.code == KeyCode::Enter && last => return FormEvent::Submit,
+            Some(PromptAction::Next) => self.next(),
+            Some(PromptAction::Prev) => self.prev(),
+            None => {
+                if !self.focused().handle_key(key) {
+                    match key.code {
+                        KeyCode::Up => self.prev(),
+                        KeyCode::Down => self.next(),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        FormEvent::Edited
+    }
+
+    fn shown_error(&self) -> Option<&str> {
+        self.error.as_deref().or(self.fields.iter().find_map(|field| field.error.as_deref()))
+    }
+}
+
+// A form in a bordered box, text areas taking the rows left over
+pub fn form_overlay<'a>(form: &'a Form, title: &str, max: Size) -> Overlay<Block<FormView<'a>>> {
+    let rows = form.fields.iter().map(|f| if f.input.multiline { 4 } else { 1 }).sum::<u16>() + 1;
+    let block = Block::new(FormView { form })
+        .title(format!(" {} ", title))
+        .hint(prompt_hint(FORM_KEYS, &[(PromptAction::Next, "next"), (PromptAction::Confirm, "send"), (PromptAction::Cancel, "close")]));
+    Overlay::new(block, max)
+        .min(Size::new(30, rows + 2))
+        .margin(Size::new(4, 2))
+}
+
+pub struct FormView<'a> {
+    pub form: &'a Form,
+}
+
+impl Widget for FormView<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let form = self.form;
+        let label_width = form.fields.iter().map(|f| f.label.len()).max().unwrap_or(0) as u16 + 2;
+        let mut rows = Stack::vertical();
+        for (i, field) in form.fields.iter().enumerate() {
+            rows = rows.push(Constraint::Length(1), FieldLine { field, focused: form.focus == i, label_width });
+            if field.input.multiline {
+                rows = rows.push(Constraint::Min(2), TextArea {
+                    input: &field.input,
+                    focused: form.focus == i,
+                    style: Style::new().fg(Color::White).bg(Color::AnsiValue(235)),
+                });
+            }
+        }
+        let error = format!(" {}", form.shown_error().unwrap_or(""));
+        rows.push(Constraint::Length(1), Text::line(vec![Span::styled(error, Style::new().fg(Color::Red))]))
+            .render(area, buf);
+    }
+}
+
+// Label, the input unless it is a text area, and the count while focused
+struct FieldLine<'a> {
+    field: &'a Field,
+    focused: bool,
+    label_width: u16,
+}
+
+impl Widget for FieldLine<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let field = self.field;
+        let label_style = if self.focused { Style::new().fg(Color::Yellow).bold() } else { Style::new().fg(Color::Cyan) };
+        let label = if field.error.is_some() { format!(" {}!", field.label) } else { format!(" {}", field.label) };
+        buf.set_string_clipped(area.x, area.y, &label, label_style, area.right());
+
+        let count = match field.input.max_len {
+            Some(max) if self.focused => format!(" {}/{}", field.input.len(), max),
+            _ => String::new(),
+        };
+        let count_width = count.len() as u16;
+        buf.set_string_clipped(area.right().saturating_sub(count_width), area.y, &count, Style::new().fg(Color::DarkGrey), area.right());
+        if field.input.multiline {
+            return;
+        }
+
+        let x = (area.x + self.label_width + 1).min(area.right());
+        let input_area = Rect::new(x, area.y, area.right().saturating_sub(x + count_width + 1), 1);
+        let style = if self.focused { Style::new().fg(Color::White) } else { Style::new().fg(Color::Grey) };
+        InputLine { input: &field.input, focused: self.focused, style }.render(input_area, buf);
+    }
+}
@@ -0,0 +1,687 @@
+// ui/keymap.rs
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::config::KeysConfig;
+use crate::ui::handler::Page;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    GoTo(Page),
+    Back,
+    Forward,
+    NextPage,
+    PrevPage,
+    ScrollUp,
+    ScrollDown,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    Top,
+    Bottom,
+    Search,
+    SearchNext,
+    SearchPrev,
+    Finder,
+    ToggleToc,
+    NextSection,
+    PrevSection,
+    Help,
+    WriteGuestbook,
+    NewerEntries,
+    OlderEntries,
+    WriteMessage,
+    NextTag,
+    PrevTag,
+    ToggleTag,
+    ClearTags,
+    Sort,
+    NewerPost,
+    OlderPost,
+    NextResumePage,
+    PrevResumePage,
+}
+
+impl Action {
+    pub const ALL: [Action; 41] = [
+        Action::Quit,
+        Action::GoTo(Page::Home),
+        Action::GoTo(Page::Store),
+        Action::GoTo(Page::About),
+        Action::GoTo(Page::FAQ),
+        Action::GoTo(Page::Guestbook),
+        Action::GoTo(Page::Repos),
+        Action::GoTo(Page::Blog),
+        Action::Back,
+        Action::Forward,
+        Action::NextPage,
+        Action::PrevPage,
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::PageUp,
+        Action::PageDown,
+        Action::HalfPageUp,
+        Action::HalfPageDown,
+        Action::Top,
+        Action::Bottom,
+        Action::Search,
+        Action::SearchNext,
+        Action::SearchPrev,
+        Action::Finder,
+        Action::ToggleToc,
+        Action::NextSection,
+        Action::PrevSection,
+        Action::Help,
+        Action::WriteGuestbook,
+        Action::NewerEntries,
+        Action::OlderEntries,
+        Action::WriteMessage,
+        Action::NextTag,
+        Action::PrevTag,
+        Action::ToggleTag,
+        Action::ClearTags,
+        Action::Sort,
+        Action::NewerPost,
+        Action::OlderPost,
+        Action::NextResumePage,
+        Action::PrevResumePage,
+    ];
+
+    // Name used for the action in the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::GoTo(Page::Home) => "home",
+            Action::GoTo(Page::Store) => "projects",
+            Action::GoTo(Page::About) => "about",
+            Action::GoTo(Page::FAQ) => "contact",
+            Action::GoTo(Page::Guestbook) => "guestbook",
+            Action::GoTo(Page::Repos) => "repos",
+            Action::GoTo(Page::Blog) => "blog",
+            Action::Back => "back",
+            Action::Forward => "forward",
+            Action::NextPage => "next_page",
+            Action::PrevPage => "prev_page",
+            Action::ScrollUp => "scroll_up",
+            Action::ScrollDown => "scroll_down",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::HalfPageUp => "half_page_up",
+            Action::HalfPageDown => "half_page_down",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::Search => "search",
+            Action::SearchNext => "search_next",
+            Action::SearchPrev => "search_prev",
+            Action::Finder => "finder",
+            Action::ToggleToc => "toc",
+            Action::NextSection => "next_section",
+            Action::PrevSection => "prev_section",
+            Action::Help => "help",
+            Action::WriteGuestbook => "guestbook_write",
+            Action::NewerEntries => "guestbook_newer",
+            Action::OlderEntries => "guestbook_older",
+            Action::WriteMessage => "contact_write",
+            Action::NextTag => "projects_next_tag",
+            Action::PrevTag => "projects_prev_tag",
+            Action::ToggleTag => "projects_filter",
+            Action::ClearTags => "projects_clear_filter",
+            Action::Sort => "sort",
+            Action::NewerPost => "blog_newer",
+            Action::OlderPost => "blog_older",
+            Action::NextResumePage => "about_next",
+            Action::PrevResumePage => "about_prev",
+        }
+    }
+
+    // Heading the action is listed under in the help overlay
+    pub fn category(&self) -> &'static str {
+        match self {
+            Action::Quit | Action::Help | Action::Finder => "General",
+            Action::GoTo(_) | Action::Back | Action::Forward | Action::NextPage | Action::PrevPage => "Pages",
+            Action::ScrollUp
+            | Action::ScrollDown
+            | Action::PageUp
+            | Action::PageDown
+            | Action::HalfPageUp
+            | Action::HalfPageDown
+            | Action::Top
+            | Action::Bottom => "Scrolling",
+            Action::ToggleToc | Action::NextSection | Action::PrevSection => "Sections",
+            Action::Search | Action::SearchNext | Action::SearchPrev => "Search",
+            Action::WriteGuestbook | Action::NewerEntries | Action::OlderEntries => "Guestbook",
+            Action::WriteMessage => "Contact",
+            Action::NextTag | Action::PrevTag | Action::ToggleTag | Action::ClearTags | Action::Sort => {
+                "Projects"
+            }
+            Action::NewerPost | Action::OlderPost => "Blog",
+            Action::NextResumePage | Action::PrevResumePage => "About",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        let text = match self {
+            Action::Quit => "Quit",
+            Action::GoTo(page) => return format!("Open {}", page.label()),
+            Action::Back => "Go back",
+            Action::Forward => "Go forward",
+            Action::NextPage => "Next page",
+            Action::PrevPage => "Previous page",
+            Action::ScrollUp => "Scroll up a line",
+            Action::ScrollDown => "Scroll down a line",
+            Action::PageUp => "Scroll up a screen",
+            Action::PageDown => "Scroll down a screen",
+            Action::HalfPageUp => "Scroll up half a screen",
+            Action::HalfPageDown => "Scroll down half a screen",
+            Action::Top => "Jump to the top",
+            Action::Bottom => "Jump to the bottom",
+            Action::Search => "Search this page",
+            Action::SearchNext => "Next match",
+            Action::SearchPrev => "Previous match",
+            Action::Finder => "Find anywhere",
+            Action::ToggleToc => "Toggle the contents sidebar",
+            Action::NextSection => "Next section",
+            Action::PrevSection => "Previous section",
+            Action::Help => "Show this help",
+            Action::WriteGuestbook => "Sign the guestbook",
+            Action::NewerEntries => "Newer guestbook entries",
+            Action::OlderEntries => "Older guestbook entries",
+            Action::WriteMessage => "Send me a message",
+            Action::NextTag => "Next tag",
+            Action::PrevTag => "Previous tag",
+            Action::ToggleTag => "Show only projects with the tag, or all again",
+            Action::ClearTags => "Clear the tag filter",
+            Action::Sort => "Sort the projects or the repositories",
+            Action::NewerPost => "Read the newer post",
+            Action::OlderPost => "Read the older post",
+            Action::NextResumePage => "Next page of the resume",
+            Action::PrevResumePage => "Previous page of the resume",
+        };
+        text.to_string()
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Action::ALL
+            .iter()
+            .find(|action| action.name() == s)
+            .copied()
+            .ok_or_else(|| format!("unknown action `{}`", s))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    pub const fn plain(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    pub const fn ctrl(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    pub const fn alt(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::ALT)
+    }
+
+    // Shift is already encoded in the character itself ('G' vs 'g') and in
+    // BackTab, and terminals disagree on whether they also report it
+    pub fn from_event(event: &KeyEvent) -> Self {
+        let mut modifiers = event.modifiers;
+        if matches!(event.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self::new(event.code, modifiers)
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    // Accepts keys like "q", "G", "ctrl-d", "M-v", "alt-left", "pagedown" or
+    // "space", and everything `Display` writes
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+        while let Some((prefix, key)) = rest.split_once('-') {
+            if key.is_empty() {
+                break;
+            }
+            match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" | "c" => modifiers.insert(KeyModifiers::CONTROL),
+                "alt" | "meta" | "m" => modifiers.insert(KeyModifiers::ALT),
+                "shift" | "s" => modifiers.insert(KeyModifiers::SHIFT),
+                _ => break,
+            }
+            rest = key;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            // The arrows as the help overlay shows them
+            (Some('↑'), None) => KeyCode::Up,
+            (Some('↓'), None) => KeyCode::Down,
+            (Some('←'), None) => KeyCode::Left,
+            (Some('→'), None) => KeyCode::Right,
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "backspace" | "bksp" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                f if f.len() > 1 && f.starts_with('f') => f[1..]
+                    .parse()
+                    .map(KeyCode::F)
+                    .map_err(|_| format!("unknown key `{}`", s))?,
+                _ => return Err(format!("unknown key `{}`", s)),
+            },
+        };
+
+        // "shift-tab" is how most people spell BackTab
+        if code == KeyCode::Tab && modifiers.contains(KeyModifiers::SHIFT) {
+            modifiers.remove(KeyModifiers::SHIFT);
+            return Ok(Self::new(KeyCode::BackTab, modifiers));
+        }
+        if let KeyCode::Char(c) = code {
+            if modifiers.contains(KeyModifiers::SHIFT) {
+                modifiers.remove(KeyModifiers::SHIFT);
+                return Ok(Self::new(KeyCode::Char(c.to_ascii_uppercase()), modifiers));
+            }
+        }
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "C-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "M-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "S-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "S-Tab"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Backspace => write!(f, "Bksp"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+// Keys of the overlays and prompts. They are fixed, typed text goes to the
+// prompt, and the tables feed both the input handlers and the help overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptAction {
+    Confirm,
+    Cancel,
+    Next,
+    Prev,
+}
+
+pub type PromptKeys = [(KeyBinding, PromptAction, &'static str)];
+
+pub const SEARCH_PROMPT_KEYS: &PromptKeys = &[
+    (KeyBinding::plain(KeyCode::Enter), PromptAction::Confirm, "Keep the matches, close the prompt"),
+    (KeyBinding::plain(KeyCode::Esc), PromptAction::Cancel, "Cancel and go back"),
+];
+
+pub const SEARCH_KEYS: &PromptKeys = &[
+    (KeyBinding::plain(KeyCode::Esc), PromptAction::Cancel, "Clear the search"),
+];
+
+pub const FINDER_KEYS: &PromptKeys = &[
+    (KeyBinding::plain(KeyCode::Enter), PromptAction::Confirm, "Open the selected result"),
+    (KeyBinding::plain(KeyCode::Esc), PromptAction::Cancel, "Close the finder"),
+    (KeyBinding::ctrl('g'), PromptAction::Cancel, "Close the finder"),
+    (KeyBinding::plain(KeyCode::Down), PromptAction::Next, "Next result"),
+    (KeyBinding::plain(KeyCode::Tab), PromptAction::Next, "Next result"),
+    (KeyBinding::ctrl('n'), PromptAction::Next, "Next result"),
+    (KeyBinding::ctrl('j'), PromptAction::Next, "Next result"),
+    (KeyBinding::plain(KeyCode::Up), PromptAction::Prev, "Previous result"),
+    (KeyBinding::plain(KeyCode::BackTab), PromptAction::Prev, "Previous result"),
+    (KeyBinding::ctrl('p'), PromptAction::Prev, "Previous result"),
+    (KeyBinding::ctrl('k'), PromptAction::Prev, "Previous result"),
+];
+
+// Enter is a Next that types a new line in a text area, see `input::Form::handle_key`
+// On the Projects page, before the keymap; Esc only while a project is open
+pub const PROJECT_KEYS: &PromptKeys = &[
+    (KeyBinding::plain(KeyCode::Enter), PromptAction::Confirm, "Open the selected project"),
+    (KeyBinding::plain(KeyCode::Esc), PromptAction::Cancel, "Back to all projects"),
+];
+
+pub const BLOG_KEYS: &PromptKeys = &[
+    (KeyBinding::plain(KeyCode::Enter), PromptAction::Confirm, "Read the selected post"),
+    (KeyBinding::plain(KeyCode::Esc), PromptAction::Cancel, "Back to all posts"),
+];
+
+pub const FORM_KEYS: &PromptKeys = &[
+    (KeyBinding::ctrl('s'), PromptAction::Confirm, "Send"),
+    (KeyBinding::plain(KeyCode::Esc), PromptAction::Cancel, "Close the form"),
+    (KeyBinding::plain(KeyCode::Tab), PromptAction::Next, "Next field"),
+    (KeyBinding::plain(KeyCode::Enter), PromptAction::Next, "Next field, send from the last one"),
+    (KeyBinding::plain(KeyCode::BackTab), PromptAction::Prev, "Previous field"),
+];
+
+pub const HELP_KEYS: &PromptKeys = &[
+    (KeyBinding::plain(KeyCode::Esc), PromptAction::Cancel, "Close the help"),
+    (KeyBinding::plain(KeyCode::Char('q')), PromptAction::Cancel, "Close the help"),
+    (KeyBinding::plain(KeyCode::Char('?')), PromptAction::Cancel, "Close the help"),
+    (KeyBinding::plain(KeyCode::Down), PromptAction::Next, "Scroll the help"),
+    (KeyBinding::plain(KeyCode::Char('j')), PromptAction::Next, "Scroll the help"),
+    (KeyBinding::plain(KeyCode::Up), PromptAction::Prev, "Scroll the help"),
+    (KeyBinding::plain(KeyCode::Char('k')), PromptAction::Prev, "Scroll the help"),
+];
+
+// Line editing in every text field: the search prompt, the finder and the forms.
+// The prompt's own keys are looked up first, so e.g. C-k is "previous result"
+// in the finder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditAction {
+    Left,
+    Right,
+    WordLeft,
+    WordRight,
+    Home,
+    End,
+    Up,
+    Down,
+    Backspace,
+    Delete,
+    DeleteWordBack,
+    DeleteWordForward,
+    DeleteToStart,
+    DeleteToEnd,
+}
+
+pub const EDIT_KEYS: &[(KeyBinding, EditAction, &str)] = &[
+    (KeyBinding::plain(KeyCode::Left), EditAction::Left, "Move the cursor"),
+    (KeyBinding::plain(KeyCode::Right), EditAction::Right, "Move the cursor"),
+    (KeyBinding::ctrl('b'), EditAction::Left, "Move the cursor"),
+    (KeyBinding::ctrl('f'), EditAction::Right, "Move the cursor"),
+    (KeyBinding::new(KeyCode::Left, KeyModifiers::CONTROL), EditAction::WordLeft, "Move by words"),
+    (KeyBinding::new(KeyCode::Right, KeyModifiers::CONTROL), EditAction::WordRight, "Move by words"),
+    (KeyBinding::alt(KeyCode::Char('b')), EditAction::WordLeft, "Move by words"),
+    (KeyBinding::alt(KeyCode::Char('f')), EditAction::WordRight, "Move by words"),
+    (KeyBinding::plain(KeyCode::Home), EditAction::Home, "Start / end of the line"),
+    (KeyBinding::plain(KeyCode::End), EditAction::End, "Start / end of the line"),
+    (KeyBinding::ctrl('a'), EditAction::Home, "Start / end of the line"),
+    (KeyBinding::ctrl('e'), EditAction::End, "Start / end of the line"),
+    (KeyBinding::plain(KeyCode::Up), EditAction::Up, "Line up / down, or the field above / below"),
+    (KeyBinding::plain(KeyCode::Down), EditAction::Down, "Line up / down, or the field above / below"),
+    (KeyBinding::plain(KeyCode::Backspace), EditAction::Backspace, "Delete a character"),
+    (KeyBinding::plain(KeyCode::Delete), EditAction::Delete, "Delete a character"),
+    (KeyBinding::ctrl('d'), EditAction::Delete, "Delete a character"),
+    (KeyBinding::ctrl('w'), EditAction::DeleteWordBack, "Delete a word"),
+    (KeyBinding::alt(KeyCode::Backspace), EditAction::DeleteWordBack, "Delete a word"),
+    (KeyBinding::alt(KeyCode::Char('d')), EditAction::DeleteWordForward, "Delete a word"),
+    (KeyBinding::ctrl('u'), EditAction::DeleteToStart, "Delete to the start / end of the line"),
+    (KeyBinding::ctrl('k'), EditAction::DeleteToEnd, "Delete to the start / end of the line"),
+];
+
+pub fn lookup_prompt(keys: &PromptKeys, event: &KeyEvent) -> Option<PromptAction> {
+    let pressed = KeyBinding::from_event(event);
+    keys.iter()
+        .find(|(key, _, _)| *key == pressed)
+        .map(|(_, action, _)| *action)
+}
+
+// A border hint such as " Tab next · Esc close ", naming the first key of
+// each action in `keys` so it can not drift from the handler. Actions shown
+// one after the other with the same label share it, e.g. " ↑↓ select ".
+pub fn prompt_hint(keys: &PromptKeys, shown: &[(PromptAction, &str)]) -> String {
+    let mut parts: Vec<(String, &str)> = Vec::new();
+    for (action, label) in shown {
+        let Some((key, _, _)) = keys.iter().find(|(_, a, _)| a == action) else {
+            continue;
+        };
+        match parts.last_mut() {
+            Some((shared, last)) if last == label => shared.push_str(&key.to_string()),
+            _ => parts.push((key.to_string(), label)),
+        }
+    }
+    let parts: Vec<String> = parts.iter().map(|(key, label)| format!("{} {}", key, label)).collect();
+    format!(" {} ", parts.join(" · "))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    #[default]
+    Default,
+    Vim,
+    Emacs,
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl Keymap {
+    pub fn preset(preset: Preset) -> Self {
+        use KeyCode::*;
+
+        let none = KeyModifiers::NONE;
+        let key = |c: char| KeyBinding::plain(Char(c));
+        let mut bindings = vec![
+            (key('1'), Action::GoTo(Page::Home)),
+            (key('2'), Action::GoTo(Page::Store)),
+            (key('3'), Action::GoTo(Page::About)),
+            (key('4'), Action::GoTo(Page::FAQ)),
+            (key('5'), Action::GoTo(Page::Guestbook)),
+            (key('6'), Action::GoTo(Page::Repos)),
+            (key('7'), Action::GoTo(Page::Blog)),
+            (KeyBinding::ctrl('c'), Action::Quit),
+            (KeyBinding::new(Up, none), Action::ScrollUp),
+            (KeyBinding::new(Down, none), Action::ScrollDown),
+            (KeyBinding::new(PageUp, none), Action::PageUp),
+            (KeyBinding::new(PageDown, none), Action::PageDown),
+            (KeyBinding::new(Home, none), Action::Top),
+            (KeyBinding::new(End, none), Action::Bottom),
+            (KeyBinding::new(Tab, none), Action::NextPage),
+            (KeyBinding::new(BackTab, none), Action::PrevPage),
+            (key('/'), Action::Search),
+            (key('n'), Action::SearchNext),
+            (key('N'), Action::SearchPrev),
+            (key('t'), Action::ToggleToc),
+            (key(']'), Action::NextSection),
+            (key('['), Action::PrevSection),
+            (KeyBinding::plain(Backspace), Action::Back),
+            (KeyBinding::alt(Left), Action::Back),
+            (KeyBinding::alt(Right), Action::Forward),
+            (key('?'), Action::Help),
+            (KeyBinding::plain(F(1)), Action::Help),
+            (key('w'), Action::WriteGuestbook),
+            (key('<'), Action::NewerEntries),
+            (key('>'), Action::OlderEntries),
+            (key('m'), Action::WriteMessage),
+            (key('.'), Action::NextTag),
+            (key(','), Action::PrevTag),
+            (key('f'), Action::ToggleTag),
+            (key('F'), Action::ClearTags),
+            (key('o'), Action::Sort),
+            (key('('), Action::NewerPost),
+            (key(')'), Action::OlderPost),
+            (key('}'), Action::NextResumePage),
+            (key('{'), Action::PrevResumePage),
+        ];
+
+        let preset_bindings = match preset {
+            Preset::Default => vec![
+                (key('q'), Action::Quit),
+                (KeyBinding::plain(Esc), Action::Quit),
+                (key('h'), Action::GoTo(Page::Home)),
+                (key('a'), Action::GoTo(Page::Store)),
+                (key('s'), Action::GoTo(Page::About)),
+                (key('d'), Action::GoTo(Page::FAQ)),
+                (key('g'), Action::GoTo(Page::Guestbook)),
+                (key('r'), Action::GoTo(Page::Repos)),
+                (key('b'), Action::GoTo(Page::Blog)),
+                (KeyBinding::plain(Right), Action::NextPage),
+                (KeyBinding::plain(Left), Action::PrevPage),
+                (key(' '), Action::PageDown),
+                (KeyBinding::ctrl('d'), Action::HalfPageDown),
+                (KeyBinding::ctrl('u'), Action::HalfPageUp),
+                (KeyBinding::ctrl('p'), Action::Finder),
+            ],
+            Preset::Vim => vec![
+                (key('q'), Action::Quit),
+                (key('k'), Action::ScrollUp),
+                (key('j'), Action::ScrollDown),
+                (KeyBinding::ctrl('y'), Action::ScrollUp),
+                (KeyBinding::ctrl('e'), Action::ScrollDown),
+                (KeyBinding::ctrl('b'), Action::PageUp),
+                (KeyBinding::ctrl('f'), Action::PageDown),
+                (KeyBinding::ctrl('u'), Action::HalfPageUp),
+                (KeyBinding::ctrl('d'), Action::HalfPageDown),
+                (key('g'), Action::Top),
+                (key('G'), Action::Bottom),
+                (key('l'), Action::NextPage),
+                (key('h'), Action::PrevPage),
+                (KeyBinding::plain(Right), Action::NextPage),
+                (KeyBinding::plain(Left), Action::PrevPage),
+                (KeyBinding::ctrl('p'), Action::Finder),
+                (KeyBinding::ctrl('o'), Action::Back),
+            ],
+            Preset::Emacs => vec![
+                (KeyBinding::ctrl('g'), Action::Quit),
+                (KeyBinding::ctrl('s'), Action::Search),
+                (key('q'), Action::Quit),
+                (KeyBinding::ctrl('p'), Action::ScrollUp),
+                (KeyBinding::ctrl('n'), Action::ScrollDown),
+                (KeyBinding::alt(Char('v')), Action::PageUp),
+                (KeyBinding::ctrl('v'), Action::PageDown),
+                (KeyBinding::alt(Char('<')), Action::Top),
+                (KeyBinding::alt(Char('>')), Action::Bottom),
+                (KeyBinding::alt(Char('n')), Action::NextPage),
+                (KeyBinding::alt(Char('p')), Action::PrevPage),
+                (KeyBinding::ctrl('f'), Action::NextPage),
+                (KeyBinding::ctrl('b'), Action::PrevPage),
+                (KeyBinding::alt(Char('x')), Action::Finder),
+                (KeyBinding::alt(Char('}')), Action::NextSection),
+                (KeyBinding::alt(Char('{')), Action::PrevSection),
+            ],
+        };
+
+        // Preset keys come first so they are the ones shown in the footer
+        bindings.splice(0..0, preset_bindings);
+        Self { bindings }
+    }
+
+    pub fn from_config(config: &KeysConfig) -> Result<Self, String> {
+        let mut keymap = Self::preset(config.preset);
+        for (name, keys) in &config.bindings {
+            let action: Action = name.parse()?;
+            let keys = keys
+                .keys()
+                .into_iter()
+                .map(str::parse)
+                .collect::<Result<Vec<KeyBinding>, _>>()?;
+            keymap.bindings.retain(|(key, bound)| *bound != action && !keys.contains(key));
+            keymap.bindings.extend(keys.into_iter().map(|key| (key, action)));
+        }
+        Ok(keymap)
+    }
+
+    pub fn lookup(&self, event: &KeyEvent) -> Option<Action> {
+        let pressed = KeyBinding::from_event(event);
+        self.bindings
+            .iter()
+            .find(|(key, _)| *key == pressed)
+            .map(|(_, action)| *action)
+    }
+
+    pub fn keys_for(&self, action: Action) -> impl Iterator<Item = KeyBinding> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(_, bound)| *bound == action)
+            .map(|(key, _)| *key)
+    }
+
+    pub fn first_key(&self, action: Action) -> Option<KeyBinding> {
+        self.keys_for(action).next()
+    }
+
+    // (keys, label) pairs for the footer, built from whatever is bound. The
+    // footer cuts the list to fit, so Quit and Help go first and the page
+    // keys, which grow with every page, after them.
+    pub fn hints(&self) -> Vec<(String, &'static str)> {
+        let mut hints = Vec::new();
+        if let Some(key) = self.first_key(Action::Quit) {
+            hints.push((key.to_string(), "Quit"));
+        }
+        if let Some(key) = self.first_key(Action::Help) {
+            hints.push((key.to_string(), "Help"));
+        }
+        if let (Some(up), Some(down)) = (self.first_key(Action::ScrollUp), self.first_key(Action::ScrollDown)) {
+            hints.push((format!("{}/{}", up, down), "Scroll"));
+        }
+        for page in Page::ALL {
+            if let Some(key) = self.first_key(Action::GoTo(page)) {
+                hints.push((key.to_string(), page.label()));
+            }
+        }
+        if let Some(key) = self.first_key(Action::Finder) {
+            hints.push((key.to_string(), "Find"));
+        }
+        if let (Some(prev), Some(next)) = (self.first_key(Action::PrevPage), self.first_key(Action::NextPage)) {
+            hints.push((format!("{}/{}", prev, next), "Pages"));
+        }
+        hints
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(Preset::Default)
+    }
+}
@@ -0,0 +1,131 @@
+// ui/layout.rs
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Size {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Size {
+    pub const fn new(width: u16, height: u16) -> Self {
+        Self { width, height }
+    }
+}
+
+impl Rect {
+    pub const fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Self { x, y, width, height }
+    }
+
+    pub fn right(&self) -> u16 {
+        self.x.saturating_add(self.width)
+    }
+
+    pub fn bottom(&self) -> u16 {
+        self.y.saturating_add(self.height)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    // Shrinks the rect by the given margins, collapsing to empty if they do not fit
+    pub fn shrink(&self, top: u16, right: u16, bottom: u16, left: u16) -> Rect {
+        let width = self.width.saturating_sub(left + right);
+        let height = self.height.saturating_sub(top + bottom);
+        Rect::new(self.x + left.min(self.width), self.y + top.min(self.height), width, height)
+    }
+
+    // A rect of at most `size`, centered inside this one
+    pub fn centered(&self, size: Size) -> Rect {
+        let width = size.width.min(self.width);
+        let height = size.height.min(self.height);
+        Rect::new(
+            self.x + (self.width - width) / 2,
+            self.y + (self.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
+    pub fn row(&self, offset: u16) -> Rect {
+        Rect::new(self.x, self.y + offset.min(self.height), self.width, u16::from(offset < self.height))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    // Exactly this many cells
+    Length(u16),
+    // At least this many, sharing whatever is left with the other `Min`s
+    Min(u16),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Vertical,
+    Horizontal,
+}
+
+// Splits `area` along `direction`. Fixed lengths are served first, in order,
+// then the remaining space is shared evenly by the `Min` constraints.
+pub fn split(area: Rect, direction: Direction, constraints: &[Constraint]) -> Vec<Rect> {
+    let total = match direction {
+        Direction::Vertical => area.height,
+        Direction::Horizontal => area.width,
+    };
+
+    let mut sizes: Vec<u16> = Vec::with_capacity(constraints.len());
+    let mut left = total;
+    for constraint in constraints {
+        let size = match constraint {
+            Constraint::Length(n) | Constraint::Min(n) => (*n).min(left),
+        };
+        left -= size;
+        sizes.push(size);
+    }
+
+    let flexible = constraints.iter().filter(|c| matches!(c, Constraint::Min(_))).count() as u16;
+    if let Some(share) = left.checked_div(flexible) {
+        let mut extra = left % flexible;
+        for (size, constraint) in sizes.iter_mut().zip(constraints) {
+            if let Constraint::Min(_) = constraint {
+                *size += share + u16::from(extra > 0);
+                extra = extra.saturating_sub(1);
+            }
+        }
+    }
+
+    let mut offset = 0;
+    sizes
+        .into_iter()
+        .map(|size| {
+            let rect = match direction {
+                Direction::Vertical => Rect::new(area.x, area.y + offset, area.width, size),
+                Direction::Horizontal => Rect::new(area.x + offset, area.y, size, area.height),
+            };
+            offset += size;
+            rect
+        })
+        .collect()
+}
+
+pub fn split_vertical(area: Rect, constraints: &[Constraint]) -> Vec<Rect> {
+    split(area, Direction::Vertical, constraints)
+}
+
+pub fn split_horizontal(area: Rect, constraints: &[Constraint]) -> Vec<Rect> {
+    split(area, Direction::Horizontal, constraints)
+}
@@ -0,0 +1,505 @@
+// ui/markdown.rs
+
+use crossterm::style::Color;
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use std::cell::Cell;
+use std::iter::Peekable;
+
+use crate::ui::buffer::Style;
+use crate::ui::markup::{display_width, escape};
+use crate::ui::widget::Span;
+
+// Markdown (CommonMark and the GitHub tables) rendered for the terminal.
+// pulldown-cmark parses it; each line comes out twice: as page markup, which
+// is what search, the finder and the exports see, and as the styled spans
+// drawn over it.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rendered {
+    pub markup: String,
+    pub spans: Vec<Span>,
+}
+
+impl Rendered {
+    fn new(markup: impl Into<String>, spans: Vec<Span>) -> Self {
+        Self { markup: markup.into(), spans }
+    }
+
+    // Markup that is drawn as it is, e.g. art and continued lines
+    fn drawn(spans: Vec<Span>) -> Self {
+        Self { markup: spans.iter().map(|span| span.text.as_str()).collect(), spans }
+    }
+
+    // Wrapped prose, escaped where a line happens to start like page markup,
+    // e.g. "# 1 rule" or "- 5 degrees", so it stays text
+    fn prose(spans: Vec<Span>) -> Self {
+        let text: String = spans.iter().map(|span| span.text.as_str()).collect();
+        Self { markup: escape(&text), spans }
+    }
+
+    fn blank() -> Self {
+        Self::new("", Vec::new())
+    }
+}
+
+const TEXT: Style = Style::new().fg(Color::White);
+const GREY: Style = Style::new().fg(Color::DarkGrey);
+const CODE: Style = Style::new().fg(Color::Yellow);
+const LINK: Style = Style::new().fg(Color::Blue).underline();
+
+// The blocks of a post, with their text already styled
+#[derive(Debug, Clone)]
+enum Block {
+    // "\n" is a hard line break
+    Paragraph(Vec<Span>),
+    Heading(usize, String),
+    Code(String, Vec<String>),
+    Rule,
+    Quote(Vec<Block>),
+    // Numbered from `start` when set, each item its own blocks
+    List(Option<u64>, Vec<Vec<Block>>),
+    // The header row first, each cell its spans
+    Table(Vec<Vec<Vec<Span>>>),
+}
+
+type Events<'a> = Peekable<Parser<'a>>;
+
+// `markdown` as lines no wider than `width` where it can be wrapped
+pub fn render(markdown: &str, width: usize) -> Vec<Rendered> {
+    let mut out = Vec::new();
+    render_blocks(&mut out, &parse(markdown), width);
+    out
+}
+
+// The page markup of rendered lines
+pub fn markup(lines: &[Rendered]) -> String {
+    lines.iter().map(|line| format!("{}\n", line.markup)).collect()
+}
+
+// The text without any Markdown, e.g. for a summary
+pub fn plain(text: &str) -> String {
+    let mut words = Vec::new();
+    for block in parse(text) {
+        match block {
+            Block::Paragraph(spans) => words.push(spans.iter().map(|span| span.text.as_str()).collect()),
+            Block::Heading(_, text) => words.push(text),
+            _ => {}
+        }
+    }
+    words.join(" ")
+}
+
+fn parse(markdown: &str) -> Vec<Block> {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut events = Parser::new_ext(markdown, options).peekable();
+    blocks(&mut events)
+}
+
+// Blocks up to the end of the container they are in, which is consumed
+fn blocks(events: &mut Events) -> Vec<Block> {
+    let mut out = Vec::new();
+    while let Some(event) = events.peek() {
+        // Tight list items hold their text without a paragraph around it
+        if is_inline(event) {
+            out.push(Block::Paragraph(inline(events)));
+            continue;
+        }
+        let Some(event) = events.next() else { break };
+        match event {
+            Event::End(_) => break,
+            Event::Rule => out.push(Block::Rule),
+            Event::Start(Tag::Paragraph) => out.push(Block::Paragraph(inline_until_end(events))),
+            Event::Start(Tag::Heading { level, .. }) => {
+                let spans = inline_until_end(events);
+                out.push(Block::Heading(level as usize, spans.iter().map(|span| span.text.as_str()).collect()));
+            }
+            Event::Start(Tag::BlockQuote(_)) => out.push(Block::Quote(blocks(events))),
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let info = match kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or("").to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                let mut code = String::new();
+                for event in events.by_ref() {
+                    match event {
+                        Event::Text(text) => code.push_str(&text),
+                        _ => break,
+                    }
+                }
+                out.push(Block::Code(info, code.lines().map(|line| line.replace('\t', "    ")).collect()));
+            }
+            Event::Start(Tag::List(start)) => {
+                let mut items = Vec::new();
+                while let Some(Event::Start(Tag::Item)) = events.next() {
+                    items.push(blocks(events));
+                }
+                out.push(Block::List(start, items));
+            }
+            Event::Start(Tag::Table(_)) => {
+                let mut rows = Vec::new();
+                while let Some(Event::Start(Tag::TableHead | Tag::TableRow)) = events.next() {
+                    let mut row = Vec::new();
+                    while let Some(Event::Start(Tag::TableCell)) = events.next() {
+                        row.push(inline_until_end(events));
+                    }
+                    rows.push(row);
+                }
+                out.push(Block::Table(rows));
+            }
+            // HTML, comments included, means nothing on a terminal
+            Event::Start(_) => skip(events),
+            _ => {}
+        }
+    }
+    out
+}
+
+// Everything up to the end of the container just started
+fn skip(events: &mut Events) {
+    let mut depth = 1;
+    for event in events.by_ref() {
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) if depth == 1 => return,
+            Event::End(_) => depth -= 1,
+            _ => {}
+        }
+    }
+}
+
+fn is_inline(event: &Event) -> bool {
+    match event {
+        Event::Start(tag) => matches!(
+            tag,
+            Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link { .. } | Tag::Image { .. } | Tag::Superscript | Tag::Subscript
+        ),
+        Event::End(tag) => matches!(
+            tag,
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link | TagEnd::Image | TagEnd::Superscript | TagEnd::Subscript
+        ),
+        Event::Text(_) | Event::Code(_) | Event::InlineHtml(_) | Event::InlineMath(_) => true,
+        Event::SoftBreak | Event::HardBreak | Event::TaskListMarker(_) | Event::FootnoteReference(_) => true,
+        _ => false,
+    }
+}
+
+// The text of a paragraph, heading or cell, and the end of it
+fn inline_until_end(events: &mut Events) -> Vec<Span> {
+    let spans = inline(events);
+    events.next();
+    spans
+}
+
+// Emphasis, code, links and images up to the next block
+fn inline(events: &mut Events) -> Vec<Span> {
+    let mut text = Inline::default();
+    while let Some(event) = events.next_if(is_inline) {
+        match event {
+            Event::Text(t) | Event::InlineMath(t) => text.push(&t, text.style()),
+            Event::InlineHtml(html) => text.push(&html, text.style()),
+            Event::Code(code) => text.push(&code, CODE),
+            Event::SoftBreak => text.push(" ", text.style()),
+            Event::HardBreak => text.push("\n", text.style()),
+            Event::TaskListMarker(done) => text.push(if done { "☑ " } else { "☐ " }, text.style()),
+            Event::FootnoteReference(name) => text.push(&format!("[{}]", name), GREY),
+            Event::Start(Tag::Emphasis) => text.italic += 1,
+            Event::End(TagEnd::Emphasis) => text.italic -= 1,
+            Event::Start(Tag::Strong) => text.bold += 1,
+            Event::End(TagEnd::Strong) => text.bold -= 1,
+            Event::Start(Tag::Strikethrough) => text.strike += 1,
+            Event::End(TagEnd::Strikethrough) => text.strike -= 1,
+            Event::Start(Tag::Link { dest_url, .. }) => text.open_link(&dest_url, false),
+            Event::Start(Tag::Image { dest_url, .. }) => text.open_link(&dest_url, true),
+            Event::End(TagEnd::Link | TagEnd::Image) => text.close_link(),
+            _ => {}
+        }
+    }
+    text.spans
+}
+
+#[derive(Default)]
+struct Inline {
+    spans: Vec<Span>,
+    bold: usize,
+    italic: usize,
+    strike: usize,
+    // Open links and images: the url, where their text starts, and whether it is an image
+    links: Vec<(String, usize, bool)>,
+    // Spans before this one are not merged into, a link's text starts after them
+    fresh: usize,
+}
+
+impl Inline {
+    fn style(&self) -> Style {
+        let mut style = if self.links.is_empty() { TEXT } else { LINK };
+        if self.bold > 0 {
+            style = style.bold();
+        }
+        if self.italic > 0 {
+            style = style.italic();
+        }
+        if self.strike > 0 {
+            style = style.dim();
+        }
+        style
+    }
+
+    fn push(&mut self, text: &str, style: Style) {
+        let mergeable = self.spans.len() > self.fresh;
+        match self.spans.last_mut() {
+            Some(last) if mergeable && last.style == style => last.text.push_str(text),
+            _ => self.spans.push(Span::styled(text, style)),
+        }
+    }
+
+    // A link keeps its text and shows where it goes unless the text says it
+    // already; an image is its alt text
+    fn close_link(&mut self) {
+        let Some((url, start, image)) = self.links.pop() else {
+            return;
+        };
+        let label: String = self.spans[start..].iter().map(|span| span.text.as_str()).collect();
+        self.fresh = self.spans.len();
+        let web = url.starts_with("http");
+        if image {
+            self.spans.truncate(start);
+            self.push(&format!("[image: {}]", label), Style::new().fg(Color::Magenta));
+            if web {
+                self.push(" ", self.style());
+                self.push(&url, LINK);
+            }
+        } else if web && label != url {
+            self.push(" (", GREY);
+            self.push(&url, GREY);
+            self.push(")", GREY);
+        }
+    }
+
+    fn open_link(&mut self, url: &str, image: bool) {
+        self.fresh = self.spans.len();
+        self.links.push((url.to_string(), self.spans.len(), image));
+    }
+}
+
+// One blank line between blocks
+fn render_blocks(out: &mut Vec<Rendered>, blocks: &[Block], width: usize) {
+    for block in blocks {
+        if out.last().is_some_and(|last| !last.markup.is_empty()) {
+            out.push(Rendered::blank());
+        }
+        match block {
+            Block::Paragraph(spans) => out.extend(wrap(spans, width).into_iter().map(Rendered::prose)),
+            Block::Heading(level, text) => out.push(heading(*level, text)),
+            Block::Code(info, code) => code_block(out, info, code),
+            Block::Rule => out.push(Rendered::drawn(vec![Span::styled("─".repeat(width), GREY)])),
+            Block::Quote(inner) => {
+                let mut quoted = Vec::new();
+                render_blocks(&mut quoted, inner, width.saturating_sub(2));
+                for inner in quoted {
+                    let mut spans = vec![Span::styled(if inner.spans.is_empty() { "│" } else { "│ " }, GREY)];
+                    spans.extend(inner.spans);
+                    out.push(Rendered::drawn(spans));
+                }
+            }
+            Block::List(start, items) => list(out, *start, items, 0, width),
+            Block::Table(rows) => table(out, rows),
+        }
+    }
+}
+
+// The code indented so the page markup takes it as art and leaves it alone
+fn code_block(out: &mut Vec<Rendered>, info: &str, code: &[String]) {
+    let fence = Style::new().fg(Color::Magenta).dim();
+    out.push(Rendered::drawn(vec![Span::styled(format!("```{}", info), fence)]));
+    for line in code {
+        out.push(Rendered::drawn(vec![Span::styled(format!("  {}", line.trim_end()), CODE)]));
+    }
+    out.push(Rendered::drawn(vec![Span::styled("```", fence)]));
+}
+
+// The post's title is the page's, so its headings are sections: "#" and
+// "##" both become "## ", deeper ones "### "
+fn heading(level: usize, text: &str) -> Rendered {
+    match level {
+        1 | 2 => Rendered::new(format!("## {}", text), vec![Span::styled(text, Style::new().fg(Color::Green).bold())]),
+        _ => Rendered::new(format!("### {}", text), vec![Span::styled(text, Style::new().fg(Color::Green))]),
+    }
+}
+
+// The items one after the other, nested lists indented under theirs. The
+// paragraphs of an item are its lines, other blocks hang under the marker.
+fn list(out: &mut Vec<Rendered>, start: Option<u64>, items: &[Vec<Block>], level: usize, width: usize) {
+    let bullet = Style::new().fg(Color::Yellow);
+    for (n, item) in items.iter().enumerate() {
+        let prefix = match (start, level) {
+            (Some(start), _) => format!("{}{}. ", "  ".repeat(level), start + n as u64),
+            (None, 0) => "● ".to_string(),
+            (None, _) => format!("{}◦ ", "  ".repeat(level)),
+        };
+        let hanging = " ".repeat(display_width(&prefix));
+        let inner_width = width.saturating_sub(hanging.len());
+        let lead = Cell::new(Some(prefix));
+        // The first line under the marker, every other one under the hanging indent
+        let mut emit = |out: &mut Vec<Rendered>, spans: Vec<Span>| {
+            let first = lead.take();
+            let mut line = vec![Span::styled(first.clone().unwrap_or_else(|| hanging.clone()), bullet)];
+            line.extend(spans);
+            let mut rendered = Rendered::drawn(line);
+            // A top-level bullet is the page markup's own "- "
+            if first.is_some() && start.is_none() && level == 0 {
+                rendered.markup = format!("- {}", &rendered.markup["● ".len()..]);
+            }
+            out.push(rendered);
+        };
+
+        let mut text: Vec<Span> = Vec::new();
+        let flush = |out: &mut Vec<Rendered>, text: &mut Vec<Span>, emit: &mut dyn FnMut(&mut Vec<Rendered>, Vec<Span>)| {
+            if !text.is_empty() {
+                wrap(text, inner_width).into_iter().for_each(|spans| emit(out, spans));
+                text.clear();
+            }
+        };
+        for block in item {
+            match block {
+                Block::Paragraph(spans) => {
+                    if !text.is_empty() {
+                        text.push(Span::styled("\n", TEXT));
+                    }
+                    text.extend(spans.iter().cloned());
+                }
+                Block::List(start, items) => {
+                    flush(out, &mut text, &mut emit);
+                    list(out, *start, items, level + 1, width);
+                }
+                block => {
+                    flush(out, &mut text, &mut emit);
+                    let mut inner = Vec::new();
+                    render_blocks(&mut inner, std::slice::from_ref(block), inner_width);
+                    inner.into_iter().for_each(|line| emit(out, line.spans));
+                }
+            }
+        }
+        flush(out, &mut text, &mut emit);
+        // An empty item still shows its marker
+        let unused = lead.take();
+        if unused.is_some() {
+            lead.set(unused);
+            emit(out, Vec::new());
+        }
+    }
+}
+
+//   Markdown    │ On screen
+//   ────────────┼──────────
+//   `# Heading` │ a section
+fn table(out: &mut Vec<Rendered>, rows: &[Vec<Vec<Span>>]) {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let empty: Vec<Span> = Vec::new();
+    let cell = |row: &[Vec<Span>], c: usize| row.get(c).unwrap_or(&empty).clone();
+    let rows: Vec<Vec<Vec<Span>>> = rows.iter().map(|row| (0..columns).map(|c| cell(row, c)).collect()).collect();
+    let width = |cell: &[Span]| cell.iter().map(Span::width).sum::<usize>();
+    let widths: Vec<usize> = (0..columns).map(|c| rows.iter().map(|row| width(&row[c])).max().unwrap_or(0)).collect();
+
+    for (r, row) in rows.iter().enumerate() {
+        let mut line = vec![Span::styled("  ", Style::default())];
+        for (c, cell) in row.iter().enumerate() {
+            if c > 0 {
+                line.push(Span::styled(" │ ", GREY));
+            }
+            for span in cell {
+                let style = if r == 0 { span.style.bold() } else { span.style };
+                line.push(Span::styled(span.text.clone(), style));
+            }
+            if c + 1 < columns {
+                line.push(Span::styled(" ".repeat(widths[c] - width(cell)), Style::default()));
+            }
+        }
+        out.push(Rendered::drawn(line));
+        if r == 0 {
+            let rule: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+            out.push(Rendered::drawn(vec![Span::styled("  ", Style::default()), Span::styled(rule.join("─┼─"), GREY)]));
+        }
+    }
+}
+
+// Styled text cut at spaces into lines of at most `width` columns, words
+// longer than that cut where they must be. "\n" is a line break.
+fn wrap(spans: &[Span], width: usize) -> Vec<Vec<Span>> {
+    let width = width.max(1);
+    let chars: Vec<(char, Style)> = spans.iter().flat_map(|span| span.text.chars().map(move |c| (c, span.style))).collect();
+    let mut lines: Vec<Vec<(char, Style)>> = vec![Vec::new()];
+    let mut used = 0;
+    for (before, word) in words(&chars) {
+        let word_width: usize = word.iter().map(|(c, _)| display_width(c.encode_utf8(&mut [0; 4]))).sum();
+        let line = lines.last_mut().expect("there is always a line");
+        match before {
+            Break::Line => {
+                lines.push(Vec::new());
+                used = 0;
+            }
+            _ if !line.is_empty() && used + 1 + word_width > width => {
+                lines.push(Vec::new());
+                used = 0;
+            }
+            Break::Space(style) if !line.is_empty() => {
+                line.push((' ', style));
+                used += 1;
+            }
+            _ => {}
+        }
+        for &(c, style) in word {
+            let c_width = display_width(c.encode_utf8(&mut [0; 4]));
+            if used + c_width > width && used > 0 {
+                lines.push(Vec::new());
+                used = 0;
+            }
+            lines.last_mut().expect("there is always a line").push((c, style));
+            used += c_width;
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|line| {
+            let mut spans: Vec<Span> = Vec::new();
+            for (c, style) in line {
+                match spans.last_mut() {
+                    Some(last) if last.style == style => last.text.push(c),
+                    _ => spans.push(Span::styled(c.to_string(), style)),
+                }
+            }
+            spans
+        })
+        .collect()
+}
+
+// The words of `chars`, each after a line break or the style of the
+// space before it, none for the first
+fn words(chars: &[(char, Style)]) -> Vec<(Break, &[(char, Style)])> {
+    let mut words = Vec::new();
+    let (mut start, mut before) = (0, Break::None);
+    for (i, &(c, style)) in chars.iter().enumerate() {
+        if c == ' ' || c == '\n' {
+            if start < i {
+                words.push((before, &chars[start..i]));
+                before = Break::None;
+            }
+            before = match (c, before) {
+                ('\n', _) | (_, Break::Line) => Break::Line,
+                _ => Break::Space(style),
+            };
+            start = i + 1;
+        }
+    }
+    if start < chars.len() {
+        words.push((before, &chars[start..]));
+    }
+    words
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Break {
+    None,
+    Space(Style),
+    Line,
+}
@@ -0,0 +1,234 @@
+// ui/markup.rs
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+// The page content uses a tiny markdown-like markup, one construct per line.
+// A leading backslash makes a line plain text.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    Title,      // "# "
+    Heading,    // "## "
+    Subheading, // "### "
+    Bullet,     // "- "
+    Fence,      // "```"
+    Text,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub kind: LineKind,
+    // What gets drawn, after the marker
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn parse(line: &'a str) -> Self {
+        let (kind, text) = if let Some(text) = line.strip_prefix('\\') {
+            (LineKind::Text, text)
+        } else if let Some(text) = line.strip_prefix("# ") {
+            (LineKind::Title, text)
+        } else if let Some(text) = line.strip_prefix("## ") {
+            (LineKind::Heading, text)
+        } else if let Some(text) = line.strip_prefix("### ") {
+            (LineKind::Subheading, text)
+        } else if let Some(text) = line.strip_prefix("- ") {
+            (LineKind::Bullet, text)
+        } else if line.starts_with("```") {
+            (LineKind::Fence, line)
+        } else {
+            (LineKind::Text, line)
+        };
+        Self { kind, text }
+    }
+
+    // Drawn in front of the text, e.g. the bullet glyph
+    pub fn marker(&self) -> &'static str {
+        match self.kind {
+            LineKind::Bullet => "● ",
+            LineKind::Subheading => "### ",
+            _ => "",
+        }
+    }
+
+    // The heading level, for "# " to "### "
+    pub fn level(&self) -> Option<u8> {
+        match self.kind {
+            LineKind::Title => Some(1),
+            LineKind::Heading => Some(2),
+            LineKind::Subheading => Some(3),
+            _ => None,
+        }
+    }
+}
+
+// Indented lines and box drawing only line up in a monospace font, escaped
+// lines are text whatever they hold
+pub fn is_art(line: &str) -> bool {
+    if line.starts_with('\\') {
+        return false;
+    }
+    line.starts_with("  ") || line.chars().any(|c| ('\u{2500}'..='\u{259f}').contains(&c))
+}
+
+// A run of page markup that reads as one unit, as the exports and the web
+// pages see it. Blank lines end paragraphs, lists and art.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block<'a> {
+    // Level and text of a "# " to "### " line
+    Heading(u8, &'a str),
+    // Hard wrapped lines, trimmed
+    Paragraph(Vec<&'a str>),
+    // The text of each bullet
+    List(Vec<&'a str>),
+    // The language after the opening fence, and the lines up to the closing one
+    Code(Option<&'a str>, Vec<&'a str>),
+    // Banners and box drawings, trimmed at the end
+    Art(Vec<&'a str>),
+}
+
+pub fn blocks(content: &str) -> Vec<Block<'_>> {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut lines = content.lines();
+    // Whether the last block may take the next line
+    let mut open = false;
+    while let Some(raw) = lines.next() {
+        let line = Line::parse(raw);
+        if let Some(level) = line.level() {
+            blocks.push(Block::Heading(level, line.text.trim()));
+            open = false;
+            continue;
+        }
+        match line.kind {
+            LineKind::Fence => {
+                let language = raw.trim_start_matches('`').trim();
+                let code = lines.by_ref().take_while(|raw| !raw.starts_with("```")).collect();
+                blocks.push(Block::Code((!language.is_empty()).then_some(language), code));
+                open = false;
+            }
+            LineKind::Bullet => match blocks.last_mut() {
+                Some(Block::List(items)) if open => items.push(line.text.trim()),
+                _ => {
+                    blocks.push(Block::List(vec![line.text.trim()]));
+                    open = true;
+                }
+            },
+            _ if raw.trim().is_empty() => open = false,
+            _ if is_art(raw) => match blocks.last_mut() {
+                Some(Block::Art(art)) if open => art.push(raw.trim_end()),
+                _ => {
+                    blocks.push(Block::Art(vec![raw.trim_end()]));
+                    open = true;
+                }
+            },
+            _ => match blocks.last_mut() {
+                Some(Block::Paragraph(paragraph)) if open => paragraph.push(line.text.trim()),
+                _ => {
+                    blocks.push(Block::Paragraph(vec![line.text.trim()]));
+                    open = true;
+                }
+            },
+        }
+    }
+    blocks
+}
+
+// Text for HTML, in element content and quoted attributes
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// Text from visitors or a renderer as one line of markup that shows as
+// typed: a backslash in front keeps it from starting a heading, bullet,
+// fence or art block
+pub fn escape(text: &str) -> String {
+    let markup = Line::parse(text).kind != LineKind::Text || text.starts_with('\\') || text.starts_with("  ");
+    match markup {
+        true => format!("\\{}", text),
+        false => text.to_string(),
+    }
+}
+
+// Slices by char index, the unit used for search match ranges
+pub fn char_slice(text: &str, start: usize, end: usize) -> &str {
+    let byte = |n: usize| text.char_indices().nth(n).map_or(text.len(), |(i, _)| i);
+    &text[byte(start)..byte(end)]
+}
+
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+// Longest prefix of `text` that fits in `width` terminal columns
+pub fn truncate(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, c) in text.char_indices() {
+        used += UnicodeWidthChar::width(c).unwrap_or(0);
+        if used > width {
+            return &text[..i];
+        }
+    }
+    text
+}
+
+// Char ranges of the http(s) URLs in `text`
+pub fn links(text: &str) -> Vec<(usize, usize)> {
+    let mut links = Vec::new();
+    let mut search_from = 0;
+    while let Some(found) = ["https://", "http://"]
+        .iter()
+        .filter_map(|scheme| text[search_from..].find(scheme))
+        .min()
+    {
+        let start = search_from + found;
+        let end = text[start..]
+            .find(|c: char| c.is_whitespace() || c == ')' || c == '"')
+            .map_or(text.len(), |i| start + i);
+        links.push((text[..start].chars().count(), text[..end].chars().count()));
+        search_from = end;
+    }
+    links
+}
+
+// Index of the char drawn at display column `column`, if any
+pub fn char_at_column(text: &str, column: usize) -> Option<usize> {
+    let mut used = 0;
+    for (i, c) in text.chars().enumerate() {
+        used += UnicodeWidthChar::width(c).unwrap_or(0);
+        if used > column {
+            return Some(i);
+        }
+    }
+    None
+}
+
+// Tags as "[Rust] [Docker]" rows no wider than `width` columns
+pub fn chips<S: AsRef<str>>(tags: &[S], width: usize) -> Vec<String> {
+    let mut rows: Vec<String> = Vec::new();
+    let mut row = String::new();
+    for tag in tags {
+        let chip = format!("[{}]", tag.as_ref().trim());
+        if !row.is_empty() && display_width(&row) + 1 + display_width(&chip) > width {
+            rows.push(std::mem::take(&mut row));
+        }
+        if !row.is_empty() {
+            row.push(' ');
+        }
+        row.push_str(&chip);
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+    rows
+}
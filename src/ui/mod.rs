@@ -1,3 +1,25 @@
+pub mod blog;
+pub mod buffer;
+pub mod contact;
+pub mod content;
 pub mod header;
+pub mod heatmap;
 pub mod handler;
-
+pub mod finder;
+pub mod footer;
+pub mod guestbook;
+pub mod help;
+pub mod history;
+pub mod input;
+pub mod keymap;
+pub mod layout;
+pub mod markdown;
+pub mod markup;
+pub mod projects;
+pub mod repos;
+pub mod resume;
+pub mod search;
+pub mod terminal;
+pub mod toc;
+pub mod view;
+pub mod widget;
@@ -0,0 +1,706 @@
+// ui/projects.rs
+
+use crossterm::event::KeyEvent;
+use crossterm::style::Color;
+use log::info;
+
+use crate::projects::{self, Project, Status};
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::handler::PageContent;
+use crate::ui::keymap::{lookup_prompt, Action, Keymap, PromptAction, PROJECT_KEYS};
+use crate::ui::layout::Rect;
+use crate::ui::markup::{chips, display_width, truncate, Line, LineKind};
+use crate::ui::search::Search;
+use crate::ui::view::{Context, Handled, View};
+use crate::ui::widget::{Span, Text, Widget};
+
+// Box lines of a card, then a blank line before the next one
+pub const CARD_HEIGHT: usize = 5;
+// Cards as wide as the text of the other pages, narrower terminals get narrower ones
+pub const CARD_WIDTH: usize = 64;
+const MIN_CARD_WIDTH: usize = 24;
+const WRAP_WIDTH: usize = 64;
+
+// Order of the cards, `Featured` is the order of the files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Sort {
+    #[default]
+    Featured,
+    Newest,
+    Name,
+    Status,
+}
+
+impl Sort {
+    pub const ALL: [Sort; 4] = [Sort::Featured, Sort::Newest, Sort::Name, Sort::Status];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Sort::Featured => "featured",
+            Sort::Newest => "newest",
+            Sort::Name => "name",
+            Sort::Status => "status",
+        }
+    }
+
+    pub fn next(&self) -> Sort {
+        let i = Self::ALL.iter().position(|sort| sort == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+// Where the visitor is in the catalog during a session
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalog {
+    // Position of the selected card among the visible ones
+    pub selected: usize,
+    // Index in the catalog of the project shown in the detail view
+    pub open: Option<usize>,
+    // Where the list was scrolled to when the project was opened
+    pub list_scroll: u16,
+    // Only projects with every one of these tags are shown
+    pub filter: Vec<String>,
+    // Index in `all_tags` of the tag the tag keys act on
+    pub tag_cursor: usize,
+    pub sort: Sort,
+}
+
+impl Catalog {
+    pub fn is_filtered(&self, tag: &str) -> bool {
+        self.filter.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    // Adds the tag to the filter, or takes it out again
+    pub fn toggle(&mut self, tag: &str) {
+        match self.filter.iter().position(|t| t.eq_ignore_ascii_case(tag)) {
+            Some(i) => {
+                self.filter.remove(i);
+            }
+            None => self.filter.push(tag.to_string()),
+        }
+        self.selected = 0;
+    }
+
+    // "Rust + Docker · newest first", empty when all projects show in the usual order
+    pub fn state(&self) -> Vec<String> {
+        let mut parts = Vec::new();
+        if !self.filter.is_empty() {
+            parts.push(self.filter.join(" + "));
+        }
+        match self.sort {
+            Sort::Featured => {}
+            Sort::Newest => parts.push("newest first".to_string()),
+            sort => parts.push(format!("by {}", sort.label())),
+        }
+        parts
+    }
+
+    // Catalog indices of the cards that pass the filter, in order
+    pub fn shown(&self) -> Vec<usize> {
+        visible(projects::catalog(), &self.filter, self.sort)
+    }
+
+    // The cards with the keys of `keymap` above them, for a content area `width` columns wide
+    pub fn listing(&self, keymap: &Keymap, width: u16) -> Listing {
+        let key = |action| keymap.first_key(action).map(|k| k.to_string()).unwrap_or_default();
+        let open = PROJECT_KEYS.iter().find(|(_, action, _)| *action == PromptAction::Confirm);
+        let hints = vec![
+            format!(
+                "{}{} choose a project · {} opens it",
+                key(Action::ScrollUp),
+                key(Action::ScrollDown),
+                open.map(|(key, _, _)| key.to_string()).unwrap_or_default()
+            ),
+            format!(
+                "{}/{} pick a tag · {} filter by it · {} clear · {} sort",
+                key(Action::PrevTag),
+                key(Action::NextTag),
+                key(Action::ToggleTag),
+                key(Action::ClearTags),
+                key(Action::Sort)
+            ),
+        ];
+        let catalog = projects::catalog();
+        let tags = all_tags(catalog);
+        let controls = Controls { hints, tags: &tags, total: catalog.len() };
+        let cards: Vec<_> = self.shown().into_iter().map(|i| &catalog[i]).collect();
+        list(&PageContent::projects_intro(), &cards, Some(&controls), card_width(width as usize))
+    }
+
+    fn open_project(&mut self, index: usize, ctx: &mut Context) -> Handled {
+        let Some(project) = projects::catalog().get(index) else {
+            return Handled::Yes;
+        };
+        info!(project = project.name.as_str(); "project opened");
+        if self.open.is_none() {
+            self.list_scroll = ctx.scroll;
+        }
+        if let Some(position) = self.shown().iter().position(|&i| i == index) {
+            self.selected = position;
+        }
+        self.open = Some(index);
+        ctx.scroll = 0;
+        Handled::Replaced
+    }
+
+    fn close(&mut self, ctx: &mut Context) {
+        self.open = None;
+        let text = self.text(ctx);
+        ctx.scroll_to(self.list_scroll, &text);
+        self.reveal_card(self.selected, ctx);
+    }
+
+    // Moves the selection one card up or down, scrolling past the first and last
+    fn select(&mut self, down: bool, ctx: &mut Context) {
+        let selected = self.selected;
+        let last = self.shown().len().saturating_sub(1);
+        let text = self.text(ctx);
+        match down {
+            true if selected < last => self.reveal_card(selected + 1, ctx),
+            false if selected > 0 => self.reveal_card(selected - 1, ctx),
+            true => ctx.scroll_to(ctx.scroll.saturating_add(1), &text),
+            false => ctx.scroll_to(ctx.scroll.saturating_sub(1), &text),
+        }
+    }
+
+    // Selects a card and scrolls just enough to show its box, the blank
+    // line under it may stay hidden
+    fn reveal_card(&mut self, index: usize, ctx: &mut Context) {
+        self.selected = index;
+        let listing = self.listing(ctx.keymap, ctx.area.width);
+        let top = listing.card_top(index);
+        ctx.show(top, top + CARD_HEIGHT - 2, &listing.text);
+    }
+
+    // The tag and sort keys, which act on the list
+    fn filter_by(&mut self, action: Action, ctx: &mut Context) -> Handled {
+        let handled = match self.open {
+            Some(_) => {
+                self.close(ctx);
+                Handled::Replaced
+            }
+            None => Handled::Yes,
+        };
+        let tags = all_tags(projects::catalog());
+        let cursor = self.tag_cursor.min(tags.len().saturating_sub(1));
+        match action {
+            Action::NextTag if !tags.is_empty() => self.tag_cursor = (cursor + 1) % tags.len(),
+            Action::PrevTag if !tags.is_empty() => self.tag_cursor = (cursor + tags.len() - 1) % tags.len(),
+            Action::ToggleTag => match tags.get(cursor) {
+                Some(tag) => self.toggle(tag),
+                None => return handled,
+            },
+            Action::ClearTags => {
+                self.filter.clear();
+                self.selected = 0;
+            }
+            Action::Sort => {
+                self.sort = self.sort.next();
+                self.selected = 0;
+            }
+            _ => return handled,
+        }
+        if matches!(action, Action::ToggleTag | Action::ClearTags | Action::Sort) {
+            info!(tags = self.filter.join(","), sort = self.sort.label(); "projects filtered");
+        }
+
+        // Keep what changed in view, the list may have become shorter
+        let listing = self.listing(ctx.keymap, ctx.area.width);
+        let line = match action {
+            Action::Sort => listing.sort_line,
+            _ => listing.tag_rows.iter().find(|(_, row)| row.contains(&self.tag_cursor)).map(|(line, _)| *line),
+        };
+        ctx.scroll_to(ctx.scroll, &listing.text);
+        if let Some(line) = line {
+            ctx.reveal(line, &listing.text);
+        }
+        handled
+    }
+}
+
+impl View for Catalog {
+    // The cards, or the page of the open project
+    fn text(&self, ctx: &Context) -> String {
+        match self.open.and_then(|i| projects::catalog().get(i)) {
+            Some(project) => {
+                let back = PROJECT_KEYS.iter().find(|(_, action, _)| *action == PromptAction::Cancel);
+                let hint = back.map(|(key, _, _)| format!("Press {} to go back to all projects.", key));
+                detail(project, hint.as_deref())
+            }
+            None => self.listing(ctx.keymap, ctx.area.width).text,
+        }
+    }
+
+    // e.g. "Rust › newest first"
+    fn breadcrumb(&self) -> Vec<String> {
+        match self.open.and_then(|i| projects::catalog().get(i)) {
+            Some(project) => vec![project.name.clone()],
+            None => self.state(),
+        }
+    }
+
+    fn handle_key(&mut self, key: &KeyEvent, ctx: &mut Context) -> Handled {
+        match lookup_prompt(PROJECT_KEYS, key) {
+            Some(PromptAction::Confirm) if self.open.is_none() => match self.shown().get(self.selected) {
+                Some(&index) => self.open_project(index, ctx),
+                None => Handled::Yes,
+            },
+            Some(PromptAction::Cancel) if self.open.is_some() => {
+                self.close(ctx);
+                Handled::Replaced
+            }
+            _ => Handled::No,
+        }
+    }
+
+    fn perform(&mut self, action: Action, ctx: &mut Context) -> Handled {
+        match action {
+            Action::ScrollUp | Action::ScrollDown if self.open.is_none() && !self.shown().is_empty() => {
+                self.select(action == Action::ScrollDown, ctx);
+                Handled::Yes
+            }
+            Action::NextTag | Action::PrevTag | Action::ToggleTag | Action::ClearTags | Action::Sort => {
+                self.filter_by(action, ctx)
+            }
+            _ => Handled::No,
+        }
+    }
+
+    // Cards open, tags filter and sort orders sort
+    fn click(&mut self, line: usize, column: usize, ctx: &mut Context) -> Handled {
+        if self.open.is_some() {
+            return Handled::No;
+        }
+        let listing = self.listing(ctx.keymap, ctx.area.width);
+        let shown = self.shown();
+        let tags = all_tags(projects::catalog());
+        if let Some(tag) = listing.tag_at(&tags, line, column) {
+            self.tag_cursor = tag;
+            return self.filter_by(Action::ToggleTag, ctx);
+        }
+        if let Some(sort) = listing.sort_at(line, column) {
+            if sort != self.sort {
+                self.sort = sort;
+                self.selected = 0;
+                let text = self.text(ctx);
+                ctx.scroll_to(ctx.scroll, &text);
+            }
+        } else if let Some(card) = listing.card_at(shown.len(), line) {
+            return self.open_project(shown[card], ctx);
+        }
+        Handled::Yes
+    }
+
+    fn open(&mut self, item: Option<usize>, ctx: &mut Context) -> Handled {
+        match item {
+            _ if item == self.open => Handled::No,
+            Some(index) => self.open_project(index, ctx),
+            None => {
+                self.close(ctx);
+                Handled::Replaced
+            }
+        }
+    }
+
+    fn draw(&self, ctx: &Context, search: &Search, buf: &mut Buffer) {
+        if self.open.is_some() {
+            return;
+        }
+        let listing = self.listing(ctx.keymap, ctx.area.width);
+        let catalog = projects::catalog();
+        let cards: Vec<_> = self.shown().into_iter().map(|i| &catalog[i]).collect();
+        let tags = all_tags(catalog);
+        FilterBar { tags: &tags, listing: &listing, catalog: self, scroll: ctx.scroll }.render(ctx.area, buf);
+        CardList {
+            projects: &cards,
+            listing: &listing,
+            selected: self.selected,
+            scroll: ctx.scroll,
+            width: card_width(ctx.area.width as usize),
+            search,
+        }
+        .render(ctx.area, buf);
+    }
+}
+
+// Every tag in the catalog, the most used first, spelled as it first appears
+pub fn all_tags(projects: &[Project]) -> Vec<String> {
+    let mut tags: Vec<(String, usize)> = Vec::new();
+    for tag in projects.iter().flat_map(|project| &project.tags) {
+        match tags.iter_mut().find(|(t, _)| t.eq_ignore_ascii_case(tag)) {
+            Some((_, count)) => *count += 1,
+            None => tags.push((tag.trim().to_string(), 1)),
+        }
+    }
+    // Stable, so equally used tags keep the catalog order
+    tags.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    tags.into_iter().map(|(tag, _)| tag).collect()
+}
+
+// Catalog indices of the projects with every tag of `filter`, in `sort` order
+pub fn visible(projects: &[Project], filter: &[String], sort: Sort) -> Vec<usize> {
+    let mut shown: Vec<usize> = (0..projects.len())
+        .filter(|&i| filter.iter().all(|tag| projects[i].tags.iter().any(|t| t.eq_ignore_ascii_case(tag))))
+        .collect();
+    match sort {
+        Sort::Featured => {}
+        // Undated projects last
+        Sort::Newest => shown.sort_by_key(|&i| std::cmp::Reverse(projects[i].year.map_or(0, |year| year as i32 + 1))),
+        Sort::Name => shown.sort_by_key(|&i| projects[i].name.to_lowercase()),
+        Sort::Status => shown.sort_by_key(|&i| projects[i].status),
+    }
+    shown
+}
+
+// The key hints, tag bar and sort line above the cards of a session
+pub struct Controls<'a> {
+    pub hints: Vec<String>,
+    pub tags: &'a [String],
+    pub total: usize,
+}
+
+// The Projects page text and the lines its parts start on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listing {
+    pub text: String,
+    pub first_card: usize,
+    // Rows of the tag bar, each the catalog tag indices on it
+    pub tag_rows: Vec<(usize, Vec<usize>)>,
+    pub sort_line: Option<usize>,
+}
+
+const TAGS_LABEL: &str = "Tags  ";
+const SORT_LABEL: &str = "Sort  ";
+const SORT_SEPARATOR: &str = " · ";
+
+impl Listing {
+    // The card drawn on `line`, if any
+    pub fn card_at(&self, count: usize, line: usize) -> Option<usize> {
+        let offset = line.checked_sub(self.first_card)?;
+        let card = offset / CARD_HEIGHT;
+        (card < count && offset % CARD_HEIGHT < CARD_HEIGHT - 1).then_some(card)
+    }
+
+    pub fn card_top(&self, card: usize) -> usize {
+        self.first_card + card * CARD_HEIGHT
+    }
+
+    // The tag whose chip is drawn at `column` of `line`
+    pub fn tag_at(&self, tags: &[String], line: usize, column: usize) -> Option<usize> {
+        let (_, row) = self.tag_rows.iter().find(|(l, _)| *l == line)?;
+        chip_columns(tags, row).find(|(start, end, _)| *start <= column && column < *end).map(|(_, _, tag)| tag)
+    }
+
+    // The sort order whose name is drawn at `column` of `line`
+    pub fn sort_at(&self, line: usize, column: usize) -> Option<Sort> {
+        if self.sort_line != Some(line) {
+            return None;
+        }
+        sort_columns().find(|(start, end, _)| *start <= column && column < *end).map(|(_, _, sort)| sort)
+    }
+}
+
+// (start, end, tag) columns of the chips on a tag bar row
+fn chip_columns<'a>(tags: &'a [String], row: &'a [usize]) -> impl Iterator<Item = (usize, usize, usize)> + 'a {
+    let mut x = display_width(TAGS_LABEL);
+    row.iter().map(move |&tag| {
+        let width = display_width(&tags[tag]) + 2;
+        let columns = (x, x + width, tag);
+        x += width + 1;
+        columns
+    })
+}
+
+fn sort_columns() -> impl Iterator<Item = (usize, usize, Sort)> {
+    let mut x = display_width(SORT_LABEL);
+    Sort::ALL.into_iter().map(move |sort| {
+        let columns = (x, x + sort.label().len(), sort);
+        x += sort.label().len() + SORT_SEPARATOR.chars().count();
+        columns
+    })
+}
+
+pub fn card_width(available: usize) -> usize {
+    available.clamp(MIN_CARD_WIDTH, CARD_WIDTH)
+}
+
+// The intro from `PageContent`, the session's controls if any and a card per project
+pub fn list(intro: &str, projects: &[&Project], controls: Option<&Controls>, width: usize) -> Listing {
+    let mut text = intro.to_string();
+    let mut listing = Listing { text: String::new(), first_card: 0, tag_rows: Vec::new(), sort_line: None };
+    match controls {
+        Some(controls) if controls.total > 0 => {
+            text.push('\n');
+            for hint in &controls.hints {
+                text.push_str(&format!("{}\n", hint));
+            }
+            text.push('\n');
+            for (i, row) in tag_layout(controls.tags, width.saturating_sub(display_width(TAGS_LABEL))).into_iter().enumerate() {
+                let label = if i == 0 { TAGS_LABEL.to_string() } else { " ".repeat(display_width(TAGS_LABEL)) };
+                let chips: Vec<String> = row.iter().map(|&tag| format!("[{}]", controls.tags[tag])).collect();
+                listing.tag_rows.push((text.lines().count(), row));
+                text.push_str(&format!("{}{}\n", label, chips.join(" ")));
+            }
+            listing.sort_line = Some(text.lines().count());
+            let sorts: Vec<&str> = Sort::ALL.iter().map(Sort::label).collect();
+            text.push_str(&format!("{}{}\n", SORT_LABEL, sorts.join(SORT_SEPARATOR)));
+            text.push_str(&match projects.len() {
+                n if n == controls.total => format!("\n{} projects\n", n),
+                0 => "\nNo project has all of these tags.\n".to_string(),
+                n => format!("\n{} of {} projects\n", n, controls.total),
+            });
+        }
+        _ if projects.is_empty() => text.push_str("\nNothing to show yet, check back soon!\n"),
+        _ => {}
+    }
+    text.push('\n');
+    listing.first_card = text.lines().count();
+    for project in projects {
+        for line in card(project, width, false) {
+            text.push_str(&line.iter().map(|span| span.text.as_str()).collect::<String>());
+            text.push('\n');
+        }
+        text.push('\n');
+    }
+    listing.text = text;
+    listing
+}
+
+// Tag indices per row of chips no wider than `width`, like `markup::chips`
+fn tag_layout(tags: &[String], width: usize) -> Vec<Vec<usize>> {
+    let mut rows: Vec<Vec<usize>> = Vec::new();
+    let mut used = 0;
+    for (i, tag) in tags.iter().enumerate() {
+        let chip = display_width(tag) + 2;
+        match rows.last_mut() {
+            Some(row) if used + 1 + chip <= width => {
+                row.push(i);
+                used += 1 + chip;
+            }
+            _ => {
+                rows.push(vec![i]);
+                used = chip;
+            }
+        }
+    }
+    rows
+}
+
+// ╭─ medaly.engineer ──────────────── 2025 ─╮
+// │ An interactive terminal portfolio       │
+// │ [Rust] [SSH] [Terminal UI]   ● Maintained │
+// ╰─────────────────────────────────────────╯
+pub fn card(project: &Project, width: usize, selected: bool) -> Vec<Vec<Span>> {
+    let width = width.max(MIN_CARD_WIDTH);
+    let inner = width - 4;
+    let border = Style::new().fg(if selected { Color::Cyan } else { Color::DarkGrey });
+    let name_style = Style::new().fg(if selected { Color::Cyan } else { Color::White }).bold();
+
+    let year = project.year.map(|year| format!(" {} ", year)).unwrap_or_default();
+    let name = truncate(&project.name, width.saturating_sub(8 + display_width(&year)));
+    let rule = width - 6 - display_width(name) - display_width(&year);
+    let top = vec![
+        Span::styled("╭─ ", border),
+        Span::styled(name, name_style),
+        Span::styled(format!(" {}", "─".repeat(rule)), border),
+        Span::styled(year, Style::new().fg(Color::DarkGrey)),
+        Span::styled("─╮", border),
+    ];
+
+    let tagline = truncate(&project.tagline, inner);
+    let middle = boxed(vec![Span::styled(tagline, Style::new().fg(Color::White))], Vec::new(), inner, border);
+
+    let status = Span::styled(format!("● {}", project.status.label()), Style::new().fg(status_color(project.status)));
+    let room = inner.saturating_sub(status.width() + 1);
+    let tags = fitting_chips(&project.tags, room);
+    let bottom = boxed(vec![Span::styled(tags, Style::new().fg(Color::Magenta))], vec![status], inner, border);
+
+    let last = vec![Span::styled(format!("╰{}╯", "─".repeat(width - 2)), border)];
+    vec![top, middle, bottom, last]
+}
+
+// "│ left      right │", `left` cut to leave room for `right`
+fn boxed(left: Vec<Span>, right: Vec<Span>, inner: usize, border: Style) -> Vec<Span> {
+    let used: usize = left.iter().chain(&right).map(Span::width).sum();
+    let mut spans = vec![Span::styled("│ ", border)];
+    spans.extend(left);
+    spans.push(Span::styled(" ".repeat(inner.saturating_sub(used)), Style::default()));
+    spans.extend(right);
+    spans.push(Span::styled(" │", border));
+    spans
+}
+
+// As many chips as fit in `width`, then "+2" for the rest
+fn fitting_chips(tags: &[String], width: usize) -> String {
+    let mut row = String::new();
+    for (i, tag) in tags.iter().enumerate() {
+        let chip = format!("[{}]", tag.trim());
+        let more = format!(" +{}", tags.len() - i);
+        let sep = if row.is_empty() { 0 } else { 1 };
+        let last = i + 1 == tags.len();
+        let needed = display_width(&row) + sep + display_width(&chip) + if last { 0 } else { display_width(&more) };
+        if needed > width {
+            if display_width(&row) + display_width(&more) <= width {
+                row.push_str(if row.is_empty() { more.trim_start() } else { &more });
+            }
+            break;
+        }
+        if sep == 1 {
+            row.push(' ');
+        }
+        row.push_str(&chip);
+    }
+    row
+}
+
+pub fn status_color(status: Status) -> Color {
+    match status {
+        Status::Active => Color::Green,
+        Status::Maintained => Color::Cyan,
+        Status::Completed => Color::Blue,
+        Status::Planned => Color::Yellow,
+        Status::Archived => Color::DarkGrey,
+    }
+}
+
+// A project on a page of its own: what it is, its tags and links, the
+// screenshot and the description, with an optional hint at the bottom
+pub fn detail(project: &Project, hint: Option<&str>) -> String {
+    let mut text = format!("\n# {}\n", project.name);
+    if !project.tagline.is_empty() {
+        text.push('\n');
+        for line in textwrap::wrap(&project.tagline, WRAP_WIDTH) {
+            text.push_str(&format!("{}\n", line));
+        }
+    }
+    let year = project.year.map(|year| year.to_string());
+    let meta: Vec<String> = year.into_iter().chain([format!("● {}", project.status.label())]).collect();
+    text.push_str(&format!("\n{}\n", meta.join(" · ")));
+    for row in chips(&project.tags, WRAP_WIDTH) {
+        text.push_str(&format!("{}\n", row));
+    }
+
+    if let Some(screenshot) = &project.screenshot {
+        text.push('\n');
+        for line in screenshot.trim_matches('\n').lines() {
+            // Indented so the markup takes every line as art
+            text.push_str(&format!("  {}\n", line));
+        }
+    }
+    if !project.description.trim().is_empty() {
+        text.push('\n');
+        text.push_str(&wrap_markup(project.description.trim_matches('\n')));
+    }
+    if !project.links.is_empty() {
+        text.push_str("\n## Links\n\n");
+        for link in &project.links {
+            text.push_str(&format!("- {}: {}\n", link.label, link.url));
+        }
+    }
+    if let Some(hint) = hint {
+        text.push_str(&format!("\n{}\n", hint));
+    }
+    text
+}
+
+// Long text and bullet lines wrapped, code and headings left alone
+fn wrap_markup(markup: &str) -> String {
+    let mut text = String::new();
+    let mut in_code = false;
+    for raw in markup.lines() {
+        let line = Line::parse(raw);
+        if line.kind == LineKind::Fence {
+            in_code = !in_code;
+        }
+        let wrappable = !in_code && matches!(line.kind, LineKind::Text | LineKind::Bullet) && !raw.starts_with("  ");
+        if !wrappable || display_width(raw) <= WRAP_WIDTH {
+            text.push_str(&format!("{}\n", raw));
+            continue;
+        }
+        let (first, rest) = if line.kind == LineKind::Bullet { ("- ", "  ") } else { ("", "") };
+        let options = textwrap::Options::new(WRAP_WIDTH).initial_indent(first).subsequent_indent(rest);
+        for wrapped in textwrap::wrap(line.text, options) {
+            text.push_str(&format!("{}\n", wrapped));
+        }
+    }
+    text
+}
+
+// Draws the visible cards over the plain text of the list in their colors,
+// the selected one highlighted. Lines with search matches keep the highlights.
+pub struct CardList<'a> {
+    pub projects: &'a [&'a Project],
+    pub listing: &'a Listing,
+    pub selected: usize,
+    pub scroll: u16,
+    pub width: usize,
+    pub search: &'a Search,
+}
+
+impl Widget for CardList<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        for row in 0..area.height {
+            let line = self.scroll as usize + row as usize;
+            let Some(index) = self.listing.card_at(self.projects.len(), line) else {
+                continue;
+            };
+            if self.search.matches_on(line).next().is_some() {
+                continue;
+            }
+            let lines = card(self.projects[index], self.width, index == self.selected);
+            let spans = lines[line - self.listing.card_top(index)].clone();
+            let row_area = Rect::new(area.x, area.y + row, area.width, 1);
+            buf.fill(row_area, ' ', Style::default());
+            Text::line(spans).render(row_area, buf);
+        }
+    }
+}
+
+// Draws the tag bar and sort line in their colors: filtered tags inverted,
+// the tag under the cursor underlined and the current sort order bright
+pub struct FilterBar<'a> {
+    pub tags: &'a [String],
+    pub listing: &'a Listing,
+    pub catalog: &'a Catalog,
+    pub scroll: u16,
+}
+
+impl Widget for FilterBar<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let grey = Style::new().fg(Color::DarkGrey);
+        let visible = |line: usize| {
+            let row = line.checked_sub(self.scroll as usize)?;
+            (row < area.height as usize).then(|| Rect::new(area.x, area.y + row as u16, area.width, 1))
+        };
+        for (i, (line, row)) in self.listing.tag_rows.iter().enumerate() {
+            let Some(row_area) = visible(*line) else {
+                continue;
+            };
+            if i == 0 {
+                buf.set_string_clipped(row_area.x, row_area.y, TAGS_LABEL, grey, row_area.right());
+            }
+            for (start, _, tag) in chip_columns(self.tags, row) {
+                let mut style = match self.catalog.is_filtered(&self.tags[tag]) {
+                    true => Style::new().fg(Color::Black).bg(Color::Magenta),
+                    false => Style::new().fg(Color::Magenta),
+                };
+                if tag == self.catalog.tag_cursor {
+                    style = style.bold().underline();
+                }
+                let x = row_area.x + start as u16;
+                buf.set_string_clipped(x, row_area.y, &format!("[{}]", self.tags[tag]), style, row_area.right());
+            }
+        }
+        if let Some(row_area) = self.listing.sort_line.and_then(visible) {
+            buf.set_string_clipped(row_area.x, row_area.y, SORT_LABEL, grey, row_area.right());
+            for (start, _, sort) in sort_columns() {
+                let style = if sort == self.catalog.sort { Style::new().fg(Color::Cyan).bold() } else { grey };
+                let x = row_area.x + start as u16;
+                let after = buf.set_string_clipped(x, row_area.y, sort.label(), style, row_area.right());
+                if sort != Sort::Status {
+                    buf.set_string_clipped(after, row_area.y, SORT_SEPARATOR, grey, row_area.right());
+                }
+            }
+        }
+    }
+}
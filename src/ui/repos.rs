@@ -0,0 +1,289 @@
+// ui/repos.rs
+
+use crossterm::style::Color;
+use log::info;
+
+use crate::github::{self, Repo, Snapshot};
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::keymap::{Action, Keymap};
+use crate::ui::layout::Rect;
+use crate::ui::markup::{chips, display_width};
+use crate::ui::search::Search;
+use crate::ui::view::{Context, Handled, View};
+use crate::ui::widget::{Span, Text, Widget};
+
+const WRAP_WIDTH: usize = 64;
+// Where the repositories are until the first `sync-github`, as on the Contact page
+const GITHUB_PROFILE: &str = "https://github.com/mohamedalidridii";
+
+const SORT_LABEL: &str = "Sort  ";
+const SORT_SEPARATOR: &str = " · ";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RepoSort {
+    #[default]
+    Stars,
+    Updated,
+    Name,
+}
+
+impl RepoSort {
+    pub const ALL: [RepoSort; 3] = [RepoSort::Stars, RepoSort::Updated, RepoSort::Name];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RepoSort::Stars => "stars",
+            RepoSort::Updated => "updated",
+            RepoSort::Name => "name",
+        }
+    }
+
+    pub fn next(&self) -> RepoSort {
+        let i = Self::ALL.iter().position(|sort| sort == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+// The Repos page during a session
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Repos {
+    pub sort: RepoSort,
+}
+
+impl Repos {
+    pub fn listing(&self, keymap: &Keymap) -> RepoListing {
+        let key = keymap.first_key(Action::Sort).map(|k| k.to_string()).unwrap_or_default();
+        let hint = format!("{} sorts by stars, last update or name", key);
+        list(github::snapshot(), self.sort, Some(&hint))
+    }
+
+    // Sorts the list and keeps the sort line in view
+    fn sort_by(&mut self, sort: RepoSort, ctx: &mut Context) -> Handled {
+        self.sort = sort;
+        info!(sort = sort.label(); "repositories sorted");
+        let listing = self.listing(ctx.keymap);
+        if let Some(line) = listing.sort_line {
+            ctx.reveal(line, &listing.text);
+        }
+        Handled::Yes
+    }
+}
+
+impl View for Repos {
+    fn text(&self, ctx: &Context) -> String {
+        self.listing(ctx.keymap).text
+    }
+
+    fn breadcrumb(&self) -> Vec<String> {
+        match self.sort {
+            RepoSort::Stars => Vec::new(),
+            RepoSort::Updated => vec!["recently updated".to_string()],
+            RepoSort::Name => vec!["by name".to_string()],
+        }
+    }
+
+    fn perform(&mut self, action: Action, ctx: &mut Context) -> Handled {
+        match action {
+            Action::Sort => self.sort_by(self.sort.next(), ctx),
+            _ => Handled::No,
+        }
+    }
+
+    fn click(&mut self, line: usize, column: usize, ctx: &mut Context) -> Handled {
+        match self.listing(ctx.keymap).sort_at(line, column) {
+            Some(sort) => self.sort_by(sort, ctx),
+            None => Handled::Yes,
+        }
+    }
+
+    fn draw(&self, ctx: &Context, search: &Search, buf: &mut Buffer) {
+        if let Some(snapshot) = github::snapshot() {
+            let listing = self.listing(ctx.keymap);
+            RepoList { repos: &snapshot.repos, listing: &listing, sort: self.sort, scroll: ctx.scroll, search }
+                .render(ctx.area, buf);
+        }
+    }
+}
+
+// The Repos page text and the lines the colored parts are on
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepoListing {
+    pub text: String,
+    // (line, repository index in the snapshot) of each "● Rust · ★ 12" line
+    pub meta_lines: Vec<(usize, usize)>,
+    pub sort_line: Option<usize>,
+}
+
+impl RepoListing {
+    // The sort order whose name is drawn at `column` of `line`
+    pub fn sort_at(&self, line: usize, column: usize) -> Option<RepoSort> {
+        if self.sort_line != Some(line) {
+            return None;
+        }
+        sort_columns().find(|(start, end, _)| *start <= column && column < *end).map(|(_, _, sort)| sort)
+    }
+}
+
+fn sort_columns() -> impl Iterator<Item = (usize, usize, RepoSort)> {
+    let mut x = display_width(SORT_LABEL);
+    RepoSort::ALL.into_iter().map(move |sort| {
+        let columns = (x, x + sort.label().len(), sort);
+        x += sort.label().len() + SORT_SEPARATOR.chars().count();
+        columns
+    })
+}
+
+// Snapshot indices of the repositories in `sort` order, ties by name
+pub fn sorted(repos: &[Repo], sort: RepoSort) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..repos.len()).collect();
+    order.sort_by_key(|&i| repos[i].name.to_lowercase());
+    match sort {
+        RepoSort::Stars => order.sort_by_key(|&i| std::cmp::Reverse(repos[i].stargazers_count)),
+        RepoSort::Updated => order.sort_by_key(|&i| std::cmp::Reverse(repos[i].updated())),
+        RepoSort::Name => {}
+    }
+    order
+}
+
+// Every repository of the snapshot as a section, `hint` under the intro
+pub fn list(snapshot: Option<&Snapshot>, sort: RepoSort, hint: Option<&str>) -> RepoListing {
+    let mut text = String::from("\n# Repositories\n\n");
+    let mut listing = RepoListing::default();
+    let Some(snapshot) = snapshot.filter(|snapshot| !snapshot.repos.is_empty()) else {
+        text.push_str(&format!("Nothing synced from GitHub yet. Meanwhile, my repositories are at\n{}\n", GITHUB_PROFILE));
+        listing.text = text;
+        return listing;
+    };
+
+    let profile = snapshot.profile().unwrap_or_else(|| GITHUB_PROFILE.to_string());
+    text.push_str(&format!("Open source on {}\n", profile));
+    if let Some(synced) = snapshot.synced {
+        text.push_str(&format!("{} repositories, as of {}.\n", snapshot.repos.len(), synced.format("%Y-%m-%d")));
+    }
+    text.push('\n');
+    if let Some(hint) = hint {
+        text.push_str(&format!("{}\n", hint));
+    }
+    listing.sort_line = Some(text.lines().count());
+    let sorts: Vec<&str> = RepoSort::ALL.iter().map(RepoSort::label).collect();
+    text.push_str(&format!("{}{}\n", SORT_LABEL, sorts.join(SORT_SEPARATOR)));
+
+    for index in sorted(&snapshot.repos, sort) {
+        let repo = &snapshot.repos[index];
+        text.push_str(&format!("\n## {}\n\n", repo.name));
+        listing.meta_lines.push((text.lines().count(), index));
+        text.push_str(&format!("{}\n", meta(repo).iter().map(|span| span.text.as_str()).collect::<String>()));
+        if let Some(description) = repo.description.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+            for line in textwrap::wrap(description, WRAP_WIDTH) {
+                text.push_str(&format!("{}\n", line));
+            }
+        }
+        for row in chips(&repo.topics, WRAP_WIDTH) {
+            text.push_str(&format!("{}\n", row));
+        }
+        text.push_str(&format!("{}\n", repo.html_url));
+    }
+    listing.text = text;
+    listing
+}
+
+// "● Rust · ★ 128 · 4 forks · updated 2025-06-01 · archived"
+fn meta(repo: &Repo) -> Vec<Span> {
+    let grey = Style::new().fg(Color::DarkGrey);
+    let mut parts: Vec<Vec<Span>> = Vec::new();
+    if let Some(language) = &repo.language {
+        parts.push(vec![
+            Span::styled("● ", Style::new().fg(language_color(language))),
+            Span::styled(language.clone(), Style::new().fg(Color::White)),
+        ]);
+    }
+    parts.push(vec![Span::styled(format!("★ {}", repo.stargazers_count), Style::new().fg(Color::Yellow))]);
+    if repo.forks_count > 0 {
+        let forks = if repo.forks_count == 1 { "1 fork".to_string() } else { format!("{} forks", repo.forks_count) };
+        parts.push(vec![Span::styled(forks, grey)]);
+    }
+    if let Some(updated) = repo.updated() {
+        parts.push(vec![Span::styled(format!("updated {}", updated.format("%Y-%m-%d")), grey)]);
+    }
+    if repo.archived {
+        parts.push(vec![Span::styled("archived", Style::new().fg(Color::Red))]);
+    }
+
+    let mut spans = Vec::new();
+    for (i, part) in parts.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(SORT_SEPARATOR, grey));
+        }
+        spans.extend(part);
+    }
+    spans
+}
+
+// The colors GitHub shows next to the most common languages
+pub fn language_color(language: &str) -> Color {
+    let rgb = |hex: u32| Color::Rgb { r: (hex >> 16) as u8, g: (hex >> 8) as u8, b: hex as u8 };
+    match language.to_ascii_lowercase().as_str() {
+        "rust" => rgb(0xdea584),
+        "typescript" => rgb(0x3178c6),
+        "javascript" => rgb(0xf1e05a),
+        "python" => rgb(0x3572a5),
+        "go" => rgb(0x00add8),
+        "java" => rgb(0xb07219),
+        "kotlin" => rgb(0xa97bff),
+        "c" => rgb(0x555555),
+        "c++" => rgb(0xf34b7d),
+        "c#" => rgb(0x178600),
+        "php" => rgb(0x4f5d95),
+        "ruby" => rgb(0x701516),
+        "swift" => rgb(0xf05138),
+        "dart" => rgb(0x00b4ab),
+        "html" => rgb(0xe34c26),
+        "css" | "scss" => rgb(0x663399),
+        "vue" => rgb(0x41b883),
+        "svelte" => rgb(0xff3e00),
+        "shell" => rgb(0x89e051),
+        "dockerfile" => rgb(0x384d54),
+        "lua" => rgb(0x000080),
+        "nix" => rgb(0x7e7eff),
+        "jupyter notebook" => rgb(0xda5b0b),
+        _ => Color::Grey,
+    }
+}
+
+// Draws the visible "● Rust · ★ 12" lines and the sort line in their colors.
+// Lines with search matches keep the highlights.
+pub struct RepoList<'a> {
+    pub repos: &'a [Repo],
+    pub listing: &'a RepoListing,
+    pub sort: RepoSort,
+    pub scroll: u16,
+    pub search: &'a Search,
+}
+
+impl Widget for RepoList<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let grey = Style::new().fg(Color::DarkGrey);
+        let visible = |line: usize| {
+            let row = line.checked_sub(self.scroll as usize)?;
+            let shown = row < area.height as usize && self.search.matches_on(line).next().is_none();
+            shown.then(|| Rect::new(area.x, area.y + row as u16, area.width, 1))
+        };
+        for &(line, index) in &self.listing.meta_lines {
+            if let Some(row_area) = visible(line) {
+                buf.fill(row_area, ' ', Style::default());
+                Text::line(meta(&self.repos[index])).render(row_area, buf);
+            }
+        }
+        if let Some(row_area) = self.listing.sort_line.and_then(visible) {
+            buf.set_string_clipped(row_area.x, row_area.y, SORT_LABEL, grey, row_area.right());
+            for (start, _, sort) in sort_columns() {
+                let style = if sort == self.sort { Style::new().fg(Color::Cyan).bold() } else { grey };
+                let x = row_area.x + start as u16;
+                let after = buf.set_string_clipped(x, row_area.y, sort.label(), style, row_area.right());
+                if sort != RepoSort::Name {
+                    buf.set_string_clipped(after, row_area.y, SORT_SEPARATOR, grey, row_area.right());
+                }
+            }
+        }
+    }
+}
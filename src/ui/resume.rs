@@ -0,0 +1,404 @@
+// ui/resume.rs
+
+use crossterm::style::Color;
+use log::info;
+
+use crate::resume::{Education, Resume, Skill, Work};
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::keymap::{Action, Keymap};
+use crate::ui::markup::{chips, display_width};
+use crate::ui::search::Search;
+use crate::ui::view::{Context, Handled, View};
+
+// Fits next to the contents sidebar on a 100 column terminal
+const WRAP_WIDTH: usize = 64;
+const BAR_WIDTH: usize = 10;
+// Longer skill names push the bars further right than looks good
+const MAX_NAME_WIDTH: usize = 24;
+
+const PAGES_LABEL: &str = "Resume  ";
+const PAGES_SEPARATOR: &str = " · ";
+// The row of the resume's pages, under the blank first line
+const PAGES_LINE: usize = 1;
+
+// One stop on a timeline: the dates on the left, a title on the rail and the
+// lines below it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub when: String,
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+// The pages the About page splits a resume into, each shown on its own
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResumePage {
+    #[default]
+    About,
+    Experience,
+    Education,
+    Skills,
+}
+
+impl ResumePage {
+    pub const ALL: [ResumePage; 4] =
+        [ResumePage::About, ResumePage::Experience, ResumePage::Education, ResumePage::Skills];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResumePage::About => "About",
+            ResumePage::Experience => "Experience",
+            ResumePage::Education => "Education",
+            ResumePage::Skills => "Skills",
+        }
+    }
+
+    // Whether the resume has entries for the page, About always shows
+    pub fn shown_for(&self, resume: &Resume) -> bool {
+        match self {
+            ResumePage::About => true,
+            ResumePage::Experience => !resume.work.is_empty(),
+            ResumePage::Education => !resume.education.is_empty(),
+            ResumePage::Skills => !resume.skills.is_empty(),
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|page| page == self).unwrap_or(0)
+    }
+}
+
+pub fn page(resume: &Resume, page: ResumePage) -> String {
+    match page {
+        ResumePage::About => about(resume),
+        ResumePage::Experience => {
+            format!("\n# Experience\n\n{}", timeline(&resume.work.iter().map(work_entry).collect::<Vec<_>>()))
+        }
+        ResumePage::Education => {
+            format!("\n# Education\n\n{}", timeline(&resume.education.iter().map(education_entry).collect::<Vec<_>>()))
+        }
+        ResumePage::Skills => format!("\n# Skills\n\n{}", skills(&resume.skills)),
+    }
+}
+
+// The About page during a session with a resume, and which of its pages is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResumePages {
+    pub resume: &'static Resume,
+    pub shown: ResumePage,
+}
+
+impl ResumePages {
+    pub fn new(resume: &'static Resume) -> Self {
+        Self { resume, shown: ResumePage::default() }
+    }
+
+    fn show(&mut self, page: ResumePage, ctx: &mut Context) -> Handled {
+        if page == self.shown {
+            return Handled::Yes;
+        }
+        self.shown = page;
+        info!(page = page.label(); "resume page shown");
+        ctx.scroll = 0;
+        Handled::Replaced
+    }
+
+    // The next page the resume has, `step` pages on and wrapping around
+    fn turn(&mut self, step: usize, ctx: &mut Context) -> Handled {
+        let pages = pages(self.resume);
+        let i = pages.iter().position(|page| *page == self.shown).unwrap_or(0);
+        self.show(pages[(i + step) % pages.len()], ctx)
+    }
+}
+
+impl View for ResumePages {
+    fn text(&self, ctx: &Context) -> String {
+        text(self.resume, self.shown, hint(ctx.keymap).as_deref())
+    }
+
+    fn breadcrumb(&self) -> Vec<String> {
+        match self.shown {
+            ResumePage::About => Vec::new(),
+            page => vec![page.label().to_string()],
+        }
+    }
+
+    fn perform(&mut self, action: Action, ctx: &mut Context) -> Handled {
+        let count = pages(self.resume).len();
+        match action {
+            Action::NextResumePage => self.turn(1, ctx),
+            Action::PrevResumePage => self.turn(count - 1, ctx),
+            _ => Handled::No,
+        }
+    }
+
+    fn click(&mut self, line: usize, column: usize, ctx: &mut Context) -> Handled {
+        let page = page_columns(self.resume).find(|(start, end, _)| *start <= column && column < *end);
+        match page {
+            Some((_, _, page)) if line == PAGES_LINE => self.show(page, ctx),
+            _ => Handled::No,
+        }
+    }
+
+    fn open(&mut self, item: Option<usize>, ctx: &mut Context) -> Handled {
+        self.show(ResumePage::ALL[item.unwrap_or(0)], ctx)
+    }
+
+    // The row of pages in color, unless a search match is on it
+    fn draw(&self, ctx: &Context, search: &Search, buf: &mut Buffer) {
+        let Some(row) = PAGES_LINE.checked_sub(ctx.scroll as usize).filter(|row| *row < ctx.area.height as usize) else {
+            return;
+        };
+        if search.matches_on(PAGES_LINE).next().is_some() {
+            return;
+        }
+        let (y, right) = (ctx.area.y + row as u16, ctx.area.right());
+        let grey = Style::new().fg(Color::DarkGrey);
+        buf.set_string_clipped(ctx.area.x, y, PAGES_LABEL, grey, right);
+        for (i, (start, _, page)) in page_columns(self.resume).enumerate() {
+            let x = ctx.area.x + start as u16;
+            if i > 0 {
+                buf.set_string_clipped(x - PAGES_SEPARATOR.chars().count() as u16, y, PAGES_SEPARATOR, grey, right);
+            }
+            let style = if page == self.shown { Style::new().fg(Color::Cyan).bold() } else { grey };
+            buf.set_string_clipped(x, y, page.label(), style, right);
+        }
+    }
+}
+
+// The pages `resume` has entries for, in order
+pub fn pages(resume: &Resume) -> Vec<ResumePage> {
+    ResumePage::ALL.into_iter().filter(|page| page.shown_for(resume)).collect()
+}
+
+// The page under the row of the resume's pages, `hint` at the bottom where it
+// does not move the lines above
+pub fn text(resume: &Resume, shown: ResumePage, hint: Option<&str>) -> String {
+    let labels: Vec<&str> = pages(resume).iter().map(ResumePage::label).collect();
+    let mut text = format!("\n{}{}\n", PAGES_LABEL, labels.join(PAGES_SEPARATOR));
+    text.push_str(&page(resume, shown));
+    if let Some(hint) = hint {
+        text.push_str(&format!("\n{}\n", hint));
+    }
+    text
+}
+
+fn hint(keymap: &Keymap) -> Option<String> {
+    let prev = keymap.first_key(Action::PrevResumePage)?;
+    let next = keymap.first_key(Action::NextResumePage)?;
+    Some(format!("{}/{} or a click on the row at the top switches between the pages", prev, next))
+}
+
+fn page_columns(resume: &Resume) -> impl Iterator<Item = (usize, usize, ResumePage)> {
+    let mut x = display_width(PAGES_LABEL);
+    pages(resume).into_iter().map(move |page| {
+        let columns = (x, x + page.label().len(), page);
+        x += page.label().len() + PAGES_SEPARATOR.chars().count();
+        columns
+    })
+}
+
+// Every page the resume has, one after the other, for output nobody can
+// switch pages in: the web pages and the exports
+pub fn all_pages(resume: &Resume) -> String {
+    pages(resume).into_iter().map(|p| page(resume, p)).collect()
+}
+
+// The About page: who, then the Languages and Interests sections the resume
+// has entries for. Experience, Education and Skills are pages of their own.
+pub fn about(resume: &Resume) -> String {
+    let basics = &resume.basics;
+    let mut text = format!("\n# {}\n", basics.name.as_deref().unwrap_or("About Me"));
+    if let Some(label) = &basics.label {
+        text.push_str(&format!("\n## {}\n", label));
+    }
+    if let Some(summary) = &basics.summary {
+        text.push('\n');
+        push_wrapped(&mut text, summary);
+    }
+
+    let location = basics.location.as_ref().map(|location| {
+        [&location.city, &location.region, &location.country_code].into_iter().flatten().cloned().collect::<Vec<_>>()
+    });
+    let details: Vec<String> = [location.map(|parts| parts.join(", ")), basics.url.clone(), basics.email.clone()]
+        .into_iter()
+        .flatten()
+        .filter(|detail| !detail.is_empty())
+        .collect();
+    if !details.is_empty() {
+        text.push_str(&format!("\n{}\n", details.join(" · ")));
+    }
+    if !basics.profiles.is_empty() {
+        text.push_str("\n## Profiles\n\n");
+        for profile in &basics.profiles {
+            let network = profile.network.as_deref().unwrap_or("Web");
+            let url = profile.url.as_deref().or(profile.username.as_deref()).unwrap_or_default();
+            text.push_str(&format!("- {}: {}\n", network, url));
+        }
+    }
+
+    if !resume.languages.is_empty() {
+        let languages: Vec<String> = resume
+            .languages
+            .iter()
+            .filter_map(|l| match (&l.language, &l.fluency) {
+                (Some(language), Some(fluency)) => Some(format!("{} · {}", language, fluency)),
+                (language, _) => language.clone(),
+            })
+            .collect();
+        text.push_str("\n## Languages\n\n");
+        for row in chips(&languages, WRAP_WIDTH) {
+            text.push_str(&format!("{}\n", row));
+        }
+    }
+    if !resume.interests.is_empty() {
+        text.push_str("\n## Interests\n\n");
+        for interest in &resume.interests {
+            text.push_str(&format!("### {}\n", interest.name.as_deref().unwrap_or("Interests")));
+            for row in chips(&interest.keywords, WRAP_WIDTH) {
+                text.push_str(&format!("{}\n", row));
+            }
+        }
+    }
+    text
+}
+
+fn work_entry(work: &Work) -> Entry {
+    let title = [&work.position, &work.name].into_iter().flatten().cloned().collect::<Vec<_>>().join(" · ");
+    let mut lines: Vec<String> = [&work.location, &work.url].into_iter().flatten().cloned().collect();
+    if let Some(summary) = &work.summary {
+        lines.push(summary.clone());
+    }
+    lines.extend(work.highlights.iter().map(|highlight| format!("• {}", highlight)));
+    Entry { when: period(work.start_date.as_deref(), work.end_date.as_deref()), title, lines }
+}
+
+fn education_entry(education: &Education) -> Entry {
+    let title = [&education.study_type, &education.area].into_iter().flatten().cloned().collect::<Vec<_>>().join(" · ");
+    let mut lines: Vec<String> = [&education.institution, &education.url].into_iter().flatten().cloned().collect();
+    if let Some(score) = &education.score {
+        lines.push(format!("Score: {}", score));
+    }
+    lines.extend(education.courses.iter().map(|course| format!("• {}", course)));
+    Entry { when: period(education.start_date.as_deref(), education.end_date.as_deref()), title, lines }
+}
+
+//   2024 – Present  ● Founder & CEO · KOYOTEC DIGITAL
+//                   │ Tunisia
+//                   │ • Founded and leading a creative agency
+pub fn timeline(entries: &[Entry]) -> String {
+    let when_width = entries.iter().map(|entry| display_width(&entry.when)).max().unwrap_or(0);
+    let gutter = " ".repeat(2 + when_width + 2);
+    let width = WRAP_WIDTH.saturating_sub(gutter.len() + 2);
+    let mut text = String::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let padding = when_width - display_width(&entry.when);
+        text.push_str(&format!("  {}{}  ● {}\n", entry.when, " ".repeat(padding), entry.title));
+        for line in &entry.lines {
+            // Bullets hang their continuation lines under the text
+            let (first, rest) = match line.strip_prefix("• ") {
+                Some(item) => ("• ", item),
+                None => ("", line.as_str()),
+            };
+            let indent = " ".repeat(first.chars().count());
+            for (n, wrapped) in textwrap::wrap(rest, width.saturating_sub(indent.len())).iter().enumerate() {
+                let lead = if n == 0 { first } else { indent.as_str() };
+                text.push_str(&format!("{}│ {}{}\n", gutter, lead, wrapped));
+            }
+        }
+        if i + 1 < entries.len() {
+            text.push_str(&format!("{}│\n", gutter));
+        }
+    }
+    text
+}
+
+//   Frontend Development  ████████░░ Advanced
+//                         [Next.js] [React] [GSAP]
+fn skills(skills: &[Skill]) -> String {
+    let name_width = skills
+        .iter()
+        .map(|skill| display_width(skill.name.as_deref().unwrap_or_default()))
+        .max()
+        .unwrap_or(0)
+        .min(MAX_NAME_WIDTH);
+    let indent = " ".repeat(2 + name_width + 2);
+    let mut text = String::new();
+    for skill in skills {
+        let name = skill.name.as_deref().unwrap_or_default();
+        let padding = name_width.saturating_sub(display_width(name));
+        let level = skill.level.as_deref().filter(|level| !level.is_empty());
+        let mut rows = chips(&skill.keywords, WRAP_WIDTH.saturating_sub(indent.len())).into_iter();
+        let first = match level {
+            Some(level) => match bar(level) {
+                Some(bar) => format!("{} {}", bar, level),
+                None => level.to_string(),
+            },
+            None => rows.next().unwrap_or_default(),
+        };
+        let line = format!("  {}{}  {}", name, " ".repeat(padding), first);
+        text.push_str(&format!("{}\n", line.trim_end()));
+        for row in rows {
+            text.push_str(&format!("{}{}\n", indent, row));
+        }
+    }
+    text
+}
+
+// "████████░░" for a level the bar can show
+pub fn bar(level: &str) -> Option<String> {
+    let filled = level_fill(level)?;
+    Some(format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled)))
+}
+
+// Out of `BAR_WIDTH`: the usual JSON Resume words, or a percentage
+fn level_fill(level: &str) -> Option<usize> {
+    let level = level.trim().to_ascii_lowercase();
+    if let Some(percent) = level.strip_suffix('%') {
+        let percent: usize = percent.trim().parse().ok()?;
+        return Some((percent.min(100) * BAR_WIDTH + 50) / 100);
+    }
+    let fill = match level.as_str() {
+        "master" | "expert" | "native" | "native speaker" => 10,
+        "advanced" | "fluent" | "proficient" => 8,
+        "intermediate" => 6,
+        "beginner" | "basic" | "novice" | "elementary" => 3,
+        _ => return None,
+    };
+    Some(fill)
+}
+
+// "Mar 2024 – Present", "2019 – 2023", "2021"; empty without dates
+pub fn period(start: Option<&str>, end: Option<&str>) -> String {
+    match (start, end) {
+        (Some(start), Some(end)) if month_year(start) == month_year(end) => month_year(start),
+        (Some(start), Some(end)) => format!("{} – {}", month_year(start), month_year(end)),
+        (Some(start), None) => format!("{} – Present", month_year(start)),
+        (None, Some(end)) => month_year(end),
+        (None, None) => String::new(),
+    }
+}
+
+// ISO 8601 dates as "Mar 2024", or just the year when that is all there is
+fn month_year(date: &str) -> String {
+    let mut parts = date.split('-');
+    let year = parts.next().unwrap_or_default();
+    let month = parts.next().and_then(|month| month.parse::<usize>().ok()).filter(|m| (1..=12).contains(m));
+    const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    match month {
+        Some(month) if year.len() == 4 => format!("{} {}", MONTHS[month - 1], year),
+        _ => date.to_string(),
+    }
+}
+
+// Hard wrapped like the built-in pages, blank lines between paragraphs kept
+fn push_wrapped(text: &mut String, paragraphs: &str) {
+    for (i, paragraph) in paragraphs.split("\n\n").enumerate() {
+        if i > 0 {
+            text.push('\n');
+        }
+        for line in textwrap::wrap(paragraph.trim(), WRAP_WIDTH) {
+            text.push_str(&format!("{}\n", line));
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// ui/search.rs
+
+use crate::ui::input::TextInput;
+use crate::ui::markup::Line;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub line: usize,
+    // Char range inside the line's displayed text
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Search {
+    pub input: TextInput,
+    pub editing: bool,
+    pub matches: Vec<Match>,
+    pub current: usize,
+    // Scroll position when the prompt was opened, restored on cancel
+    pub origin: u16,
+}
+
+impl Search {
+    pub fn start(&mut self, scroll_offset: u16) {
+        *self = Self {
+            editing: true,
+            origin: scroll_offset,
+            ..Self::default()
+        };
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.editing || !self.input.is_empty()
+    }
+
+    // Recomputes the matches and selects the first one at or after `from_line`
+    pub fn update(&mut self, content: &str, from_line: usize) {
+        self.matches = content
+            .lines()
+            .enumerate()
+            .flat_map(|(i, line)| {
+                find_matches(Line::parse(line).text, self.input.value())
+                    .into_iter()
+                    .map(move |(start, end)| Match { line: i, start, end })
+            })
+            .collect();
+        self.current = self
+            .matches
+            .iter()
+            .position(|m| m.line >= from_line)
+            .unwrap_or(0);
+    }
+
+    pub fn current_match(&self) -> Option<Match> {
+        self.matches.get(self.current).copied()
+    }
+
+    pub fn next(&mut self) -> Option<Match> {
+        if !self.matches.is_empty() {
+            self.current = (self.current + 1) % self.matches.len();
+        }
+        self.current_match()
+    }
+
+    pub fn prev(&mut self) -> Option<Match> {
+        if !self.matches.is_empty() {
+            self.current = (self.current + self.matches.len() - 1) % self.matches.len();
+        }
+        self.current_match()
+    }
+
+    pub fn matches_on(&self, line: usize) -> impl Iterator<Item = (usize, &Match)> {
+        self.matches
+            .iter()
+            .enumerate()
+            .filter(move |(_, m)| m.line == line)
+    }
+
+    // e.g. "[3/12]", shown next to the prompt
+    pub fn counter(&self) -> String {
+        if self.input.is_empty() {
+            String::new()
+        } else if self.matches.is_empty() {
+            "[no matches]".to_string()
+        } else {
+            format!("[{}/{}]", self.current + 1, self.matches.len())
+        }
+    }
+}
+
+// Smart case: the search is case-insensitive unless the query has an uppercase letter.
+// Returns char ranges so they line up with what is drawn on screen.
+pub fn find_matches(haystack: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let case_sensitive = query.chars().any(char::is_uppercase);
+    let fold = |c: char| {
+        if case_sensitive {
+            c
+        } else {
+            c.to_lowercase().next().unwrap_or(c)
+        }
+    };
+    let haystack: Vec<char> = haystack.chars().map(fold).collect();
+    let needle: Vec<char> = query.chars().map(fold).collect();
+
+    let mut matches = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= haystack.len() {
+        if haystack[i..i + needle.len()] == needle[..] {
+            matches.push((i, i + needle.len()));
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    matches
+}
@@ -0,0 +1,20 @@
+// ui/terminal.rs
+
+use std::io::{self, Write};
+
+use crate::ui::buffer::Buffer;
+
+// Keeps the last frame so only the rows that changed are sent again. A frame
+// of a different size (after a resize) is always drawn in full.
+#[derive(Debug, Default)]
+pub struct Terminal {
+    previous: Option<Buffer>,
+}
+
+impl Terminal {
+    pub fn draw(&mut self, frame: Buffer, out: &mut impl Write) -> io::Result<()> {
+        frame.flush(self.previous.as_ref(), out)?;
+        self.previous = Some(frame);
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+// ui/toc.rs
+
+use crossterm::style::Color;
+
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::layout::{Rect, Size};
+use crate::ui::markup::{truncate, Line, LineKind};
+use crate::ui::widget::{Rule, Widget};
+
+pub const SIDEBAR_WIDTH: u16 = 30;
+// Below this terminal width the sidebar hides itself
+pub const MIN_TERMINAL_WIDTH: u16 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    pub line: usize,
+    // 1 for "# ", 2 for "## ", 3 for "### "
+    pub level: u8,
+    pub text: String,
+}
+
+// Headings of a page, ignoring anything inside code blocks
+pub fn headings(content: &str) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    let mut in_code = false;
+    for (i, raw) in content.lines().enumerate() {
+        let line = Line::parse(raw);
+        if line.kind == LineKind::Fence {
+            in_code = !in_code;
+            continue;
+        }
+        let Some(level) = line.level().filter(|_| !in_code) else {
+            continue;
+        };
+        let text = line.text.trim();
+        if !text.is_empty() {
+            headings.push(Heading { line: i, level, text: text.to_string() });
+        }
+    }
+    headings
+}
+
+// The section the reader is in: the last heading at or just above the top of the view
+pub fn current(headings: &[Heading], scroll_offset: u16) -> Option<usize> {
+    headings
+        .iter()
+        .rposition(|h| h.line <= scroll_offset as usize + 1)
+}
+
+pub fn next(headings: &[Heading], scroll_offset: u16) -> Option<&Heading> {
+    headings.iter().find(|h| h.line > scroll_offset as usize + 1)
+}
+
+pub fn prev(headings: &[Heading], scroll_offset: u16) -> Option<&Heading> {
+    headings.iter().rev().find(|h| h.line < scroll_offset as usize)
+}
+
+// The sidebar: a divider, a title row, a blank row, then the headings
+pub struct TocSidebar {
+    pub headings: Vec<Heading>,
+    pub current: Option<usize>,
+}
+
+impl TocSidebar {
+    pub fn new(content: &str, scroll_offset: u16) -> Self {
+        let headings = headings(content);
+        let current = current(&headings, scroll_offset);
+        Self { headings, current }
+    }
+
+    // Headings shown in `area` with their row, scrolled so the current one is visible
+    pub fn entries(&self, area: Rect) -> Vec<(u16, &Heading, bool)> {
+        let rows = area.height.saturating_sub(2) as usize;
+        let first = self.current.unwrap_or(0).saturating_sub(rows.saturating_sub(1));
+        self.headings
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(rows)
+            .enumerate()
+            .map(|(row, (i, heading))| (area.y + 2 + row as u16, heading, self.current == Some(i)))
+            .collect()
+    }
+}
+
+impl Widget for TocSidebar {
+    fn measure(&self, available: Size) -> Size {
+        Size::new(available.width.min(SIDEBAR_WIDTH + 1), available.height)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let width = area.width.saturating_sub(2) as usize;
+        Rule::vertical(Style::new().fg(Color::DarkGrey)).render(area, buf);
+        buf.set_string_clipped(area.x + 2, area.y, "CONTENTS", Style::new().fg(Color::Grey).bold(), area.right());
+
+        for (y, heading, is_current) in self.entries(area) {
+            let indent = "  ".repeat(heading.level.saturating_sub(1) as usize);
+            let label = format!("{} {}{}", if is_current { "▸" } else { " " }, indent, heading.text);
+            let style = match (is_current, heading.level) {
+                (true, _) => Style::new().fg(Color::Cyan).bold(),
+                (false, 1 | 2) => Style::new().fg(Color::Grey),
+                (false, _) => Style::new().fg(Color::DarkGrey),
+            };
+            buf.set_string(area.x + 1, y, truncate(&label, width), style);
+        }
+    }
+}
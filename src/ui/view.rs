@@ -0,0 +1,91 @@
+// ui/view.rs
+
+use crossterm::event::KeyEvent;
+
+use crate::ui::buffer::Buffer;
+use crate::ui::keymap::{Action, Keymap};
+use crate::ui::layout::Rect;
+use crate::ui::search::Search;
+
+// The content area a page is shown in, and how far it is scrolled
+pub struct Context<'a> {
+    pub keymap: &'a Keymap,
+    pub area: Rect,
+    pub scroll: u16,
+}
+
+impl Context<'_> {
+    // Scrolls to `offset`, no further than the last screen of `text`
+    pub fn scroll_to(&mut self, offset: u16, text: &str) {
+        let lines = text.lines().count() as u16;
+        self.scroll = offset.min(lines.saturating_sub(self.area.height));
+    }
+
+    // Scrolls just enough to show the lines from `top` to `bottom`
+    pub fn show(&mut self, top: usize, bottom: usize, text: &str) {
+        let (top, bottom) = (top as u16, bottom as u16);
+        if top < self.scroll {
+            self.scroll_to(top, text);
+        } else if bottom >= self.scroll + self.area.height {
+            self.scroll_to(bottom + 1 - self.area.height, text);
+        }
+    }
+
+    // Scrolls some context around `line` into view when it is off screen
+    pub fn reveal(&mut self, line: usize, text: &str) {
+        let line = line as u16;
+        let height = self.area.height;
+        if line < self.scroll || line >= self.scroll + height {
+            self.scroll_to(line.saturating_sub(height / 3), text);
+        }
+    }
+
+    // The line of the page shown on screen row `row`
+    pub fn line_at(&self, row: u16) -> usize {
+        (self.scroll + row - self.area.y) as usize
+    }
+}
+
+// How a page took a key, an action or a click
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handled {
+    // Not the page's, the session handles it
+    No,
+    Yes,
+    // The page shows something else now, e.g. an opened project, so the
+    // search no longer applies
+    Replaced,
+}
+
+// A page with state of its own on top of its text, e.g. the selected project.
+// The session owns the scroll position and hands it over in the `Context`.
+pub trait View {
+    fn text(&self, ctx: &Context) -> String;
+
+    // What follows the page name in the breadcrumb
+    fn breadcrumb(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    // Keys of the page's own table, looked up before the keymap
+    fn handle_key(&mut self, _key: &KeyEvent, _ctx: &mut Context) -> Handled {
+        Handled::No
+    }
+
+    fn perform(&mut self, _action: Action, _ctx: &mut Context) -> Handled {
+        Handled::No
+    }
+
+    // A click at `column` of `line`, both counted from the content area
+    fn click(&mut self, _line: usize, _column: usize, _ctx: &mut Context) -> Handled {
+        Handled::No
+    }
+
+    // Opens one of the page's items, or its list for None, e.g. from the finder
+    fn open(&mut self, _item: Option<usize>, _ctx: &mut Context) -> Handled {
+        Handled::No
+    }
+
+    // Drawn over the page's text
+    fn draw(&self, _ctx: &Context, _search: &Search, _buf: &mut Buffer) {}
+}
@@ -0,0 +1,304 @@
+// ui/widget.rs
+
+use crossterm::style::Color;
+
+use crate::ui::buffer::{Buffer, Style};
+use crate::ui::layout::{split, Constraint, Direction, Rect, Size};
+use crate::ui::markup::display_width;
+
+// Something that can draw itself into a rect of a `Buffer`
+pub trait Widget {
+    // The space the widget would like out of `available`, all of it by default
+    fn measure(&self, available: Size) -> Size {
+        available
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer);
+}
+
+impl<W: Widget + ?Sized> Widget for Box<W> {
+    fn measure(&self, available: Size) -> Size {
+        (**self).measure(available)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        (**self).render(area, buf)
+    }
+}
+
+impl<W: Widget + ?Sized> Widget for &W {
+    fn measure(&self, available: Size) -> Size {
+        (**self).measure(available)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        (**self).render(area, buf)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+impl Span {
+    pub fn styled(text: impl Into<String>, style: Style) -> Self {
+        Self { text: text.into(), style }
+    }
+
+    pub fn width(&self) -> usize {
+        display_width(&self.text)
+    }
+}
+
+// Lines of styled spans, each clipped to the width of the area
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Text {
+    pub lines: Vec<Vec<Span>>,
+}
+
+impl Text {
+    pub fn new(lines: Vec<Vec<Span>>) -> Self {
+        Self { lines }
+    }
+
+    pub fn line(spans: Vec<Span>) -> Self {
+        Self::new(vec![spans])
+    }
+}
+
+impl Widget for Text {
+    fn measure(&self, available: Size) -> Size {
+        let width = self
+            .lines
+            .iter()
+            .map(|line| line.iter().map(Span::width).sum::<usize>())
+            .max()
+            .unwrap_or(0);
+        Size::new(
+            (width as u16).min(available.width),
+            (self.lines.len() as u16).min(available.height),
+        )
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        for (row, line) in self.lines.iter().take(area.height as usize).enumerate() {
+            let y = area.y + row as u16;
+            let mut x = area.x;
+            for span in line {
+                x = buf.set_string_clipped(x, y, &span.text, span.style, area.right());
+            }
+        }
+    }
+}
+
+// A line of `symbol` across the area, horizontal or vertical
+pub struct Rule {
+    pub direction: Direction,
+    pub style: Style,
+}
+
+impl Rule {
+    pub fn horizontal(style: Style) -> Self {
+        Self { direction: Direction::Horizontal, style }
+    }
+
+    pub fn vertical(style: Style) -> Self {
+        Self { direction: Direction::Vertical, style }
+    }
+}
+
+impl Widget for Rule {
+    fn measure(&self, available: Size) -> Size {
+        match self.direction {
+            Direction::Horizontal => Size::new(available.width, available.height.min(1)),
+            Direction::Vertical => Size::new(available.width.min(1), available.height),
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        match self.direction {
+            Direction::Horizontal => buf.fill(area.row(0), '─', self.style),
+            Direction::Vertical => buf.fill(Rect::new(area.x, area.y, area.width.min(1), area.height), '│', self.style),
+        }
+    }
+}
+
+// Rounded border with an optional title on the top edge and a hint on the bottom one
+pub struct Block<W> {
+    pub inner: W,
+    pub title: String,
+    pub hint: String,
+    pub style: Style,
+}
+
+impl<W: Widget> Block<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            title: String::new(),
+            hint: String::new(),
+            style: Style::new().fg(Color::Cyan),
+        }
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = hint.into();
+        self
+    }
+}
+
+impl<W: Widget> Widget for Block<W> {
+    fn measure(&self, available: Size) -> Size {
+        let inner = self.inner.measure(Size::new(
+            available.width.saturating_sub(2),
+            available.height.saturating_sub(2),
+        ));
+        Size::new(
+            (inner.width + 2).min(available.width),
+            (inner.height + 2).min(available.height),
+        )
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.width < 2 || area.height < 2 {
+            return;
+        }
+        let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+        buf.fill(area.row(0), '─', self.style);
+        buf.fill(Rect::new(area.x, bottom, area.width, 1), '─', self.style);
+        buf.fill(Rect::new(area.x, area.y, 1, area.height), '│', self.style);
+        buf.fill(Rect::new(right, area.y, 1, area.height), '│', self.style);
+        buf.set_string(area.x, area.y, "╭", self.style);
+        buf.set_string(right, area.y, "╮", self.style);
+        buf.set_string(area.x, bottom, "╰", self.style);
+        buf.set_string(right, bottom, "╯", self.style);
+
+        buf.set_string_clipped(area.x + 2, area.y, &self.title, self.style, right);
+        let hint_x = right.saturating_sub(display_width(&self.hint) as u16).max(area.x + 1);
+        buf.set_string_clipped(hint_x, bottom, &self.hint, self.style, right);
+
+        self.inner.render(area.shrink(1, 1, 1, 1), buf);
+    }
+}
+
+// Empty space around a widget
+pub struct Padding<W> {
+    pub inner: W,
+    pub top: u16,
+    pub right: u16,
+    pub bottom: u16,
+    pub left: u16,
+}
+
+impl<W: Widget> Padding<W> {
+    pub fn new(inner: W, top: u16, right: u16, bottom: u16, left: u16) -> Self {
+        Self { inner, top, right, bottom, left }
+    }
+
+    pub fn horizontal(inner: W, padding: u16) -> Self {
+        Self::new(inner, 0, padding, 0, padding)
+    }
+}
+
+impl<W: Widget> Widget for Padding<W> {
+    fn measure(&self, available: Size) -> Size {
+        let inner = self.inner.measure(Size::new(
+            available.width.saturating_sub(self.left + self.right),
+            available.height.saturating_sub(self.top + self.bottom),
+        ));
+        Size::new(
+            (inner.width + self.left + self.right).min(available.width),
+            (inner.height + self.top + self.bottom).min(available.height),
+        )
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.inner.render(area.shrink(self.top, self.right, self.bottom, self.left), buf);
+    }
+}
+
+// Children laid out one after the other with `layout::split`
+pub struct Stack<'a> {
+    pub direction: Direction,
+    pub children: Vec<(Constraint, Box<dyn Widget + 'a>)>,
+}
+
+impl<'a> Stack<'a> {
+    pub fn vertical() -> Self {
+        Self { direction: Direction::Vertical, children: Vec::new() }
+    }
+
+    pub fn push(mut self, constraint: Constraint, child: impl Widget + 'a) -> Self {
+        self.children.push((constraint, Box::new(child)));
+        self
+    }
+
+    pub fn areas(&self, area: Rect) -> Vec<Rect> {
+        let constraints: Vec<Constraint> = self.children.iter().map(|(c, _)| *c).collect();
+        split(area, self.direction, &constraints)
+    }
+}
+
+impl Widget for Stack<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        for ((_, child), rect) in self.children.iter().zip(self.areas(area)) {
+            if !rect.is_empty() {
+                child.render(rect, buf);
+            }
+        }
+    }
+}
+
+// Draws a widget centered on top of whatever is already there. `margin` is
+// the total room kept free around it on each axis; when less than `min` is
+// left the overlay is not drawn at all.
+pub struct Overlay<W> {
+    pub inner: W,
+    pub max: Size,
+    pub min: Size,
+    pub margin: Size,
+}
+
+impl<W: Widget> Overlay<W> {
+    pub fn new(inner: W, max: Size) -> Self {
+        Self { inner, max, min: Size::default(), margin: Size::default() }
+    }
+
+    pub fn min(mut self, min: Size) -> Self {
+        self.min = min;
+        self
+    }
+
+    pub fn margin(mut self, margin: Size) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    // Where the overlay lands inside `area`, if it fits
+    pub fn area(&self, area: Rect) -> Option<Rect> {
+        let available = Size::new(
+            area.width.saturating_sub(self.margin.width).min(self.max.width),
+            area.height.saturating_sub(self.margin.height).min(self.max.height),
+        );
+        let size = self.inner.measure(available);
+        let size = Size::new(size.width.min(available.width), size.height.min(available.height));
+        (size.width >= self.min.width && size.height >= self.min.height && size.width > 0 && size.height > 0)
+            .then(|| area.centered(size))
+    }
+}
+
+impl<W: Widget> Widget for Overlay<W> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if let Some(rect) = self.area(area) {
+            buf.fill(rect, ' ', Style::default());
+            self.inner.render(rect, buf);
+        }
+    }
+}
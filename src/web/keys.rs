@@ -0,0 +1,198 @@
+// web/keys.rs
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+const PASTE_START: &str = "\x1b[200~";
+const PASTE_END: &str = "\x1b[201~";
+
+// Turns what a terminal emulator sends for key presses, mouse reports and
+// bracketed pastes into the events crossterm reads on the SSH side. Every
+// message from the browser holds whole sequences, so a lone escape at the end
+// is the Esc key.
+pub fn parse(bytes: &[u8]) -> Vec<Event> {
+    let text = String::from_utf8_lossy(bytes);
+    let chars: Vec<char> = text.chars().collect();
+    let mut events = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (event, len) = match chars[i] {
+            '\x1b' if starts_with(&chars[i..], PASTE_START) => paste(&chars[i..]),
+            '\x1b' => escape(&chars[i + 1..]),
+            c => (Some(Event::Key(plain(c))), 1),
+        };
+        events.extend(event);
+        i += len;
+    }
+    events
+}
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+// A byte outside any escape sequence
+fn plain(c: char) -> KeyEvent {
+    match c {
+        '\r' | '\n' => key(KeyCode::Enter, KeyModifiers::NONE),
+        '\t' => key(KeyCode::Tab, KeyModifiers::NONE),
+        '\x7f' | '\x08' => key(KeyCode::Backspace, KeyModifiers::NONE),
+        '\0' => key(KeyCode::Char(' '), KeyModifiers::CONTROL),
+        '\x01'..='\x1a' => {
+            let letter = (c as u8 - 1 + b'a') as char;
+            key(KeyCode::Char(letter), KeyModifiers::CONTROL)
+        }
+        '\x1c'..='\x1f' => {
+            let symbol = (c as u8 - 0x1c + b'4') as char;
+            key(KeyCode::Char(symbol), KeyModifiers::CONTROL)
+        }
+        c if c.is_uppercase() => key(KeyCode::Char(c), KeyModifiers::SHIFT),
+        c => key(KeyCode::Char(c), KeyModifiers::NONE),
+    }
+}
+
+fn starts_with(chars: &[char], prefix: &str) -> bool {
+    chars.iter().copied().take(prefix.len()).eq(prefix.chars())
+}
+
+// `ESC [200~ text ESC [201~`, a paste missing its end runs to the end of the message
+fn paste(chars: &[char]) -> (Option<Event>, usize) {
+    let text = &chars[PASTE_START.len()..];
+    let (text, len) = match (0..text.len()).find(|&i| starts_with(&text[i..], PASTE_END)) {
+        Some(end) => (&text[..end], PASTE_START.len() + end + PASTE_END.len()),
+        None => (text, chars.len()),
+    };
+    (Some(Event::Paste(text.iter().collect())), len)
+}
+
+// The sequence after an escape, and how many chars it took with the escape
+fn escape(rest: &[char]) -> (Option<Event>, usize) {
+    match rest.first() {
+        None => (Some(Event::Key(key(KeyCode::Esc, KeyModifiers::NONE))), 1),
+        Some('[') => csi(&rest[1..]).map_or((None, 2), |(event, len)| (event, len + 2)),
+        Some('O') if rest.len() > 1 => (ss3(rest[1]).map(Event::Key), 3),
+        Some('\x1b') => (Some(Event::Key(key(KeyCode::Esc, KeyModifiers::NONE))), 1),
+        // Meta sends the key after an escape
+        Some(&c) => {
+            let mut event = plain(c);
+            event.modifiers.insert(KeyModifiers::ALT);
+            (Some(Event::Key(event)), 2)
+        }
+    }
+}
+
+// F1-F4 and the arrows in application cursor mode
+fn ss3(c: char) -> Option<KeyEvent> {
+    let code = match c {
+        'P' => KeyCode::F(1),
+        'Q' => KeyCode::F(2),
+        'R' => KeyCode::F(3),
+        'S' => KeyCode::F(4),
+        c => cursor_key(c)?,
+    };
+    Some(key(code, KeyModifiers::NONE))
+}
+
+fn cursor_key(c: char) -> Option<KeyCode> {
+    Some(match c {
+        'A' => KeyCode::Up,
+        'B' => KeyCode::Down,
+        'C' => KeyCode::Right,
+        'D' => KeyCode::Left,
+        'H' => KeyCode::Home,
+        'F' => KeyCode::End,
+        _ => return None,
+    })
+}
+
+// `ESC [ params final`, None when the sequence never ends
+fn csi(rest: &[char]) -> Option<(Option<Event>, usize)> {
+    let end = rest.iter().position(|c| ('@'..='~').contains(c))?;
+    let params: String = rest[..end].iter().collect();
+    let event = match (params.strip_prefix('<'), rest[end]) {
+        (Some(mouse), last @ ('M' | 'm')) => sgr_mouse(mouse, last == 'M').map(Event::Mouse),
+        (None, last) => csi_key(&params, last).map(Event::Key),
+        _ => None,
+    };
+    Some((event, end + 1))
+}
+
+fn csi_key(params: &str, last: char) -> Option<KeyEvent> {
+    let mut numbers = params.split(';').map(|n| n.parse::<u8>().unwrap_or(1));
+    let first = numbers.next().unwrap_or(1);
+    let modifiers = modifiers(numbers.next().unwrap_or(1));
+    let code = match last {
+        'Z' => return Some(key(KeyCode::BackTab, KeyModifiers::SHIFT)),
+        '~' => match first {
+            1 | 7 => KeyCode::Home,
+            2 => KeyCode::Insert,
+            3 => KeyCode::Delete,
+            4 | 8 => KeyCode::End,
+            5 => KeyCode::PageUp,
+            6 => KeyCode::PageDown,
+            11..=15 => KeyCode::F(first - 10),
+            17..=21 => KeyCode::F(first - 11),
+            23 | 24 => KeyCode::F(first - 12),
+            _ => return None,
+        },
+        'P' => KeyCode::F(1),
+        'Q' => KeyCode::F(2),
+        'R' => KeyCode::F(3),
+        'S' => KeyCode::F(4),
+        c => cursor_key(c)?,
+    };
+    Some(key(code, modifiers))
+}
+
+// xterm encodes modifiers as 1 + shift + 2 * alt + 4 * ctrl
+fn modifiers(value: u8) -> KeyModifiers {
+    let bits = value.saturating_sub(1);
+    let mut modifiers = KeyModifiers::NONE;
+    if bits & 1 != 0 {
+        modifiers.insert(KeyModifiers::SHIFT);
+    }
+    if bits & 2 != 0 {
+        modifiers.insert(KeyModifiers::ALT);
+    }
+    if bits & 4 != 0 {
+        modifiers.insert(KeyModifiers::CONTROL);
+    }
+    modifiers
+}
+
+// `ESC [ < button ; column ; row M` (press) or `m` (release), 1-based
+fn sgr_mouse(params: &str, pressed: bool) -> Option<MouseEvent> {
+    let mut numbers = params.split(';').map(|n| n.parse::<u16>().ok());
+    let (button, column, row) = (numbers.next()??, numbers.next()??, numbers.next()??);
+
+    let mut modifiers = KeyModifiers::NONE;
+    if button & 4 != 0 {
+        modifiers.insert(KeyModifiers::SHIFT);
+    }
+    if button & 8 != 0 {
+        modifiers.insert(KeyModifiers::ALT);
+    }
+    if button & 16 != 0 {
+        modifiers.insert(KeyModifiers::CONTROL);
+    }
+    let which = match button & 3 {
+        0 => MouseButton::Left,
+        1 => MouseButton::Middle,
+        _ => MouseButton::Right,
+    };
+    let kind = if button & 64 != 0 {
+        match button & 1 {
+            0 => MouseEventKind::ScrollUp,
+            _ => MouseEventKind::ScrollDown,
+        }
+    } else if button & 32 != 0 {
+        match button & 3 {
+            3 => MouseEventKind::Moved,
+            _ => MouseEventKind::Drag(which),
+        }
+    } else if pressed {
+        MouseEventKind::Down(which)
+    } else {
+        MouseEventKind::Up(which)
+    };
+    Some(MouseEvent { kind, column: column.saturating_sub(1), row: row.saturating_sub(1), modifiers })
+}
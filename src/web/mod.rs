@@ -0,0 +1,292 @@
+// web/mod.rs
+
+pub mod http;
+pub mod keys;
+pub mod pages;
+
+use crossterm::event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture};
+use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, queue};
+use log::{debug, error, info, warn};
+use serde::Deserialize;
+use std::io;
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tungstenite::protocol::WebSocketConfig;
+use tungstenite::{Message, WebSocket};
+
+use crate::config::Config;
+use crate::logging;
+use crate::session::Session;
+use crate::ui::handler::Page;
+use crate::ui::keymap::Keymap;
+use http::{Request, Response};
+
+const INDEX_HTML: &str = include_str!("../../web/index.html");
+const TERMINAL_JS: &str = include_str!("../../web/terminal.js");
+// Served from `[web] assets`, bundled in web/vendor. `serve` refuses to
+// start without them.
+const ASSETS: [(&str, &str); 3] = [
+    ("/xterm.js", "text/javascript; charset=utf-8"),
+    ("/xterm.css", "text/css; charset=utf-8"),
+    ("/addon-fit.js", "text/javascript; charset=utf-8"),
+];
+
+// How long input is waited for before the screen is redrawn anyway
+const POLL: Duration = Duration::from_millis(100);
+// A browser sends its request right away, a slow one is dropped
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+// Key presses and pastes, a paste longer than any form field is cut
+const MAX_MESSAGE: usize = 64 * 1024;
+const MAX_SIZE: u16 = 500;
+
+// What the browser sends as text messages
+#[derive(Debug, Deserialize)]
+struct Resize {
+    cols: u16,
+    rows: u16,
+}
+
+// Serves the pages and the web terminal until the process is stopped, one
+// thread per connection and at most `max_connections` of them
+pub fn serve(config: Config, keymap: Keymap, listen: &str) -> io::Result<()> {
+    check_assets(&config.web.assets)?;
+    let listener = TcpListener::bind(listen).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", listen, e)))?;
+    info!(listen = listen; "web terminal listening");
+    eprintln!("Serving the portfolio on http://{}", listener.local_addr()?);
+
+    let config = Arc::new(config);
+    let sessions = Arc::new(AtomicUsize::new(0));
+    // Every connection holds a thread, slow or idle clients included
+    let connections = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!(error = e.to_string(); "could not accept a connection");
+                continue;
+            }
+        };
+        if connections.fetch_add(1, Ordering::SeqCst) >= config.web.max_connections {
+            connections.fetch_sub(1, Ordering::SeqCst);
+            debug!("too many connections, closing a new one");
+            continue;
+        }
+        let (config, keymap, sessions, connections) =
+            (Arc::clone(&config), keymap.clone(), Arc::clone(&sessions), Arc::clone(&connections));
+        std::thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &config, keymap, &sessions) {
+                warn!(error = e.to_string(); "web request failed");
+            }
+            connections.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream, config: &Config, keymap: Keymap, sessions: &AtomicUsize) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let peer = stream.peer_addr()?;
+    let (request, len) = match http::peek_request(&stream, REQUEST_TIMEOUT) {
+        Ok(request) => request,
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+            return Response::text(400, "Bad request").write(&stream, false);
+        }
+        Err(e) => return Err(e),
+    };
+
+    if request.path == "/ws" && request.is_upgrade() {
+        if !same_origin(&request) {
+            http::consume(&stream, len)?;
+            return Response::text(403, "Cross-origin terminals are not allowed").write(&stream, false);
+        }
+        // Taken before the handshake so two visitors can not both get the last seat
+        if sessions.fetch_add(1, Ordering::SeqCst) >= config.web.max_sessions {
+            sessions.fetch_sub(1, Ordering::SeqCst);
+            http::consume(&stream, len)?;
+            return Response::text(503, "Too many visitors right now, please try again in a minute")
+                .write(&stream, false);
+        }
+        let result = open_terminal(stream, &request, peer, config, keymap);
+        sessions.fetch_sub(1, Ordering::SeqCst);
+        return result;
+    }
+
+    http::consume(&stream, len)?;
+    let head_only = request.method == "HEAD";
+    route(&request, config).write(&stream, head_only)
+}
+
+// The pages, the web terminal and the bundled xterm.js files
+fn route(request: &Request, config: &Config) -> Response {
+    if request.method != "GET" && request.method != "HEAD" {
+        return Response::text(405, "Method not allowed").header("Allow", "GET, HEAD");
+    }
+    if let Some(page) = pages::page_at(&request.path) {
+        return page_response(request, page, config);
+    }
+    match request.path.as_str() {
+        "/terminal" => Response::new(200, "text/html; charset=utf-8", INDEX_HTML)
+            .header("Content-Security-Policy", CSP)
+            .header("Cache-Control", "no-cache"),
+        "/terminal.js" => Response::new(200, "text/javascript; charset=utf-8", TERMINAL_JS)
+            .header("Cache-Control", "no-cache"),
+        path => match ASSETS.iter().find(|(name, _)| *name == path) {
+            Some((name, content_type)) => asset(config, name, content_type),
+            None => Response::text(404, "Not found"),
+        },
+    }
+}
+
+// ANSI for curl and friends, HTML for everyone else
+fn page_response(request: &Request, page: Page, config: &Config) -> Response {
+    let terminal = request.header("user-agent").is_some_and(pages::is_terminal_client);
+    let response = if terminal {
+        let width = pages::width(request.query.as_deref(), request.header("columns"));
+        Response::new(200, "text/plain; charset=utf-8", pages::ansi(page, width))
+    } else {
+        Response::new(200, "text/html; charset=utf-8", pages::html(page, &base_url(request, config))).header("Content-Security-Policy", PAGE_CSP)
+    };
+    response.header("Vary", "User-Agent, Columns").header("Cache-Control", "no-cache")
+}
+
+const PAGE_CSP: &str = "default-src 'none'; style-src 'unsafe-inline'";
+
+// xterm.js styles its rows inline
+const CSP: &str = "default-src 'self'; style-src 'self' 'unsafe-inline'; connect-src 'self' ws: wss:";
+
+// A web terminal that can not load xterm.js is a blank page, better not to serve one
+pub fn check_assets(dir: &Path) -> io::Result<()> {
+    for (name, _) in ASSETS {
+        let path = dir.join(name.trim_start_matches('/'));
+        if !path.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is missing, copy the xterm.js files from web/vendor to {}", path.display(), dir.display()),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn asset(config: &Config, name: &str, content_type: &str) -> Response {
+    let path = config.web.assets.join(name.trim_start_matches('/'));
+    match std::fs::read(&path) {
+        Ok(body) => Response::new(200, content_type, body).header("Cache-Control", "max-age=86400"),
+        Err(e) => {
+            warn!(path = path.display().to_string(), error = e.to_string(); "web asset missing");
+            Response::text(404, "Not found")
+        }
+    }
+}
+
+// Where visitors reach the server, `[web] public_url` or else the Host they asked for
+fn base_url(request: &Request, config: &Config) -> String {
+    match (&config.web.public_url, request.header("host")) {
+        (Some(url), _) => url.clone(),
+        (None, Some(host)) => format!("http://{}", host),
+        (None, None) => format!("http://{}", config.web.listen),
+    }
+}
+
+// Other sites may not open terminals on behalf of their visitors
+fn same_origin(request: &Request) -> bool {
+    let Some(origin) = request.header("origin") else {
+        return true;
+    };
+    let origin_host = origin.split_once("://").map_or(origin, |(_, host)| host);
+    request.header("host") == Some(origin_host)
+}
+
+// The visitor's address, from the proxy in front when configured
+fn client(request: &Request, peer: SocketAddr, config: &Config) -> String {
+    let forwarded = request
+        .header("x-forwarded-for")
+        .filter(|_| config.web.forwarded_for)
+        .and_then(|value| value.rsplit(',').next())
+        .and_then(|address| address.trim().parse::<IpAddr>().ok());
+    forwarded.unwrap_or(peer.ip()).to_string()
+}
+
+fn open_terminal(stream: TcpStream, request: &Request, peer: SocketAddr, config: &Config, keymap: Keymap) -> io::Result<()> {
+    let ws_config =
+        WebSocketConfig { max_message_size: Some(MAX_MESSAGE), max_frame_size: Some(MAX_MESSAGE), ..Default::default() };
+    let socket = tungstenite::accept_with_config(stream, Some(ws_config))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    socket.get_ref().set_read_timeout(Some(POLL))?;
+
+    let session = Session::new(Some(client(request, peer, config)), Some("xterm-256color".to_string()));
+    logging::set_session(&session.id);
+    let mut portfolio = crate::start_session(config, keymap, &session, 80, 24);
+    let result = run_terminal(socket, &mut portfolio, config);
+    if let Err(e) = &result {
+        error!(error = e.to_string(); "session failed");
+    }
+    crate::end_session(config, &session, portfolio);
+    result
+}
+
+// The SSH main loop with the socket in place of the tty: key bytes in,
+// frames out
+fn run_terminal(mut socket: WebSocket<TcpStream>, portfolio: &mut crate::Portfolio, config: &Config) -> io::Result<()> {
+    let idle_timeout = Duration::from_secs(config.web.idle_timeout_secs);
+    let mut out = Vec::new();
+    queue!(out, EnterAlternateScreen, cursor::Hide, EnableBracketedPaste)?;
+    if portfolio.mouse {
+        queue!(out, EnableMouseCapture)?;
+    }
+    portfolio.render(&mut out)?;
+    send(&mut socket, &mut out)?;
+
+    let mut last_input = Instant::now();
+    loop {
+        portfolio.tick();
+        match socket.read() {
+            Ok(Message::Binary(bytes)) => {
+                last_input = Instant::now();
+                if !portfolio.handle_events(keys::parse(&bytes)) {
+                    break;
+                }
+            }
+            Ok(Message::Text(text)) => {
+                if let Ok(size) = serde_json::from_str::<Resize>(&text) {
+                    portfolio.resize(size.cols.clamp(1, MAX_SIZE), size.rows.clamp(1, MAX_SIZE));
+                }
+            }
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e)) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {}
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => return Ok(()),
+            Err(e) => return Err(io::Error::other(e.to_string())),
+        }
+        if last_input.elapsed() > idle_timeout {
+            info!("session idle, closing");
+            break;
+        }
+        portfolio.render(&mut out)?;
+        send(&mut socket, &mut out)?;
+    }
+
+    queue!(out, DisableBracketedPaste, DisableMouseCapture, LeaveAlternateScreen, cursor::Show)?;
+    out.extend_from_slice(format!("\r\n{}\r\n", crate::GOODBYE).as_bytes());
+    send(&mut socket, &mut out)?;
+    // The browser may already be gone, the session is over either way
+    let _ = socket.close(None);
+    let _ = socket.flush();
+    Ok(())
+}
+
+// Sends what was drawn since the last call, if anything
+fn send(socket: &mut WebSocket<TcpStream>, out: &mut Vec<u8>) -> io::Result<()> {
+    if out.is_empty() {
+        return Ok(());
+    }
+    socket
+        .send(Message::Binary(std::mem::take(out)))
+        .map_err(|e| io::Error::other(e.to_string()))
+}
@@ -0,0 +1,154 @@
+// web/pages.rs
+
+use crate::ui::content::full_page;
+use crate::ui::handler::{Page, PageContent};
+use crate::ui::markup::{blocks, escape_html, links, Block};
+
+pub const DEFAULT_WIDTH: u16 = 80;
+pub const MIN_WIDTH: u16 = 40;
+pub const MAX_WIDTH: u16 = 240;
+
+// The pages served over HTTP, the first one at "/"
+pub const PATHS: [(&str, Page); 4] =
+    [("/", Page::Home), ("/projects", Page::Store), ("/about", Page::About), ("/contact", Page::FAQ)];
+
+pub fn page_at(path: &str) -> Option<Page> {
+    let path = match path.trim_end_matches('/') {
+        "" => "/",
+        path => path,
+    };
+    PATHS.iter().find(|(p, _)| *p == path).map(|(_, page)| *page)
+}
+
+fn path_of(page: Page) -> &'static str {
+    PATHS.iter().find(|(_, p)| *p == page).map_or("/", |(path, _)| path)
+}
+
+// curl, wget and HTTPie get the terminal version of a page
+pub fn is_terminal_client(user_agent: &str) -> bool {
+    let agent = user_agent.to_ascii_lowercase();
+    ["curl/", "wget/", "httpie/", "xh/"].iter().any(|name| agent.starts_with(name))
+}
+
+// `?width=N`, then a `Columns: N` header (`curl -H "Columns: $COLUMNS"`)
+pub fn width(query: Option<&str>, columns: Option<&str>) -> u16 {
+    let from_query = query.and_then(|query| {
+        query.split('&').find_map(|pair| match pair.split_once('=') {
+            Some(("width" | "cols" | "columns", value)) => value.parse::<u16>().ok(),
+            _ => None,
+        })
+    });
+    from_query
+        .or_else(|| columns.and_then(|value| value.trim().parse().ok()))
+        .unwrap_or(DEFAULT_WIDTH)
+        .clamp(MIN_WIDTH, MAX_WIDTH)
+}
+
+// The page as the SSH portfolio draws it, banner and navigation included,
+// printed top to bottom instead of scrolled
+pub fn ansi(page: Page, width: u16) -> String {
+    let content = PageContent::get_content(&page);
+    let buf = full_page(page, &content, width);
+
+    let mut out = Vec::new();
+    // Writing into memory does not fail
+    let _ = buf.write_ansi(&mut out);
+    let mut text = String::from_utf8_lossy(&out).into_owned();
+    let others: Vec<&str> = PATHS.iter().filter(|(_, p)| *p != page).map(|(path, _)| *path).collect();
+    text.push_str(&format!("\n  More: {} · ?width=N for wider terminals\n\n", others.join(" ")));
+    text
+}
+
+// The page as plain HTML for browsers, with a link to the web terminal and
+// the curl command for it at `base_url`
+pub fn html(page: Page, base_url: &str) -> String {
+    let content = PageContent::get_content(&page);
+    let mut body = String::new();
+    for block in blocks(&content) {
+        match block {
+            Block::Heading(level, text) => body.push_str(&format!("<h{0}>{1}</h{0}>\n", level, inline(text))),
+            Block::Paragraph(text) => {
+                body.push_str("<p>\n");
+                for line in text {
+                    body.push_str(&format!("{}\n", inline(line)));
+                }
+                body.push_str("</p>\n");
+            }
+            Block::List(items) => {
+                body.push_str("<ul>\n");
+                for item in items {
+                    body.push_str(&format!("<li>{}</li>\n", inline(item)));
+                }
+                body.push_str("</ul>\n");
+            }
+            Block::Code(language, code) => {
+                match language {
+                    Some(language) => body.push_str(&format!("<pre><code class=\"language-{}\">", escape_html(language))),
+                    None => body.push_str("<pre><code>"),
+                }
+                for line in code {
+                    body.push_str(&format!("{}\n", escape_html(line.trim_end())));
+                }
+                body.push_str("</code></pre>\n");
+            }
+            Block::Art(art) => {
+                body.push_str("<pre>");
+                for line in art {
+                    body.push_str(&format!("{}\n", escape_html(line)));
+                }
+                body.push_str("</pre>\n");
+            }
+        }
+    }
+
+    let nav: Vec<String> = PATHS
+        .iter()
+        .map(|(path, p)| {
+            let current = if *p == page { " aria-current=\"page\"" } else { "" };
+            format!("<a href=\"{}\"{}>{}</a>", path, current, p.label())
+        })
+        .collect();
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title} · medaly.engineer</title>
+<style>
+body {{ max-width: 46rem; margin: 2rem auto; padding: 0 1rem; font-family: system-ui, sans-serif; line-height: 1.5; }}
+pre {{ overflow-x: auto; }}
+nav a {{ margin-right: 1rem; }}
+nav a[aria-current] {{ font-weight: bold; }}
+</style>
+</head>
+<body>
+<nav>{nav}</nav>
+<p><a href="/terminal">Open the terminal version</a> or run <code>curl {url}</code> in yours.</p>
+<main>
+{body}</main>
+</body>
+</html>
+"#,
+        title = escape_html(page.label()),
+        nav = nav.join(" "),
+        url = escape_html(&format!("{}{}", base_url.trim_end_matches('/'), path_of(page))),
+        body = body,
+    )
+}
+
+// Escaped text with its URLs as links
+fn inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut at = 0;
+    let chars: Vec<char> = text.chars().collect();
+    for (start, end) in links(text) {
+        let before: String = chars[at..start].iter().collect();
+        let url: String = chars[start..end].iter().collect();
+        out.push_str(&escape_html(&before));
+        out.push_str(&format!("<a href=\"{0}\">{0}</a>", escape_html(&url)));
+        at = end;
+    }
+    out.push_str(&escape_html(&chars[at..].iter().collect::<String>()));
+    out
+}
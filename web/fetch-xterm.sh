@@ -0,0 +1,40 @@
+#!/bin/sh
+# Refreshes the xterm.js files bundled in web/vendor, which the web terminal
+# serves from `[web] assets`, and checks them against web/xterm.sha256,
+# failing on any mismatch. Only needed to bump the pinned versions.
+# Usage: web/fetch-xterm.sh [DIR]            (default: web/vendor)
+#        web/fetch-xterm.sh --record [DIR]   writes web/xterm.sha256 from the
+#                                            download; review it, then commit
+#                                            both with the files
+set -eu
+
+XTERM_VERSION=5.5.0
+FIT_VERSION=0.10.0
+HERE=$(dirname "$0")
+SUMS=$(cd "$HERE" && pwd)/xterm.sha256
+CDN=https://cdn.jsdelivr.net/npm
+
+RECORD=
+if [ "${1:-}" = "--record" ]; then
+    RECORD=1
+    shift
+fi
+DIR=${1:-$HERE/vendor}
+
+if [ -z "$RECORD" ] && [ ! -f "$SUMS" ]; then
+    echo "$SUMS is missing, create it with $0 --record on a trusted machine" >&2
+    exit 1
+fi
+
+mkdir -p "$DIR"
+curl -fsSL -o "$DIR/xterm.js" "$CDN/@xterm/xterm@$XTERM_VERSION/lib/xterm.js"
+curl -fsSL -o "$DIR/xterm.css" "$CDN/@xterm/xterm@$XTERM_VERSION/css/xterm.css"
+curl -fsSL -o "$DIR/addon-fit.js" "$CDN/@xterm/addon-fit@$FIT_VERSION/lib/addon-fit.js"
+
+if [ -n "$RECORD" ]; then
+    (cd "$DIR" && sha256sum xterm.js xterm.css addon-fit.js) > "$SUMS"
+    echo "checksums of xterm.js $XTERM_VERSION and addon-fit $FIT_VERSION written to $SUMS"
+else
+    (cd "$DIR" && sha256sum -c --quiet "$SUMS")
+    echo "xterm.js $XTERM_VERSION and addon-fit $FIT_VERSION saved to $DIR"
+fi
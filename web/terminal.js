@@ -0,0 +1,76 @@
+// web/terminal.js
+//
+// Connects xterm.js to a portfolio session over a WebSocket: key presses go
+// out as binary messages, terminal sizes as JSON text, frames come back as
+// binary escape sequences.
+"use strict";
+
+(function () {
+  const status = document.getElementById("status");
+  if (typeof Terminal === "undefined") {
+    status.textContent = "The terminal could not be loaded (xterm.js is missing on the server).";
+    return;
+  }
+
+  const term = new Terminal({
+    fontFamily: "'JetBrains Mono', 'Fira Code', Menlo, Consolas, monospace",
+    fontSize: 15,
+    cursorBlink: false,
+    theme: { background: "#000000" },
+  });
+  const fit = new FitAddon.FitAddon();
+  term.loadAddon(fit);
+  term.open(document.getElementById("terminal"));
+  fit.fit();
+
+  const scheme = location.protocol === "https:" ? "wss:" : "ws:";
+  const socket = new WebSocket(scheme + "//" + location.host + "/ws");
+  socket.binaryType = "arraybuffer";
+  const encoder = new TextEncoder();
+  const open = () => socket.readyState === WebSocket.OPEN;
+
+  const sendSize = () => {
+    if (open()) {
+      socket.send(JSON.stringify({ cols: term.cols, rows: term.rows }));
+    }
+  };
+
+  socket.addEventListener("open", () => {
+    sendSize();
+    term.focus();
+  });
+  socket.addEventListener("message", (event) => {
+    if (typeof event.data === "string") {
+      term.write(event.data);
+    } else {
+      term.write(new Uint8Array(event.data));
+    }
+  });
+  socket.addEventListener("close", () => {
+    status.textContent = "Session ended. Reload the page to visit again.";
+  });
+
+  term.onData((data) => {
+    if (open()) {
+      socket.send(encoder.encode(data));
+    }
+  });
+  // Mouse reports outside UTF-8, one byte per char
+  term.onBinary((data) => {
+    if (open()) {
+      socket.send(Uint8Array.from(data, (c) => c.charCodeAt(0)));
+    }
+  });
+  term.onResize(sendSize);
+  window.addEventListener("resize", () => fit.fit());
+
+  // OSC 52, sent when a link is clicked to copy it
+  term.parser.registerOscHandler(52, (data) => {
+    const text = data.split(";")[1];
+    if (text && text !== "?" && navigator.clipboard) {
+      const bytes = Uint8Array.from(atob(text), (c) => c.charCodeAt(0));
+      navigator.clipboard.writeText(new TextDecoder().decode(bytes)).catch(() => {});
+    }
+    return true;
+  });
+})();